@@ -0,0 +1,180 @@
+// Human-readable output for campaign and supporter accounts
+
+use anchor_lang::prelude::Pubkey;
+use gkescrow_client::{content, status};
+use gkescrow_client::{
+    Campaign, CampaignAccess, CampaignUpdate, CreatorProfile, CreatorVerification, Dispute, Invitation, SupporterFunding,
+    SupporterProfile,
+};
+use solana_sdk::native_token::lamports_to_sol;
+
+pub fn campaign(address: &Pubkey, campaign: &Campaign, now: i64) {
+    println!("Campaign:        {}", address);
+    println!("Id:              {}", campaign.id);
+    println!("Project:         {}", campaign.project_name);
+    println!("Description:     {}", campaign.description);
+    println!("Content URI:     {}", campaign.content_uri);
+    println!("Content hash:    {}", content::hash_hex(&campaign.content_hash));
+    println!("Creator:         {}", campaign.creator);
+    println!("Creator index:   {}", campaign.creator_index);
+    println!("Verified tier:   {}", campaign.verification_tier);
+    println!("Status:          {:?}", status::status(campaign, now));
+    if let Some(reason) = campaign.takedown_reason {
+        println!("Takedown reason: {:?}", reason);
+    }
+    if campaign.pledge_mint == Pubkey::default() {
+        println!(
+            "Raised:          {} / {} SOL",
+            lamports_to_sol(campaign.raised_lamports),
+            lamports_to_sol(campaign.funding_goal_lamports)
+        );
+    } else {
+        // Pledge campaigns count in base units of the pledge mint
+        println!("Pledged:         {} / {}", campaign.raised_lamports, campaign.funding_goal_lamports);
+        println!("Pledge mint:     {}", campaign.pledge_mint);
+        println!("Pledges:         {} pending, {} failed", campaign.pledges_pending, campaign.pledges_failed);
+    }
+    println!("Supporters:      {}", campaign.supporters_count);
+    match campaign.access {
+        CampaignAccess::Public => println!("Access:          Public"),
+        CampaignAccess::Allowlist { root } => println!("Access:          Allowlist (root {})", content::hash_hex(&root)),
+        CampaignAccess::InviteOnly => println!("Access:          Invite only"),
+    }
+    for (index, goal) in campaign.stretch_goals.iter().enumerate() {
+        let state = if goal.reached_at != 0 { format!("reached at {}", goal.reached_at) } else { "open".to_string() };
+        let threshold = if campaign.pledge_mint == Pubkey::default() {
+            format!("{} SOL", lamports_to_sol(goal.threshold_lamports))
+        } else {
+            goal.threshold_lamports.to_string()
+        };
+        println!("Stretch goal {}:  {} ({}) {}", index + 1, threshold, state, goal.reward_uri);
+    }
+    println!("Created at:      {}", campaign.created_at);
+    println!("Ends at:         {}", campaign.end_timestamp);
+    println!("Claim deadline:  {}", campaign.claim_deadline);
+    println!("Dispute window:  until {} ({} open)", campaign.dispute_deadline, campaign.open_disputes);
+    println!("Reward mode:     {:?}", campaign.reward_mode);
+    println!("NFT:             {} ({})", campaign.nft_name, campaign.nft_symbol);
+    println!("NFT URI:         {}", campaign.nft_uri);
+    println!("NFT mint:        {}", campaign.nft_mint);
+    println!("NFT in escrow:   {}", campaign.nft_in_escrow);
+    println!("Editions:        {} / {}", campaign.editions_minted, campaign.max_editions);
+    if campaign.collection_mint != Pubkey::default() {
+        println!("Collection mint: {}", campaign.collection_mint);
+    }
+    if campaign.share_mint != Pubkey::default() {
+        println!("Share mint:      {}", campaign.share_mint);
+    }
+}
+
+pub fn creator_profile(profile: &CreatorProfile) {
+    println!("Creator:         {}", profile.creator);
+    println!("Display name:    {}", profile.display_name);
+    println!("Avatar URI:      {}", profile.avatar_uri);
+    println!("Launched:        {}", profile.campaign_count);
+    println!("Succeeded:       {}", profile.campaigns_succeeded);
+    println!("Failed:          {}", profile.campaigns_failed);
+    println!("Cancelled:       {}", profile.campaigns_cancelled);
+    println!("Taken down:      {}", profile.campaigns_taken_down);
+    println!("Total raised:    {} SOL", lamports_to_sol(profile.total_raised_lamports));
+    println!("Total refunded:  {} SOL", lamports_to_sol(profile.total_refunded_lamports));
+}
+
+pub fn creator_verification(verification: &CreatorVerification, now: i64) {
+    let state = if verification.expires_at > now { "valid" } else { "expired" };
+    println!("Verified tier:   {} ({})", verification.tier, state);
+    println!("Verified by:     {}", verification.attester);
+    println!("Expires at:      {}", verification.expires_at);
+}
+
+pub fn supporter_profile(profile: &SupporterProfile) {
+    println!("Supporter:       {}", profile.supporter);
+    println!("Contributed:     {} SOL", lamports_to_sol(profile.total_contributed_lamports));
+    println!("Backed:          {} campaign(s)", profile.campaigns_backed);
+    println!("Refunds:         {} ({} SOL)", profile.refunds_received, lamports_to_sol(profile.total_refunded_lamports));
+    println!("NFTs claimed:    {}", profile.nfts_claimed);
+}
+
+pub fn campaign_list(campaigns: &[(Pubkey, Campaign)], now: i64) {
+    for (address, campaign) in campaigns {
+        println!(
+            "{:>5}  {}  {:<12} {:>14} / {:<14} {}",
+            campaign.id,
+            address,
+            format!("{:?}", status::status(campaign, now)),
+            lamports_to_sol(campaign.raised_lamports),
+            lamports_to_sol(campaign.funding_goal_lamports),
+            campaign.project_name
+        );
+    }
+    println!("{} campaign(s)", campaigns.len());
+}
+
+pub fn supporters(supporters: &[(Pubkey, SupporterFunding)]) {
+    for (_, funding) in supporters {
+        let mut flags = Vec::new();
+        if funding.is_claimed {
+            flags.push("refunded".to_string());
+        }
+        if funding.nft_minted {
+            match funding.reward_tier {
+                0 => flags.push("reward claimed".to_string()),
+                tier => flags.push(format!("reward claimed (stretch tier {})", tier)),
+            }
+        }
+        if funding.shares_minted > 0 {
+            flags.push("shares claimed".to_string());
+        }
+        match funding.pledge_status {
+            None => println!(
+                "{}  {:>14} SOL  funded at {}  {}",
+                funding.supporter,
+                lamports_to_sol(funding.amount_lamports),
+                funding.funded_at,
+                flags.join(", ")
+            ),
+            Some(status) => println!(
+                "{}  {:>14}      pledged at {}  {:?}  {}",
+                funding.supporter,
+                funding.amount_lamports,
+                funding.funded_at,
+                status,
+                flags.join(", ")
+            ),
+        }
+    }
+    println!("{} supporter(s)", supporters.len());
+}
+
+pub fn updates(updates: &[(Pubkey, CampaignUpdate)]) {
+    for (_, update) in updates {
+        println!(
+            "{:>4}  posted at {}  {}{}",
+            update.sequence,
+            update.posted_at,
+            update.content_uri,
+            if update.is_milestone { "  [milestone]" } else { "" }
+        );
+    }
+    println!("{} update(s)", updates.len());
+}
+
+pub fn invitations(invitations: &[(Pubkey, Invitation)]) {
+    for (_, invitation) in invitations {
+        println!("{}  invited at {}", invitation.supporter, invitation.invited_at);
+    }
+    println!("{} invitation(s)", invitations.len());
+}
+
+pub fn disputes(disputes: &[(Pubkey, Dispute)]) {
+    for (_, dispute) in disputes {
+        println!(
+            "{}  {:<8}  filed at {}  evidence {}",
+            dispute.supporter,
+            format!("{:?}", dispute.status),
+            dispute.filed_at,
+            content::hash_hex(&dispute.evidence_hash)
+        );
+    }
+    println!("{} dispute(s)", disputes.len());
+}
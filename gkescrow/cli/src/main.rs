@@ -0,0 +1,829 @@
+// Command-line tool for gkescrow creators and admins.
+// Works against any RPC endpoint, including a local solana-test-validator.
+
+mod display;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use gkescrow_client::instructions::{self, InitializeCampaignArgs};
+use gkescrow_client::{
+    accounts, allowlist, content, pda, CampaignAccess, CampaignContent, DisputeResolution, RewardMode,
+    StretchGoalTarget, TakedownReason,
+};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+// Lamports per SOL, as a decimal exponent
+const SOL_DECIMALS: u8 = 9;
+// Pledges settled per collect_pledges transaction, each taking two extra accounts
+const PLEDGES_PER_TRANSACTION: usize = 8;
+
+#[derive(Parser)]
+#[command(name = "gkescrow", about = "Manage GotongKarya crowdfunding campaigns")]
+struct Cli {
+    /// Path to the signer keypair
+    #[arg(short, long, env = "GKESCROW_KEYPAIR", default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// RPC endpoint URL
+    #[arg(short, long, env = "GKESCROW_RPC_URL", default_value = "http://localhost:8899")]
+    url: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a campaign with the signer as creator
+    CreateCampaign {
+        #[arg(long)]
+        name: String,
+        /// Short summary shown alongside the name
+        #[arg(long, default_value = "")]
+        description: String,
+        /// Where the full campaign description is published (Arweave, IPFS or HTTPS)
+        #[arg(long)]
+        content_uri: String,
+        /// Local copy of the JSON document at --content-uri, hashed into the campaign
+        #[arg(long)]
+        content_file: PathBuf,
+        /// Funding goal in SOL, or in pledge tokens with --pledge-mint
+        #[arg(long)]
+        goal: String,
+        /// Mint of the reward NFT the creator will escrow
+        #[arg(long)]
+        nft_mint: Pubkey,
+        #[arg(long)]
+        nft_name: String,
+        #[arg(long)]
+        nft_symbol: String,
+        #[arg(long)]
+        nft_uri: String,
+        #[arg(long, value_enum, default_value_t = RewardModeArg::Transferable)]
+        reward_mode: RewardModeArg,
+        /// Issue fungible share tokens to supporters
+        #[arg(long)]
+        share_tokens: bool,
+        /// Only accept contributions from the wallets listed in this file, one per line
+        #[arg(long, conflicts_with = "invite_only")]
+        allowlist: Option<PathBuf>,
+        /// Only accept contributions from wallets invited with `invite`
+        #[arg(long)]
+        invite_only: bool,
+        /// Take token pledges in this mint, collected only if the campaign succeeds
+        #[arg(long, conflicts_with = "share_tokens")]
+        pledge_mint: Option<Pubkey>,
+    },
+    /// Change a live campaign's name, summary, content or reward URI (creator only)
+    UpdateCampaign {
+        campaign: Pubkey,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        description: Option<String>,
+        #[arg(long, requires = "content_file")]
+        content_uri: Option<String>,
+        #[arg(long, requires = "content_uri")]
+        content_file: Option<PathBuf>,
+        #[arg(long)]
+        nft_uri: Option<String>,
+    },
+    /// Download a campaign's content and check it against the on-chain hash
+    VerifyContent { campaign: Pubkey },
+    /// Post a progress update for supporters (creator only)
+    PostUpdate {
+        campaign: Pubkey,
+        /// Where the update is published (Arweave, IPFS or HTTPS)
+        #[arg(long)]
+        content_uri: String,
+        /// Local copy of the JSON document at --content-uri, hashed into the update
+        #[arg(long)]
+        content_file: PathBuf,
+        /// The update reports reaching a project milestone
+        #[arg(long)]
+        milestone: bool,
+    },
+    /// List a campaign's progress updates
+    Updates { campaign: Pubkey },
+    /// Dispute a funded campaign before its creator can withdraw
+    Dispute {
+        campaign: Pubkey,
+        /// Evidence file; only its SHA-256 hash is stored on-chain
+        #[arg(long)]
+        evidence_file: PathBuf,
+    },
+    /// List the disputes filed against a campaign
+    Disputes { campaign: Pubkey },
+    /// Settle a supporter's dispute (arbitrator or admin)
+    ResolveDispute {
+        campaign: Pubkey,
+        supporter: Pubkey,
+        #[arg(long, value_enum)]
+        resolution: ResolutionArg,
+    },
+    /// Change who may contribute to a running campaign; without options anyone can (creator only)
+    SetAccess {
+        campaign: Pubkey,
+        /// Only accept contributions from the wallets listed in this file, one per line
+        #[arg(long, conflicts_with = "invite_only")]
+        allowlist: Option<PathBuf>,
+        /// Only accept contributions from invited wallets
+        #[arg(long)]
+        invite_only: bool,
+    },
+    /// Replace a running campaign's stretch goals; reached goals must be repeated unchanged (creator only)
+    StretchGoals {
+        campaign: Pubkey,
+        /// AMOUNT=URI: raised total unlocking the goal (SOL, or pledge tokens) and its reward metadata URI.
+        /// Repeat in increasing order; omit to clear unreached goals.
+        #[arg(long = "goal")]
+        goals: Vec<String>,
+    },
+    /// Invite a wallet to contribute to an invite-only campaign (creator only)
+    Invite { campaign: Pubkey, supporter: Pubkey },
+    /// Withdraw a wallet's invitation (creator only)
+    Uninvite { campaign: Pubkey, supporter: Pubkey },
+    /// List the wallets invited to a campaign
+    Invitations { campaign: Pubkey },
+    /// Contribute to a campaign; token campaigns take a pledge instead
+    Fund {
+        campaign: Pubkey,
+        /// Amount in SOL, or in pledge tokens for token campaigns
+        amount: String,
+        /// The campaign's allowlist file, needed to prove membership of an allowlisted campaign
+        #[arg(long)]
+        allowlist: Option<PathBuf>,
+    },
+    /// Collect the pledges of a successful token campaign (anyone)
+    CollectPledges { campaign: Pubkey },
+    /// Withdraw the funds of a successful campaign (creator only)
+    Withdraw { campaign: Pubkey },
+    /// Claim a refund from a failed or cancelled campaign
+    Refund { campaign: Pubkey },
+    /// Take back a contribution or pledge while the campaign is still running
+    Unfund { campaign: Pubkey },
+    /// Cancel a running campaign below its goal (creator only)
+    Cancel { campaign: Pubkey },
+    /// Count a failed campaign in its creator's profile
+    RecordFailure { campaign: Pubkey },
+    /// Move the campaign's reward NFT into escrow (creator only)
+    EscrowNft { campaign: Pubkey },
+    /// Claim the campaign's reward as a supporter: the escrowed NFT or a soulbound badge
+    Claim { campaign: Pubkey },
+    /// Claim share tokens for a contribution
+    ClaimShares { campaign: Pubkey },
+    /// Return an unclaimed escrowed NFT to the creator (creator only)
+    ReclaimNft { campaign: Pubkey },
+    /// Show campaign details
+    Show { campaign: Pubkey },
+    /// Set the signer's creator profile
+    UpdateProfile {
+        #[arg(long)]
+        display_name: String,
+        #[arg(long, default_value = "")]
+        avatar_uri: String,
+        /// Social links; only their hash is stored on-chain
+        #[arg(long, default_value = "")]
+        socials: String,
+    },
+    /// Show a creator's profile and track record (defaults to the signer)
+    Profile { creator: Option<Pubkey> },
+    /// List a campaign's supporters
+    Supporters { campaign: Pubkey },
+    /// Show a supporter's portfolio across campaigns (defaults to the signer)
+    Portfolio { supporter: Option<Pubkey> },
+    /// List all campaigns
+    List {
+        /// Only list campaigns launched by this creator
+        #[arg(long)]
+        creator: Option<Pubkey>,
+    },
+    /// Withdraw platform fees from the treasury (admin only)
+    WithdrawTreasury {
+        /// Amount in SOL, or in tokens with --mint
+        amount: String,
+        /// Withdraw fees collected in this pledge token instead of SOL
+        #[arg(long)]
+        mint: Option<Pubkey>,
+    },
+    /// Stop launches, contributions, withdrawals and reward claims platform-wide (admin only)
+    Pause,
+    /// Lift the platform pause (admin only)
+    Unpause,
+    /// Freeze a flagged campaign; its supporters can still claim refunds (admin only)
+    Freeze { campaign: Pubkey },
+    /// Lift a campaign freeze (admin only)
+    Unfreeze { campaign: Pubkey },
+    /// Appoint the moderator who can take down campaigns (admin only)
+    SetModerator { moderator: Pubkey },
+    /// Appoint the dispute arbitrator and set the dispute window for new campaigns (admin only)
+    ConfigureDisputes {
+        arbitrator: Pubkey,
+        /// Dispute window after a campaign ends, in seconds
+        #[arg(long, default_value_t = gkescrow_client::DEFAULT_DISPUTE_WINDOW_SECONDS)]
+        window: i64,
+    },
+    /// Set how long supporters may withdraw a contribution after making it (admin only)
+    ConfigureWithdrawals {
+        /// Cooling-off period in seconds; omit to allow withdrawals until the campaign ends
+        #[arg(long)]
+        cooling_off: Option<i64>,
+    },
+    /// Appoint the KYC attester and set the goal above which creators must be verified (admin only)
+    ConfigureVerification {
+        attester: Pubkey,
+        /// Goals above this many SOL need a verified creator; omit to disable the check
+        #[arg(long)]
+        threshold: Option<String>,
+    },
+    /// Record a creator's completed KYC (attester or admin)
+    Verify {
+        creator: Pubkey,
+        #[arg(long, default_value_t = 1)]
+        tier: u8,
+        /// Days until the verification expires
+        #[arg(long, default_value_t = 365)]
+        days: i64,
+    },
+    /// Revoke a creator's verification (attester or admin)
+    RevokeVerification { creator: Pubkey },
+    /// Take down a fraudulent campaign and open refunds to all supporters (moderator or admin)
+    Takedown {
+        campaign: Pubkey,
+        #[arg(long, value_enum)]
+        reason: TakedownReasonArg,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum RewardModeArg {
+    Transferable,
+    Soulbound,
+    Frozen,
+}
+
+impl From<RewardModeArg> for RewardMode {
+    fn from(mode: RewardModeArg) -> Self {
+        match mode {
+            RewardModeArg::Transferable => RewardMode::Transferable,
+            RewardModeArg::Soulbound => RewardMode::Soulbound,
+            RewardModeArg::Frozen => RewardMode::Frozen,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TakedownReasonArg {
+    Fraud,
+    Impersonation,
+    ProhibitedContent,
+    IntellectualProperty,
+    Other,
+}
+
+impl From<TakedownReasonArg> for TakedownReason {
+    fn from(reason: TakedownReasonArg) -> Self {
+        match reason {
+            TakedownReasonArg::Fraud => TakedownReason::Fraud,
+            TakedownReasonArg::Impersonation => TakedownReason::Impersonation,
+            TakedownReasonArg::ProhibitedContent => TakedownReason::ProhibitedContent,
+            TakedownReasonArg::IntellectualProperty => TakedownReason::IntellectualProperty,
+            TakedownReasonArg::Other => TakedownReason::Other,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ResolutionArg {
+    /// Dismiss the dispute; the contribution stays with the campaign
+    Release,
+    /// Uphold the dispute and return the contribution to the supporter
+    Refund,
+}
+
+impl From<ResolutionArg> for DisputeResolution {
+    fn from(resolution: ResolutionArg) -> Self {
+        match resolution {
+            ResolutionArg::Release => DisputeResolution::Release,
+            ResolutionArg::Refund => DisputeResolution::Refund,
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+    // Only commands that send transactions need the keypair
+    let keypair_path = expand_home(&cli.keypair);
+    let load_signer = || {
+        read_keypair_file(&keypair_path)
+            .map_err(|err| anyhow!("failed to read keypair {}: {}", keypair_path, err))
+    };
+
+    match cli.command {
+        Command::CreateCampaign {
+            name,
+            description,
+            content_uri,
+            content_file,
+            goal,
+            nft_mint,
+            nft_name,
+            nft_symbol,
+            nft_uri,
+            reward_mode,
+            share_tokens,
+            allowlist,
+            invite_only,
+            pledge_mint,
+        } => {
+            let signer = load_signer()?;
+            let goal = match pledge_mint {
+                Some(mint) => parse_amount(&goal, mint_decimals(&rpc, &mint)?)?,
+                None => parse_sol(&goal)?,
+            };
+            let creator_index = accounts::next_creator_index(&rpc, &signer.pubkey())?;
+            let (campaign, _) = pda::campaign(&signer.pubkey(), creator_index);
+            let reward_mode = RewardMode::from(reward_mode);
+            let mut ixs = vec![instructions::initialize_campaign(
+                &signer.pubkey(),
+                &nft_mint,
+                InitializeCampaignArgs {
+                    campaign_id: accounts::next_campaign_id(&rpc)?,
+                    creator_index,
+                    project_name: name,
+                    description,
+                    content: read_content(&content_uri, &content_file)?,
+                    funding_goal_lamports: goal,
+                    nft_name,
+                    nft_symbol,
+                    nft_uri,
+                    reward_mode,
+                    issue_share_tokens: share_tokens,
+                    creator_verified: accounts::fetch_creator_verification(&rpc, &signer.pubkey())?.is_some(),
+                    access: campaign_access(allowlist.as_deref(), invite_only)?,
+                    pledge_mint,
+                },
+            )];
+            // Soulbound campaigns need their badge mint before supporters can claim
+            if reward_mode != RewardMode::Transferable {
+                ixs.push(instructions::initialize_badge_mint(&campaign, &signer.pubkey(), reward_mode));
+            }
+            send(&rpc, &signer, &ixs)?;
+            println!("Campaign: {}", campaign);
+        }
+        Command::UpdateCampaign {
+            campaign,
+            name,
+            description,
+            content_uri,
+            content_file,
+            nft_uri,
+        } => {
+            let signer = load_signer()?;
+            let content = match (content_uri, content_file) {
+                (Some(uri), Some(file)) => Some(read_content(&uri, &file)?),
+                _ => None,
+            };
+            send(
+                &rpc,
+                &signer,
+                &[instructions::update_campaign_metadata(
+                    &campaign,
+                    &signer.pubkey(),
+                    name,
+                    description,
+                    content,
+                    nft_uri,
+                )],
+            )?;
+        }
+        Command::VerifyContent { campaign } => {
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            let document = content::fetch_verified(&data)?;
+            println!("Content at {} matches the on-chain hash", data.content_uri);
+            println!("{}", serde_json::to_string_pretty(&document)?);
+        }
+        Command::PostUpdate {
+            campaign,
+            content_uri,
+            content_file,
+            milestone,
+        } => {
+            let signer = load_signer()?;
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            let content = read_content(&content_uri, &content_file)?;
+            send(
+                &rpc,
+                &signer,
+                &[instructions::post_update(&campaign, &signer.pubkey(), data.updates_count, content, milestone)],
+            )?;
+            println!("Update: {}", pda::campaign_update(&campaign, data.updates_count).0);
+        }
+        Command::Updates { campaign } => {
+            display::updates(&accounts::fetch_campaign_updates(&rpc, &campaign)?);
+        }
+        Command::Dispute { campaign, evidence_file } => {
+            let signer = load_signer()?;
+            let evidence = fs::read(&evidence_file)
+                .with_context(|| format!("failed to read {}", evidence_file.display()))?;
+            send(
+                &rpc,
+                &signer,
+                &[instructions::file_dispute(&campaign, &signer.pubkey(), hash(&evidence).to_bytes())],
+            )?;
+            println!("Dispute: {}", pda::dispute(&campaign, &signer.pubkey()).0);
+        }
+        Command::Disputes { campaign } => {
+            let mut disputes = accounts::fetch_disputes(&rpc, &campaign)?;
+            disputes.sort_by_key(|(_, dispute)| dispute.filed_at);
+            display::disputes(&disputes);
+        }
+        Command::ResolveDispute {
+            campaign,
+            supporter,
+            resolution,
+        } => {
+            let signer = load_signer()?;
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            send(
+                &rpc,
+                &signer,
+                &[instructions::resolve_dispute(
+                    &campaign,
+                    &data.creator,
+                    &supporter,
+                    &signer.pubkey(),
+                    resolution.into(),
+                )],
+            )?;
+        }
+        Command::SetAccess {
+            campaign,
+            allowlist,
+            invite_only,
+        } => {
+            let signer = load_signer()?;
+            let access = campaign_access(allowlist.as_deref(), invite_only)?;
+            send(&rpc, &signer, &[instructions::set_campaign_access(&campaign, &signer.pubkey(), access)])?;
+        }
+        Command::StretchGoals { campaign, goals } => {
+            let signer = load_signer()?;
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            let decimals = if data.pledge_mint == Pubkey::default() {
+                SOL_DECIMALS
+            } else {
+                mint_decimals(&rpc, &data.pledge_mint)?
+            };
+            let stretch_goals = goals
+                .iter()
+                .map(|goal| {
+                    let (amount, reward_uri) =
+                        goal.split_once('=').ok_or_else(|| anyhow!("expected AMOUNT=URI, got {}", goal))?;
+                    Ok(StretchGoalTarget {
+                        threshold_lamports: parse_amount(amount, decimals)?,
+                        reward_uri: reward_uri.to_string(),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            send(&rpc, &signer, &[instructions::set_stretch_goals(&campaign, &signer.pubkey(), stretch_goals)])?;
+        }
+        Command::Invite { campaign, supporter } => {
+            let signer = load_signer()?;
+            send(&rpc, &signer, &[instructions::invite_supporter(&campaign, &signer.pubkey(), &supporter)])?;
+        }
+        Command::Uninvite { campaign, supporter } => {
+            let signer = load_signer()?;
+            send(&rpc, &signer, &[instructions::revoke_invitation(&campaign, &signer.pubkey(), &supporter)])?;
+        }
+        Command::Invitations { campaign } => {
+            let mut invitations = accounts::fetch_invitations(&rpc, &campaign)?;
+            invitations.sort_by_key(|(_, invitation)| invitation.invited_at);
+            display::invitations(&invitations);
+        }
+        Command::Fund {
+            campaign,
+            amount,
+            allowlist,
+        } => {
+            let signer = load_signer()?;
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            let (proof, invited) = match data.access {
+                CampaignAccess::Public => (Vec::new(), false),
+                CampaignAccess::Allowlist { root } => {
+                    let path = allowlist.ok_or_else(|| anyhow!("campaign is allowlisted; pass --allowlist"))?;
+                    let wallets = read_allowlist(&path)?;
+                    if allowlist::root(&wallets) != Some(root) {
+                        bail!("{} does not match the campaign's allowlist", path.display());
+                    }
+                    let proof = allowlist::proof(&wallets, &signer.pubkey())
+                        .ok_or_else(|| anyhow!("{} is not on the allowlist", signer.pubkey()))?;
+                    (proof, false)
+                }
+                CampaignAccess::InviteOnly => {
+                    if accounts::fetch_invitation(&rpc, &campaign, &signer.pubkey())?.is_none() {
+                        bail!("{} has not been invited to this campaign", signer.pubkey());
+                    }
+                    (Vec::new(), true)
+                }
+            };
+            let ix = if data.pledge_mint == Pubkey::default() {
+                instructions::fund_campaign(&campaign, &signer.pubkey(), parse_sol(&amount)?, proof, invited)
+            } else {
+                let amount = parse_amount(&amount, mint_decimals(&rpc, &data.pledge_mint)?)?;
+                instructions::pledge(&campaign, &signer.pubkey(), &data.pledge_mint, amount, proof, invited)
+            };
+            send(&rpc, &signer, &[ix])?;
+        }
+        Command::CollectPledges { campaign } => {
+            let signer = load_signer()?;
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            let pledges: Vec<(Pubkey, Pubkey)> = accounts::fetch_pending_pledges(&rpc, &campaign)?
+                .into_iter()
+                .map(|(_, funding)| (funding.supporter, funding.pledge_source))
+                .collect();
+            // An empty batch still records the outcome once every pledge is settled
+            if pledges.is_empty() {
+                send(
+                    &rpc,
+                    &signer,
+                    &[instructions::collect_pledges(&campaign, &data.creator, &data.pledge_mint, &signer.pubkey(), &[])],
+                )?;
+            }
+            for batch in pledges.chunks(PLEDGES_PER_TRANSACTION) {
+                send(
+                    &rpc,
+                    &signer,
+                    &[instructions::collect_pledges(&campaign, &data.creator, &data.pledge_mint, &signer.pubkey(), batch)],
+                )?;
+                // Failed pledges can sink the campaign below its goal, which ends collection
+                if !accounts::fetch_campaign(&rpc, &campaign)?.is_funded {
+                    println!("Campaign fell below its goal; collected pledges are refundable");
+                    return Ok(());
+                }
+            }
+            println!("{} pledge(s) settled", pledges.len());
+        }
+        Command::Withdraw { campaign } => {
+            let signer = load_signer()?;
+            send(&rpc, &signer, &[instructions::withdraw_funds(&campaign, &signer.pubkey())])?;
+        }
+        Command::Refund { campaign } => {
+            let signer = load_signer()?;
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            let funding = accounts::fetch_supporter_funding(&rpc, &campaign, &signer.pubkey())?;
+            let share_mint = (funding.shares_minted > 0).then_some(data.share_mint);
+            let pledge = funding.pledge_status.map(|_| (&data.pledge_mint, &funding.pledge_source));
+            send(
+                &rpc,
+                &signer,
+                &[instructions::claim_refund(&campaign, &data.creator, &signer.pubkey(), share_mint.as_ref(), pledge)],
+            )?;
+        }
+        Command::Unfund { campaign } => {
+            let signer = load_signer()?;
+            let funding = accounts::fetch_supporter_funding(&rpc, &campaign, &signer.pubkey())?;
+            let pledge_source = funding.pledge_status.map(|_| funding.pledge_source);
+            send(
+                &rpc,
+                &signer,
+                &[instructions::withdraw_contribution(&campaign, &signer.pubkey(), pledge_source.as_ref())],
+            )?;
+        }
+        Command::Cancel { campaign } => {
+            let signer = load_signer()?;
+            send(&rpc, &signer, &[instructions::cancel_campaign(&campaign, &signer.pubkey())])?;
+        }
+        Command::RecordFailure { campaign } => {
+            let signer = load_signer()?;
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            send(&rpc, &signer, &[instructions::record_campaign_failure(&campaign, &data.creator)])?;
+        }
+        Command::EscrowNft { campaign } => {
+            let signer = load_signer()?;
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            send(
+                &rpc,
+                &signer,
+                &[instructions::transfer_nft_to_escrow(&campaign, &signer.pubkey(), &data.nft_mint)],
+            )?;
+        }
+        Command::Claim { campaign } => {
+            let signer = load_signer()?;
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            let ix = match data.reward_mode {
+                RewardMode::Transferable => {
+                    instructions::claim_nft_from_escrow(&campaign, &signer.pubkey(), &data.nft_mint)
+                }
+                mode => instructions::claim_supporter_badge(&campaign, &signer.pubkey(), mode),
+            };
+            send(&rpc, &signer, &[ix])?;
+        }
+        Command::ClaimShares { campaign } => {
+            let signer = load_signer()?;
+            send(&rpc, &signer, &[instructions::claim_share_tokens(&campaign, &signer.pubkey())])?;
+        }
+        Command::ReclaimNft { campaign } => {
+            let signer = load_signer()?;
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            send(
+                &rpc,
+                &signer,
+                &[instructions::reclaim_escrowed_nft(&campaign, &signer.pubkey(), &data.nft_mint)],
+            )?;
+        }
+        Command::Show { campaign } => {
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            display::campaign(&campaign, &data, cluster_time(&rpc)?);
+        }
+        Command::UpdateProfile {
+            display_name,
+            avatar_uri,
+            socials,
+        } => {
+            let signer = load_signer()?;
+            let socials_hash = if socials.is_empty() { [0; 32] } else { hash(socials.as_bytes()).to_bytes() };
+            send(
+                &rpc,
+                &signer,
+                &[instructions::update_creator_profile(&signer.pubkey(), display_name, avatar_uri, socials_hash)],
+            )?;
+        }
+        Command::Profile { creator } => {
+            let creator = match creator {
+                Some(creator) => creator,
+                None => load_signer()?.pubkey(),
+            };
+            display::creator_profile(&accounts::fetch_creator_profile(&rpc, &creator)?);
+            if let Some(verification) = accounts::fetch_creator_verification(&rpc, &creator)? {
+                display::creator_verification(&verification, cluster_time(&rpc)?);
+            }
+        }
+        Command::Supporters { campaign } => {
+            let mut supporters = accounts::fetch_supporters(&rpc, &campaign)?;
+            supporters.sort_by_key(|(_, funding)| funding.funded_at);
+            display::supporters(&supporters);
+        }
+        Command::Portfolio { supporter } => {
+            let supporter = match supporter {
+                Some(supporter) => supporter,
+                None => load_signer()?.pubkey(),
+            };
+            display::supporter_profile(&accounts::fetch_supporter_profile(&rpc, &supporter)?);
+        }
+        Command::List { creator } => {
+            let now = cluster_time(&rpc)?;
+            let campaigns = match creator {
+                Some(creator) => accounts::fetch_creator_campaigns(&rpc, &creator)?,
+                None => accounts::fetch_registered_campaigns(&rpc)?,
+            };
+            display::campaign_list(&campaigns, now);
+        }
+        Command::WithdrawTreasury { amount, mint } => {
+            let signer = load_signer()?;
+            let ix = match mint {
+                Some(mint) => {
+                    let amount = parse_amount(&amount, mint_decimals(&rpc, &mint)?)?;
+                    instructions::withdraw_treasury_tokens(&signer.pubkey(), &mint, amount)
+                }
+                None => instructions::withdraw_treasury(&signer.pubkey(), parse_sol(&amount)?),
+            };
+            send(&rpc, &signer, &[ix])?;
+        }
+        Command::Pause | Command::Unpause => {
+            let signer = load_signer()?;
+            let paused = matches!(cli.command, Command::Pause);
+            send(&rpc, &signer, &[instructions::set_platform_paused(&signer.pubkey(), paused)])?;
+        }
+        Command::Freeze { campaign } | Command::Unfreeze { campaign } => {
+            let signer = load_signer()?;
+            let frozen = matches!(cli.command, Command::Freeze { .. });
+            send(&rpc, &signer, &[instructions::set_campaign_frozen(&campaign, &signer.pubkey(), frozen)])?;
+        }
+        Command::SetModerator { moderator } => {
+            let signer = load_signer()?;
+            send(&rpc, &signer, &[instructions::set_moderator(&signer.pubkey(), &moderator)])?;
+        }
+        Command::ConfigureDisputes { arbitrator, window } => {
+            let signer = load_signer()?;
+            send(&rpc, &signer, &[instructions::configure_disputes(&signer.pubkey(), &arbitrator, window)])?;
+        }
+        Command::ConfigureWithdrawals { cooling_off } => {
+            let signer = load_signer()?;
+            send(&rpc, &signer, &[instructions::configure_withdrawals(&signer.pubkey(), cooling_off)])?;
+        }
+        Command::ConfigureVerification { attester, threshold } => {
+            let signer = load_signer()?;
+            let threshold = threshold.as_deref().map(parse_sol).transpose()?;
+            send(&rpc, &signer, &[instructions::configure_verification(&signer.pubkey(), &attester, threshold)])?;
+        }
+        Command::Verify { creator, tier, days } => {
+            let signer = load_signer()?;
+            let expires_at = cluster_time(&rpc)? + days * 24 * 60 * 60;
+            send(&rpc, &signer, &[instructions::issue_verification(&signer.pubkey(), &creator, tier, expires_at)])?;
+        }
+        Command::RevokeVerification { creator } => {
+            let signer = load_signer()?;
+            send(&rpc, &signer, &[instructions::revoke_verification(&signer.pubkey(), &creator)])?;
+        }
+        Command::Takedown { campaign, reason } => {
+            let signer = load_signer()?;
+            let data = accounts::fetch_campaign(&rpc, &campaign)?;
+            send(
+                &rpc,
+                &signer,
+                &[instructions::flag_campaign(&campaign, &data.creator, &signer.pubkey(), reason.into())],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn send(rpc: &RpcClient, signer: &Keypair, ixs: &[Instruction]) -> Result<()> {
+    let blockhash = rpc.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&signer.pubkey()), &[signer], blockhash);
+    let signature = rpc.send_and_confirm_transaction(&tx)?;
+    println!("Signature: {}", signature);
+    Ok(())
+}
+
+// Unix timestamp of the latest confirmed block, the clock the program checks against
+fn cluster_time(rpc: &RpcClient) -> Result<i64> {
+    let slot = rpc.get_slot()?;
+    Ok(rpc.get_block_time(slot)?)
+}
+
+// Hash a local copy of the content document that is (or will be) published at `uri`
+fn read_content(uri: &str, path: &Path) -> Result<CampaignContent> {
+    let document = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(content::campaign_content(uri, &document)?)
+}
+
+// Wallet addresses, one per line; blank lines and `#` comments are skipped
+fn read_allowlist(path: &Path) -> Result<Vec<Pubkey>> {
+    let text = fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse().map_err(|_| anyhow!("invalid wallet address in {}: {}", path.display(), line)))
+        .collect()
+}
+
+fn campaign_access(allowlist: Option<&Path>, invite_only: bool) -> Result<CampaignAccess> {
+    if let Some(path) = allowlist {
+        let root = allowlist::root(&read_allowlist(path)?)
+            .ok_or_else(|| anyhow!("{} lists no wallets", path.display()))?;
+        Ok(CampaignAccess::Allowlist { root })
+    } else if invite_only {
+        Ok(CampaignAccess::InviteOnly)
+    } else {
+        Ok(CampaignAccess::Public)
+    }
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home, rest),
+        _ => path.to_string(),
+    }
+}
+
+// Parse a decimal SOL amount into lamports
+fn parse_sol(amount: &str) -> Result<u64> {
+    parse_amount(amount, SOL_DECIMALS)
+}
+
+// Parse a decimal amount into base units of a mint with `decimals` decimals,
+// without going through floats
+fn parse_amount(amount: &str, decimals: u8) -> Result<u64> {
+    let decimals = decimals as usize;
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if fraction.len() > decimals {
+        bail!("{} has more than {} decimal places", amount, decimals);
+    }
+    let whole: u64 = if whole.is_empty() { 0 } else { whole.parse().context("invalid amount")? };
+    let fraction: u64 = if decimals == 0 {
+        0
+    } else {
+        format!("{:0<width$}", fraction, width = decimals).parse().context("invalid amount")?
+    };
+    10u64
+        .checked_pow(decimals as u32)
+        .and_then(|unit| whole.checked_mul(unit))
+        .and_then(|units| units.checked_add(fraction))
+        .ok_or_else(|| anyhow!("{} is too large", amount))
+}
+
+fn mint_decimals(rpc: &RpcClient, mint: &Pubkey) -> Result<u8> {
+    Ok(rpc.get_token_supply(mint)?.decimals)
+}
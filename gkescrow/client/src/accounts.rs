@@ -0,0 +1,247 @@
+// Account fetch and deserialize helpers
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use gkescrow::{
+    Campaign, CampaignRegistry, CampaignRegistryPage, CampaignUpdate, CreatorProfile, CreatorVerification, Dispute,
+    Invitation, OutcomeMetadata, PlatformConfig, PledgeStatus, SupporterFunding, SupporterProfile, CAMPAIGNS_PER_PAGE,
+};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+
+use crate::{pda, ClientError};
+
+// Byte offset of `SupporterFunding::campaign`, right after the discriminator
+const SUPPORTER_FUNDING_CAMPAIGN_OFFSET: usize = 8;
+
+// Byte offset of `Dispute::campaign`, right after the discriminator
+const DISPUTE_CAMPAIGN_OFFSET: usize = 8;
+
+// Byte offset of `Invitation::campaign`, right after the discriminator
+const INVITATION_CAMPAIGN_OFFSET: usize = 8;
+
+// Most accounts getMultipleAccounts returns per request
+const MULTIPLE_ACCOUNTS_LIMIT: usize = 100;
+
+// Deserialize raw account data, checking the account discriminator
+pub fn deserialize<T: AccountDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+    T::try_deserialize(&mut &data[..]).map_err(ClientError::Deserialize)
+}
+
+pub fn fetch<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T, ClientError> {
+    let account = rpc.get_account(address)?;
+    if account.owner != gkescrow::ID {
+        return Err(ClientError::InvalidOwner(*address));
+    }
+    deserialize(&account.data)
+}
+
+// Fetch several accounts of type `T`; accounts that don't exist come back as `None`
+pub fn fetch_multiple<T: AccountDeserialize>(
+    rpc: &RpcClient,
+    addresses: &[Pubkey],
+) -> Result<Vec<(Pubkey, Option<T>)>, ClientError> {
+    let mut results = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(MULTIPLE_ACCOUNTS_LIMIT) {
+        for (address, account) in chunk.iter().zip(rpc.get_multiple_accounts(chunk)?) {
+            let data = match account {
+                Some(account) if account.owner != gkescrow::ID => return Err(ClientError::InvalidOwner(*address)),
+                Some(account) => Some(deserialize(&account.data)?),
+                None => None,
+            };
+            results.push((*address, data));
+        }
+    }
+    Ok(results)
+}
+
+pub fn fetch_campaign(rpc: &RpcClient, campaign: &Pubkey) -> Result<Campaign, ClientError> {
+    fetch(rpc, campaign)
+}
+
+pub fn fetch_supporter_funding(
+    rpc: &RpcClient,
+    campaign: &Pubkey,
+    supporter: &Pubkey,
+) -> Result<SupporterFunding, ClientError> {
+    fetch(rpc, &pda::supporter_funding(campaign, supporter).0)
+}
+
+pub fn fetch_supporter_profile(rpc: &RpcClient, supporter: &Pubkey) -> Result<SupporterProfile, ClientError> {
+    fetch(rpc, &pda::supporter_profile(supporter).0)
+}
+
+pub fn fetch_dispute(rpc: &RpcClient, campaign: &Pubkey, supporter: &Pubkey) -> Result<Dispute, ClientError> {
+    fetch(rpc, &pda::dispute(campaign, supporter).0)
+}
+
+// The supporter's invitation to an invite-only campaign, if they have one
+pub fn fetch_invitation(
+    rpc: &RpcClient,
+    campaign: &Pubkey,
+    supporter: &Pubkey,
+) -> Result<Option<Invitation>, ClientError> {
+    let (invitation, _) = pda::invitation(campaign, supporter);
+    Ok(fetch_multiple(rpc, &[invitation])?.pop().and_then(|(_, invitation)| invitation))
+}
+
+pub fn fetch_outcome_metadata(rpc: &RpcClient, campaign: &Pubkey) -> Result<OutcomeMetadata, ClientError> {
+    fetch(rpc, &pda::outcome_metadata(campaign).0)
+}
+
+// Whether the admin has paused the platform; false before the config exists
+pub fn is_platform_paused(rpc: &RpcClient) -> Result<bool, ClientError> {
+    let (config, _) = pda::platform_config();
+    match fetch_multiple::<PlatformConfig>(rpc, &[config])?.pop() {
+        Some((_, Some(config))) => Ok(config.paused),
+        _ => Ok(false),
+    }
+}
+
+// Id the next campaign will be given; zero until the first campaign creates the registry
+pub fn next_campaign_id(rpc: &RpcClient) -> Result<u64, ClientError> {
+    let (registry, _) = pda::campaign_registry();
+    match fetch_multiple::<CampaignRegistry>(rpc, &[registry])?.pop() {
+        Some((_, Some(registry))) => Ok(registry.campaign_count),
+        _ => Ok(0),
+    }
+}
+
+pub fn fetch_creator_profile(rpc: &RpcClient, creator: &Pubkey) -> Result<CreatorProfile, ClientError> {
+    fetch(rpc, &pda::creator_profile(creator).0)
+}
+
+// Creator index the creator's next campaign will be given; zero before their first campaign
+pub fn next_creator_index(rpc: &RpcClient, creator: &Pubkey) -> Result<u64, ClientError> {
+    let (profile, _) = pda::creator_profile(creator);
+    match fetch_multiple::<CreatorProfile>(rpc, &[profile])?.pop() {
+        Some((_, Some(profile))) => Ok(profile.campaign_count),
+        _ => Ok(0),
+    }
+}
+
+// The creator's verification, if one has been issued and not revoked
+pub fn fetch_creator_verification(
+    rpc: &RpcClient,
+    creator: &Pubkey,
+) -> Result<Option<CreatorVerification>, ClientError> {
+    let (verification, _) = pda::creator_verification(creator);
+    Ok(fetch_multiple(rpc, &[verification])?.pop().and_then(|(_, verification)| verification))
+}
+
+// Every campaign a creator launched, in creation order
+pub fn fetch_creator_campaigns(rpc: &RpcClient, creator: &Pubkey) -> Result<Vec<(Pubkey, Campaign)>, ClientError> {
+    let addresses: Vec<Pubkey> = (0..next_creator_index(rpc, creator)?)
+        .map(|index| pda::campaign(creator, index).0)
+        .collect();
+    Ok(fetch_multiple(rpc, &addresses)?
+        .into_iter()
+        .filter_map(|(address, campaign)| Some((address, campaign?)))
+        .collect())
+}
+
+// A campaign's progress updates in posting order
+pub fn fetch_campaign_updates(
+    rpc: &RpcClient,
+    campaign: &Pubkey,
+) -> Result<Vec<(Pubkey, CampaignUpdate)>, ClientError> {
+    let addresses: Vec<Pubkey> = (0..fetch_campaign(rpc, campaign)?.updates_count)
+        .map(|sequence| pda::campaign_update(campaign, sequence).0)
+        .collect();
+    Ok(fetch_multiple(rpc, &addresses)?
+        .into_iter()
+        .filter_map(|(address, update)| Some((address, update?)))
+        .collect())
+}
+
+// Every registered campaign address in id order, read from the registry pages
+pub fn fetch_registered_campaign_addresses(rpc: &RpcClient) -> Result<Vec<Pubkey>, ClientError> {
+    let page_count = next_campaign_id(rpc)?.div_ceil(CAMPAIGNS_PER_PAGE);
+    let pages: Vec<Pubkey> = (0..page_count).map(|index| pda::campaign_registry_page(index).0).collect();
+    let mut campaigns = Vec::new();
+    for (address, page) in fetch_multiple::<CampaignRegistryPage>(rpc, &pages)? {
+        campaigns.extend(page.ok_or(ClientError::AccountNotFound(address))?.campaigns);
+    }
+    Ok(campaigns)
+}
+
+// Every registered campaign in id order, without a getProgramAccounts scan
+pub fn fetch_registered_campaigns(rpc: &RpcClient) -> Result<Vec<(Pubkey, Campaign)>, ClientError> {
+    let addresses = fetch_registered_campaign_addresses(rpc)?;
+    Ok(fetch_multiple(rpc, &addresses)?
+        .into_iter()
+        .filter_map(|(address, campaign)| Some((address, campaign?)))
+        .collect())
+}
+
+// Fetch every program account of type `T`, optionally narrowed by extra filters
+pub fn fetch_all<T: AccountDeserialize + Discriminator>(
+    rpc: &RpcClient,
+    filters: Vec<RpcFilterType>,
+) -> Result<Vec<(Pubkey, T)>, ClientError> {
+    let mut all_filters = vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, T::DISCRIMINATOR.to_vec()))];
+    all_filters.extend(filters);
+
+    let config = RpcProgramAccountsConfig {
+        filters: Some(all_filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    rpc.get_program_accounts_with_config(&gkescrow::ID, config)?
+        .into_iter()
+        .map(|(address, account)| Ok((address, deserialize(&account.data)?)))
+        .collect()
+}
+
+pub fn fetch_all_campaigns(rpc: &RpcClient) -> Result<Vec<(Pubkey, Campaign)>, ClientError> {
+    fetch_all(rpc, vec![])
+}
+
+// All supporter funding records for a campaign
+pub fn fetch_supporters(rpc: &RpcClient, campaign: &Pubkey) -> Result<Vec<(Pubkey, SupporterFunding)>, ClientError> {
+    fetch_all(
+        rpc,
+        vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            SUPPORTER_FUNDING_CAMPAIGN_OFFSET,
+            campaign.to_bytes().to_vec(),
+        ))],
+    )
+}
+
+// Pledges of a token campaign that collect_pledges hasn't settled yet
+pub fn fetch_pending_pledges(
+    rpc: &RpcClient,
+    campaign: &Pubkey,
+) -> Result<Vec<(Pubkey, SupporterFunding)>, ClientError> {
+    let mut pledges = fetch_supporters(rpc, campaign)?;
+    pledges.retain(|(_, funding)| funding.pledge_status == Some(PledgeStatus::Pending));
+    Ok(pledges)
+}
+
+// Every wallet invited to a campaign
+pub fn fetch_invitations(rpc: &RpcClient, campaign: &Pubkey) -> Result<Vec<(Pubkey, Invitation)>, ClientError> {
+    fetch_all(
+        rpc,
+        vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            INVITATION_CAMPAIGN_OFFSET,
+            campaign.to_bytes().to_vec(),
+        ))],
+    )
+}
+
+// Every dispute filed against a campaign
+pub fn fetch_disputes(rpc: &RpcClient, campaign: &Pubkey) -> Result<Vec<(Pubkey, Dispute)>, ClientError> {
+    fetch_all(
+        rpc,
+        vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            DISPUTE_CAMPAIGN_OFFSET,
+            campaign.to_bytes().to_vec(),
+        ))],
+    )
+}
@@ -0,0 +1,117 @@
+// Merkle allowlists for private campaigns. Leaves are the SHA-256 hash of each
+// wallet address and parents hash their two children in sorted order, matching
+// the on-chain proof check. A node without a sibling moves up a level unchanged.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+
+pub fn leaf(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[wallet.as_ref()]).to_bytes()
+}
+
+fn parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    if left <= right {
+        hashv(&[left, right]).to_bytes()
+    } else {
+        hashv(&[right, left]).to_bytes()
+    }
+}
+
+// Every level of the tree, leaves first; duplicate wallets are dropped
+fn levels(wallets: &[Pubkey]) -> Vec<Vec<[u8; 32]>> {
+    let mut leaves: Vec<[u8; 32]> = wallets.iter().map(leaf).collect();
+    leaves.sort();
+    leaves.dedup();
+
+    let mut levels = vec![leaves];
+    while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+        let next = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => parent(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+// Root to store in the campaign; None for an empty list
+pub fn root(wallets: &[Pubkey]) -> Option<[u8; 32]> {
+    levels(wallets).last()?.first().copied()
+}
+
+// Sibling hashes from the wallet's leaf up to the root; None if the wallet isn't listed
+pub fn proof(wallets: &[Pubkey], wallet: &Pubkey) -> Option<Vec<[u8; 32]>> {
+    let levels = levels(wallets);
+    let mut index = levels[0].binary_search(&leaf(wallet)).ok()?;
+    let mut proof = Vec::new();
+    for level in &levels[..levels.len() - 1] {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        index /= 2;
+    }
+    Some(proof)
+}
+
+// Same check fund_campaign makes
+pub fn verify(root: &[u8; 32], wallet: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    proof.iter().fold(leaf(wallet), |node, sibling| parent(&node, sibling)) == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gkescrow::verify_allowlist_proof;
+
+    fn wallets(count: usize) -> Vec<Pubkey> {
+        (0..count).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    #[test]
+    fn members_prove_on_chain() {
+        // Odd sizes leave nodes without a sibling on some levels
+        for count in [2, 3, 5, 8, 13] {
+            let wallets = wallets(count);
+            let root = root(&wallets).unwrap();
+            for wallet in &wallets {
+                let proof = proof(&wallets, wallet).unwrap();
+                assert!(verify_allowlist_proof(&root, wallet, &proof), "{} wallets", count);
+                assert!(verify(&root, wallet, &proof));
+            }
+        }
+    }
+
+    #[test]
+    fn non_members_are_rejected_on_chain() {
+        let wallets = wallets(5);
+        let root = root(&wallets).unwrap();
+        let outsider = Pubkey::new_unique();
+        assert!(proof(&wallets, &outsider).is_none());
+
+        // A member's proof doesn't carry over to another wallet
+        let member_proof = proof(&wallets, &wallets[0]).unwrap();
+        assert!(!verify_allowlist_proof(&root, &outsider, &member_proof));
+        assert!(!verify_allowlist_proof(&root, &outsider, &[]));
+    }
+
+    #[test]
+    fn single_wallet_root_is_its_leaf() {
+        let wallets = wallets(1);
+        let root = root(&wallets).unwrap();
+        assert_eq!(root, leaf(&wallets[0]));
+
+        let proof = proof(&wallets, &wallets[0]).unwrap();
+        assert!(proof.is_empty());
+        assert!(verify_allowlist_proof(&root, &wallets[0], &proof));
+        assert!(!verify_allowlist_proof(&root, &Pubkey::new_unique(), &proof));
+    }
+
+    #[test]
+    fn empty_list_has_no_root() {
+        assert!(root(&[]).is_none());
+    }
+}
@@ -0,0 +1,113 @@
+// Off-chain campaign content. A campaign stores the URI of its full description
+// and the SHA-256 hash of that document as canonical JSON: object keys sorted,
+// no insignificant whitespace. Fetched content is only trusted if it matches.
+
+use std::time::Duration;
+
+use anchor_lang::solana_program::hash::hash;
+use gkescrow::{Campaign, CampaignContent};
+use serde_json::Value;
+
+use crate::ClientError;
+
+const IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+const ARWEAVE_GATEWAY: &str = "https://arweave.net/";
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Re-serialize a JSON document in canonical form
+pub fn canonicalize(document: &[u8]) -> Result<Vec<u8>, ClientError> {
+    // serde_json keeps object keys in a sorted map and writes compact output
+    let value: Value = serde_json::from_slice(document).map_err(ClientError::InvalidContent)?;
+    serde_json::to_vec(&value).map_err(ClientError::InvalidContent)
+}
+
+// SHA-256 of the canonical form of a JSON document
+pub fn content_hash(document: &[u8]) -> Result<[u8; 32], ClientError> {
+    Ok(hash(&canonicalize(document)?).to_bytes())
+}
+
+// The content argument for a campaign whose description is published at `uri`
+pub fn campaign_content(uri: &str, document: &[u8]) -> Result<CampaignContent, ClientError> {
+    Ok(CampaignContent {
+        uri: uri.to_string(),
+        hash: content_hash(document)?,
+    })
+}
+
+// Check a fetched document against the hash anchored in the campaign
+pub fn verify(campaign: &Campaign, document: &[u8]) -> Result<(), ClientError> {
+    if content_hash(document)? != campaign.content_hash {
+        return Err(ClientError::ContentHashMismatch);
+    }
+    Ok(())
+}
+
+// Hex form of a content hash, as printed by `sha256sum` for canonical documents
+pub fn hash_hex(hash: &[u8; 32]) -> String {
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// HTTPS URL to download a content URI from, going through public gateways
+// for IPFS and Arweave
+pub fn gateway_url(uri: &str) -> Result<String, ClientError> {
+    if let Some(path) = uri.strip_prefix("ipfs://") {
+        Ok(format!("{}{}", IPFS_GATEWAY, path.trim_start_matches("ipfs/")))
+    } else if let Some(path) = uri.strip_prefix("ar://") {
+        Ok(format!("{}{}", ARWEAVE_GATEWAY, path))
+    } else if uri.starts_with("https://") || uri.starts_with("http://") {
+        Ok(uri.to_string())
+    } else {
+        Err(ClientError::UnsupportedUri(uri.to_string()))
+    }
+}
+
+// Download a campaign's content and verify it, returning the parsed document
+pub fn fetch_verified(campaign: &Campaign) -> Result<Value, ClientError> {
+    let url = gateway_url(&campaign.content_uri)?;
+    let client = reqwest::blocking::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(|err| ClientError::Http(Box::new(err)))?;
+    let document = client
+        .get(url)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.bytes())
+        .map_err(|err| ClientError::Http(Box::new(err)))?;
+
+    verify(campaign, &document)?;
+    serde_json::from_slice(&document).map_err(ClientError::InvalidContent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Keys out of order, nested, with whitespace. The expected bytes rely on
+    // serde_json's default sorted object map: enabling its `preserve_order`
+    // feature anywhere in the build would change every content hash.
+    const DOCUMENT: &[u8] = br#"{
+        "title": "Solar kit",
+        "b": [1, 2.5, "x"],
+        "a": { "z": null, "y": true }
+    }"#;
+    const CANONICAL: &[u8] = br#"{"a":{"y":true,"z":null},"b":[1,2.5,"x"],"title":"Solar kit"}"#;
+    const CANONICAL_SHA256: &str = "28b218d70a62b8d451815bc05603b34f60f9d0d652f465e249afb73fa86dbe1f";
+
+    #[test]
+    fn canonicalize_sorts_keys_and_drops_whitespace() {
+        assert_eq!(canonicalize(DOCUMENT).unwrap(), CANONICAL);
+        assert_eq!(canonicalize(CANONICAL).unwrap(), CANONICAL);
+    }
+
+    #[test]
+    fn content_hash_matches_sha256sum_of_canonical_form() {
+        assert_eq!(hash_hex(&content_hash(DOCUMENT).unwrap()), CANONICAL_SHA256);
+        assert_eq!(content_hash(DOCUMENT).unwrap(), content_hash(CANONICAL).unwrap());
+    }
+
+    #[test]
+    fn invalid_json_is_rejected() {
+        assert!(matches!(canonicalize(b"{\"title\":"), Err(ClientError::InvalidContent(_))));
+    }
+}
@@ -0,0 +1,258 @@
+// Decoding of program events and instruction data from confirmed transactions.
+// Events are read both from `Program data:` log lines (`emit!`) and from the
+// self-CPI instructions created by `emit_cpi!`.
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use gkescrow::{
+    BadgeClaimed, BadgeMintInitialized, CampaignAccessSet, CampaignCancelled, CampaignCollectionSet,
+    CampaignCollectionVerified, CampaignCreated, CampaignDelivered, CampaignFailed, CampaignFreezeSet,
+    CampaignMetadataUpdated, CampaignTakenDown, Contributed, ContributionWithdrawn, CreatorProfileUpdated,
+    CreatorVerified, DisputeFiled, DisputeResolved, DisputesConfigured, FundsWithdrawn, GoalReached,
+    InvitationRevoked, ModeratorSet, NftClaimed, NftEscrowed, NftReclaimed, OutcomeMetadataSet,
+    PlatformCollectionCreated, PlatformPauseSet, PledgeCollected, PledgeFailed, Pledged, RefundClaimed,
+    RewardMetadataUpdated, RewardVerified, SharesClaimed, StretchGoalReached, StretchGoalsSet, SupporterInvited,
+    TreasuryTokensWithdrawn, TreasuryWithdrawn, UpdatePosted, VerificationConfigured, VerificationRevoked,
+    WithdrawalsConfigured,
+};
+
+macro_rules! program_events {
+    ($($event:ident),* $(,)?) => {
+        pub enum ProgramEvent {
+            $($event($event),)*
+        }
+
+        impl ProgramEvent {
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(ProgramEvent::$event(_) => stringify!($event),)*
+                }
+            }
+        }
+
+        // Decode an event from its discriminator-prefixed serialized form
+        pub fn decode(data: &[u8]) -> Option<ProgramEvent> {
+            $(
+                if let Some(mut payload) = data.strip_prefix($event::DISCRIMINATOR) {
+                    return $event::deserialize(&mut payload).ok().map(ProgramEvent::$event);
+                }
+            )*
+            None
+        }
+    };
+}
+
+program_events!(
+    CampaignCreated,
+    Contributed,
+    GoalReached,
+    FundsWithdrawn,
+    RefundClaimed,
+    TreasuryWithdrawn,
+    NftEscrowed,
+    NftClaimed,
+    NftReclaimed,
+    BadgeMintInitialized,
+    BadgeClaimed,
+    PlatformCollectionCreated,
+    CampaignCollectionSet,
+    RewardVerified,
+    CampaignCollectionVerified,
+    CampaignDelivered,
+    OutcomeMetadataSet,
+    RewardMetadataUpdated,
+    SharesClaimed,
+    CreatorProfileUpdated,
+    CampaignCancelled,
+    CampaignFailed,
+    CampaignMetadataUpdated,
+    UpdatePosted,
+    PlatformPauseSet,
+    CampaignFreezeSet,
+    ModeratorSet,
+    CampaignTakenDown,
+    DisputesConfigured,
+    DisputeFiled,
+    DisputeResolved,
+    VerificationConfigured,
+    CreatorVerified,
+    VerificationRevoked,
+    CampaignAccessSet,
+    SupporterInvited,
+    InvitationRevoked,
+    Pledged,
+    PledgeCollected,
+    PledgeFailed,
+    TreasuryTokensWithdrawn,
+    WithdrawalsConfigured,
+    ContributionWithdrawn,
+    StretchGoalsSet,
+    StretchGoalReached,
+);
+
+impl ProgramEvent {
+    // Campaign the event belongs to, if any
+    pub fn campaign(&self) -> Option<Pubkey> {
+        match self {
+            ProgramEvent::CampaignCreated(event) => Some(event.campaign),
+            ProgramEvent::Contributed(event) => Some(event.campaign),
+            ProgramEvent::GoalReached(event) => Some(event.campaign),
+            ProgramEvent::FundsWithdrawn(event) => Some(event.campaign),
+            ProgramEvent::RefundClaimed(event) => Some(event.campaign),
+            ProgramEvent::NftEscrowed(event) => Some(event.campaign),
+            ProgramEvent::NftClaimed(event) => Some(event.campaign),
+            ProgramEvent::NftReclaimed(event) => Some(event.campaign),
+            ProgramEvent::BadgeMintInitialized(event) => Some(event.campaign),
+            ProgramEvent::BadgeClaimed(event) => Some(event.campaign),
+            ProgramEvent::CampaignCollectionSet(event) => Some(event.campaign),
+            ProgramEvent::RewardVerified(event) => Some(event.campaign),
+            ProgramEvent::CampaignCollectionVerified(event) => Some(event.campaign),
+            ProgramEvent::CampaignDelivered(event) => Some(event.campaign),
+            ProgramEvent::OutcomeMetadataSet(event) => Some(event.campaign),
+            ProgramEvent::RewardMetadataUpdated(event) => Some(event.campaign),
+            ProgramEvent::SharesClaimed(event) => Some(event.campaign),
+            ProgramEvent::CampaignCancelled(event) => Some(event.campaign),
+            ProgramEvent::CampaignFailed(event) => Some(event.campaign),
+            ProgramEvent::CampaignMetadataUpdated(event) => Some(event.campaign),
+            ProgramEvent::UpdatePosted(event) => Some(event.campaign),
+            ProgramEvent::CampaignFreezeSet(event) => Some(event.campaign),
+            ProgramEvent::CampaignTakenDown(event) => Some(event.campaign),
+            ProgramEvent::DisputeFiled(event) => Some(event.campaign),
+            ProgramEvent::DisputeResolved(event) => Some(event.campaign),
+            ProgramEvent::CampaignAccessSet(event) => Some(event.campaign),
+            ProgramEvent::SupporterInvited(event) => Some(event.campaign),
+            ProgramEvent::InvitationRevoked(event) => Some(event.campaign),
+            ProgramEvent::Pledged(event) => Some(event.campaign),
+            ProgramEvent::PledgeCollected(event) => Some(event.campaign),
+            ProgramEvent::PledgeFailed(event) => Some(event.campaign),
+            ProgramEvent::ContributionWithdrawn(event) => Some(event.campaign),
+            ProgramEvent::StretchGoalsSet(event) => Some(event.campaign),
+            ProgramEvent::StretchGoalReached(event) => Some(event.campaign),
+            ProgramEvent::TreasuryWithdrawn(_)
+            | ProgramEvent::TreasuryTokensWithdrawn(_)
+            | ProgramEvent::PlatformCollectionCreated(_)
+            | ProgramEvent::CreatorProfileUpdated(_)
+            | ProgramEvent::PlatformPauseSet(_)
+            | ProgramEvent::ModeratorSet(_)
+            | ProgramEvent::DisputesConfigured(_)
+            | ProgramEvent::VerificationConfigured(_)
+            | ProgramEvent::CreatorVerified(_)
+            | ProgramEvent::VerificationRevoked(_)
+            | ProgramEvent::WithdrawalsConfigured(_) => None,
+        }
+    }
+
+    // Supporter whose funding record the event changed, if any
+    pub fn supporter(&self) -> Option<Pubkey> {
+        match self {
+            ProgramEvent::Contributed(event) => Some(event.supporter),
+            ProgramEvent::RefundClaimed(event) => Some(event.supporter),
+            ProgramEvent::NftClaimed(event) => Some(event.supporter),
+            ProgramEvent::BadgeClaimed(event) => Some(event.supporter),
+            ProgramEvent::SharesClaimed(event) => Some(event.supporter),
+            ProgramEvent::DisputeFiled(event) => Some(event.supporter),
+            ProgramEvent::DisputeResolved(event) => Some(event.supporter),
+            ProgramEvent::Pledged(event) => Some(event.supporter),
+            ProgramEvent::PledgeCollected(event) => Some(event.supporter),
+            ProgramEvent::PledgeFailed(event) => Some(event.supporter),
+            ProgramEvent::ContributionWithdrawn(event) => Some(event.supporter),
+            _ => None,
+        }
+    }
+}
+
+// Decode the data of an `emit_cpi!` self-invocation
+pub fn decode_cpi(instruction_data: &[u8]) -> Option<ProgramEvent> {
+    decode(instruction_data.strip_prefix(EVENT_IX_TAG_LE)?)
+}
+
+// Events logged by the gkescrow program itself, skipping `Program data:` lines
+// written by other programs in the same transaction
+pub fn from_logs(logs: &[String]) -> Vec<ProgramEvent> {
+    let program_id = gkescrow::ID.to_string();
+    let mut invocations: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        if let Some(data) = rest.strip_prefix("data: ") {
+            if invocations.last() == Some(&program_id.as_str()) {
+                if let Some(event) = BASE64_STANDARD.decode(data).ok().and_then(|data| decode(&data)) {
+                    events.push(event);
+                }
+            }
+        } else if let Some((program, status)) = rest.split_once(' ') {
+            if status.starts_with("invoke [") {
+                invocations.push(program);
+            } else if status == "success" || status.starts_with("failed") {
+                invocations.pop();
+            }
+        }
+    }
+    events
+}
+
+macro_rules! instruction_names {
+    ($($instruction:ident),* $(,)?) => {
+        // Name of a gkescrow instruction from its data, e.g. "FundCampaign"
+        pub fn instruction_name(data: &[u8]) -> Option<&'static str> {
+            $(
+                if data.starts_with(gkescrow::instruction::$instruction::DISCRIMINATOR) {
+                    return Some(stringify!($instruction));
+                }
+            )*
+            None
+        }
+    };
+}
+
+instruction_names!(
+    InitializeCampaign,
+    FundCampaign,
+    WithdrawFunds,
+    ClaimRefund,
+    WithdrawTreasury,
+    TransferNftToEscrow,
+    ClaimNftFromEscrow,
+    UpdateSupporterNftMint,
+    InitializeBadgeMint,
+    ClaimSupporterBadge,
+    ReclaimEscrowedNft,
+    InitializePlatformCollection,
+    InitializeCampaignCollection,
+    SetCampaignCollection,
+    VerifyRewardCollection,
+    VerifyCampaignCollection,
+    MarkCampaignDelivered,
+    SetOutcomeMetadata,
+    UpdateRewardMetadata,
+    ClaimShareTokens,
+    UpdateCreatorProfile,
+    CancelCampaign,
+    RecordCampaignFailure,
+    UpdateCampaignMetadata,
+    PostUpdate,
+    SetPlatformPaused,
+    SetCampaignFrozen,
+    SetModerator,
+    FlagCampaign,
+    ConfigureDisputes,
+    FileDispute,
+    ResolveDispute,
+    ConfigureVerification,
+    IssueVerification,
+    RevokeVerification,
+    SetCampaignAccess,
+    InviteSupporter,
+    RevokeInvitation,
+    Pledge,
+    CollectPledges,
+    WithdrawTreasuryTokens,
+    ConfigureWithdrawals,
+    WithdrawContribution,
+    SetStretchGoals,
+);
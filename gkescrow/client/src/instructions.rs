@@ -0,0 +1,867 @@
+// Typed instruction builders for the gkescrow program.
+// PDAs and associated token accounts are derived here so callers only pass
+// the wallets and mints involved.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use anchor_spl::{metadata, token, token_2022};
+use gkescrow::{CampaignAccess, CampaignContent, DisputeResolution, RewardMode, StretchGoalTarget, TakedownReason};
+
+use crate::pda;
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: gkescrow::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, &token::ID)
+}
+
+// Token program that owns a campaign's badge mint
+fn badge_token_program(reward_mode: RewardMode) -> Pubkey {
+    match reward_mode {
+        RewardMode::Soulbound => token_2022::ID,
+        RewardMode::Transferable | RewardMode::Frozen => token::ID,
+    }
+}
+
+// Arguments for creating a campaign. `campaign_id` and `creator_index` are the
+// current registry and creator counters (see `accounts::next_campaign_id` and
+// `accounts::next_creator_index`); they pick the registry page and campaign address.
+// `content` points at the full description (see `content::campaign_content`).
+// `creator_verified` passes the creator's verification account, which must exist
+// (see `accounts::fetch_creator_verification`). `pledge_mint` makes it a token
+// campaign that takes pledges instead of SOL.
+#[derive(Clone, Debug)]
+pub struct InitializeCampaignArgs {
+    pub campaign_id: u64,
+    pub creator_index: u64,
+    pub project_name: String,
+    pub description: String,
+    pub content: CampaignContent,
+    pub funding_goal_lamports: u64,
+    pub nft_name: String,
+    pub nft_symbol: String,
+    pub nft_uri: String,
+    pub reward_mode: RewardMode,
+    pub issue_share_tokens: bool,
+    pub creator_verified: bool,
+    pub access: CampaignAccess,
+    pub pledge_mint: Option<Pubkey>,
+}
+
+pub fn initialize_campaign(creator: &Pubkey, nft_mint: &Pubkey, args: InitializeCampaignArgs) -> Instruction {
+    let (campaign, _) = pda::campaign(creator, args.creator_index);
+    let (share_mint, token_program) = if args.issue_share_tokens {
+        (Some(pda::share_mint(&campaign).0), Some(token::ID))
+    } else {
+        (None, None)
+    };
+
+    instruction(
+        gkescrow::accounts::InitializeCampaign {
+            creator: *creator,
+            creator_profile: pda::creator_profile(creator).0,
+            campaign,
+            nft_mint: *nft_mint,
+            campaign_registry: pda::campaign_registry().0,
+            platform_config: pda::platform_config().0,
+            creator_verification: args.creator_verified.then(|| pda::creator_verification(creator).0),
+            registry_page: pda::campaign_registry_page(args.campaign_id / gkescrow::CAMPAIGNS_PER_PAGE).0,
+            share_mint,
+            pledge_mint: args.pledge_mint,
+            token_program,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::InitializeCampaign {
+            project_name: args.project_name,
+            description: args.description,
+            content: args.content,
+            funding_goal_lamports: args.funding_goal_lamports,
+            nft_name: args.nft_name,
+            nft_symbol: args.nft_symbol,
+            nft_uri: args.nft_uri,
+            reward_mode: args.reward_mode,
+            access: args.access,
+        },
+    )
+}
+
+// `allowlist_proof` comes from `allowlist::proof` for allowlisted campaigns;
+// `invited` passes the supporter's invitation to an invite-only campaign
+pub fn fund_campaign(
+    campaign: &Pubkey,
+    supporter: &Pubkey,
+    amount_lamports: u64,
+    allowlist_proof: Vec<[u8; 32]>,
+    invited: bool,
+) -> Instruction {
+    instruction(
+        gkescrow::accounts::FundCampaign {
+            campaign: *campaign,
+            platform_config: pda::platform_config().0,
+            supporter: *supporter,
+            supporter_funding: pda::supporter_funding(campaign, supporter).0,
+            supporter_profile: pda::supporter_profile(supporter).0,
+            invitation: invited.then(|| pda::invitation(campaign, supporter).0),
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::FundCampaign {
+            amount_lamports,
+            allowlist_proof,
+        },
+    )
+}
+
+// Pledges from the supporter's associated token account for `pledge_mint`
+pub fn pledge(
+    campaign: &Pubkey,
+    supporter: &Pubkey,
+    pledge_mint: &Pubkey,
+    amount: u64,
+    allowlist_proof: Vec<[u8; 32]>,
+    invited: bool,
+) -> Instruction {
+    instruction(
+        gkescrow::accounts::Pledge {
+            campaign: *campaign,
+            platform_config: pda::platform_config().0,
+            supporter: *supporter,
+            supporter_funding: pda::supporter_funding(campaign, supporter).0,
+            supporter_profile: pda::supporter_profile(supporter).0,
+            invitation: invited.then(|| pda::invitation(campaign, supporter).0),
+            supporter_token_account: ata(supporter, pledge_mint),
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::Pledge {
+            amount,
+            allowlist_proof,
+        },
+    )
+}
+
+// `pledges` are (supporter, pledge source token account) pairs, see
+// `accounts::fetch_pending_pledges`; transaction size limits a batch to a few dozen
+pub fn collect_pledges(
+    campaign: &Pubkey,
+    creator: &Pubkey,
+    pledge_mint: &Pubkey,
+    collector: &Pubkey,
+    pledges: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let (treasury, _) = pda::treasury();
+    let mut ix = instruction(
+        gkescrow::accounts::CollectPledges {
+            campaign: *campaign,
+            platform_config: pda::platform_config().0,
+            collector: *collector,
+            pledge_mint: *pledge_mint,
+            pledge_escrow: ata(campaign, pledge_mint),
+            creator: *creator,
+            creator_token_account: ata(creator, pledge_mint),
+            creator_profile: pda::creator_profile(creator).0,
+            treasury,
+            treasury_token_account: ata(&treasury, pledge_mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::CollectPledges {},
+    );
+    for (supporter, source) in pledges {
+        ix.accounts.push(AccountMeta::new(pda::supporter_funding(campaign, supporter).0, false));
+        ix.accounts.push(AccountMeta::new(*source, false));
+    }
+    ix
+}
+
+pub fn withdraw_funds(campaign: &Pubkey, creator: &Pubkey) -> Instruction {
+    instruction(
+        gkescrow::accounts::WithdrawFunds {
+            campaign: *campaign,
+            platform_config: pda::platform_config().0,
+            creator: *creator,
+            creator_profile: pda::creator_profile(creator).0,
+            treasury: pda::treasury().0,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::WithdrawFunds {},
+    )
+}
+
+// `creator` is the campaign creator, whose profile records the refund.
+// `share_mint` must be given when share tokens were claimed for the contribution,
+// so they can be burned from the supporter's associated token account.
+// `pledge` is the pledge mint and source account of a collected pledge, which is
+// refunded from the campaign's pledge escrow
+pub fn claim_refund(
+    campaign: &Pubkey,
+    creator: &Pubkey,
+    supporter: &Pubkey,
+    share_mint: Option<&Pubkey>,
+    pledge: Option<(&Pubkey, &Pubkey)>,
+) -> Instruction {
+    instruction(
+        gkescrow::accounts::ClaimRefund {
+            campaign: *campaign,
+            supporter_funding: pda::supporter_funding(campaign, supporter).0,
+            supporter_profile: pda::supporter_profile(supporter).0,
+            supporter: *supporter,
+            creator_profile: pda::creator_profile(creator).0,
+            share_mint: share_mint.copied(),
+            supporter_share_account: share_mint.map(|mint| ata(supporter, mint)),
+            pledge_escrow: pledge.map(|(mint, _)| ata(campaign, mint)),
+            pledge_source: pledge.map(|(_, source)| *source),
+            token_program: (share_mint.is_some() || pledge.is_some()).then_some(token::ID),
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::ClaimRefund {},
+    )
+}
+
+// `pledge_source` is the funding's pledge token account, needed to withdraw a pledge
+pub fn withdraw_contribution(campaign: &Pubkey, supporter: &Pubkey, pledge_source: Option<&Pubkey>) -> Instruction {
+    instruction(
+        gkescrow::accounts::WithdrawContribution {
+            campaign: *campaign,
+            platform_config: pda::platform_config().0,
+            supporter_funding: pda::supporter_funding(campaign, supporter).0,
+            dispute: pda::dispute(campaign, supporter).0,
+            supporter: *supporter,
+            supporter_profile: pda::supporter_profile(supporter).0,
+            pledge_source: pledge_source.copied(),
+            token_program: pledge_source.map(|_| token::ID),
+        },
+        gkescrow::instruction::WithdrawContribution {},
+    )
+}
+
+pub fn withdraw_treasury(admin: &Pubkey, amount: u64) -> Instruction {
+    instruction(
+        gkescrow::accounts::WithdrawTreasury {
+            admin: *admin,
+            treasury: pda::treasury().0,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::WithdrawTreasury { amount },
+    )
+}
+
+// Token fees go to the admin's associated token account for `mint`
+pub fn withdraw_treasury_tokens(admin: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    let (treasury, _) = pda::treasury();
+    instruction(
+        gkescrow::accounts::WithdrawTreasuryTokens {
+            admin: *admin,
+            mint: *mint,
+            treasury,
+            treasury_token_account: ata(&treasury, mint),
+            admin_token_account: ata(admin, mint),
+            token_program: token::ID,
+        },
+        gkescrow::instruction::WithdrawTreasuryTokens { amount },
+    )
+}
+
+pub fn set_platform_paused(admin: &Pubkey, paused: bool) -> Instruction {
+    instruction(
+        gkescrow::accounts::UpdatePlatformConfig {
+            admin: *admin,
+            platform_config: pda::platform_config().0,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::SetPlatformPaused { paused },
+    )
+}
+
+pub fn set_moderator(admin: &Pubkey, moderator: &Pubkey) -> Instruction {
+    instruction(
+        gkescrow::accounts::UpdatePlatformConfig {
+            admin: *admin,
+            platform_config: pda::platform_config().0,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::SetModerator { moderator: *moderator },
+    )
+}
+
+// Signed by the platform moderator or the admin
+pub fn flag_campaign(campaign: &Pubkey, creator: &Pubkey, moderator: &Pubkey, reason: TakedownReason) -> Instruction {
+    instruction(
+        gkescrow::accounts::FlagCampaign {
+            campaign: *campaign,
+            moderator: *moderator,
+            platform_config: pda::platform_config().0,
+            creator_profile: pda::creator_profile(creator).0,
+        },
+        gkescrow::instruction::FlagCampaign { reason },
+    )
+}
+
+pub fn configure_disputes(admin: &Pubkey, arbitrator: &Pubkey, dispute_window_seconds: i64) -> Instruction {
+    instruction(
+        gkescrow::accounts::UpdatePlatformConfig {
+            admin: *admin,
+            platform_config: pda::platform_config().0,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::ConfigureDisputes {
+            arbitrator: *arbitrator,
+            dispute_window_seconds,
+        },
+    )
+}
+
+pub fn file_dispute(campaign: &Pubkey, supporter: &Pubkey, evidence_hash: [u8; 32]) -> Instruction {
+    instruction(
+        gkescrow::accounts::FileDispute {
+            campaign: *campaign,
+            supporter_funding: pda::supporter_funding(campaign, supporter).0,
+            dispute: pda::dispute(campaign, supporter).0,
+            supporter: *supporter,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::FileDispute { evidence_hash },
+    )
+}
+
+// Signed by the platform arbitrator or the admin
+pub fn resolve_dispute(
+    campaign: &Pubkey,
+    creator: &Pubkey,
+    supporter: &Pubkey,
+    arbitrator: &Pubkey,
+    resolution: DisputeResolution,
+) -> Instruction {
+    instruction(
+        gkescrow::accounts::ResolveDispute {
+            campaign: *campaign,
+            dispute: pda::dispute(campaign, supporter).0,
+            supporter_funding: pda::supporter_funding(campaign, supporter).0,
+            supporter: *supporter,
+            supporter_profile: pda::supporter_profile(supporter).0,
+            creator_profile: pda::creator_profile(creator).0,
+            arbitrator: *arbitrator,
+            platform_config: pda::platform_config().0,
+        },
+        gkescrow::instruction::ResolveDispute { resolution },
+    )
+}
+
+pub fn configure_verification(admin: &Pubkey, attester: &Pubkey, threshold_lamports: Option<u64>) -> Instruction {
+    instruction(
+        gkescrow::accounts::UpdatePlatformConfig {
+            admin: *admin,
+            platform_config: pda::platform_config().0,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::ConfigureVerification {
+            attester: *attester,
+            threshold_lamports,
+        },
+    )
+}
+
+pub fn configure_withdrawals(admin: &Pubkey, cooling_off_seconds: Option<i64>) -> Instruction {
+    instruction(
+        gkescrow::accounts::UpdatePlatformConfig {
+            admin: *admin,
+            platform_config: pda::platform_config().0,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::ConfigureWithdrawals { cooling_off_seconds },
+    )
+}
+
+// Signed by the platform attester or the admin
+pub fn issue_verification(attester: &Pubkey, creator: &Pubkey, tier: u8, expires_at: i64) -> Instruction {
+    instruction(
+        gkescrow::accounts::IssueVerification {
+            attester: *attester,
+            creator: *creator,
+            creator_verification: pda::creator_verification(creator).0,
+            platform_config: pda::platform_config().0,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::IssueVerification { tier, expires_at },
+    )
+}
+
+// Signed by the platform attester or the admin
+pub fn revoke_verification(attester: &Pubkey, creator: &Pubkey) -> Instruction {
+    instruction(
+        gkescrow::accounts::RevokeVerification {
+            attester: *attester,
+            creator_verification: pda::creator_verification(creator).0,
+            platform_config: pda::platform_config().0,
+        },
+        gkescrow::instruction::RevokeVerification {},
+    )
+}
+
+pub fn set_campaign_frozen(campaign: &Pubkey, admin: &Pubkey, frozen: bool) -> Instruction {
+    instruction(
+        gkescrow::accounts::SetCampaignFrozen {
+            campaign: *campaign,
+            admin: *admin,
+        },
+        gkescrow::instruction::SetCampaignFrozen { frozen },
+    )
+}
+
+pub fn set_campaign_access(campaign: &Pubkey, creator: &Pubkey, access: CampaignAccess) -> Instruction {
+    instruction(
+        gkescrow::accounts::SetCampaignAccess {
+            campaign: *campaign,
+            creator: *creator,
+        },
+        gkescrow::instruction::SetCampaignAccess { access },
+    )
+}
+
+// Replaces the campaign's stretch goals; reached goals must be passed back unchanged
+pub fn set_stretch_goals(campaign: &Pubkey, creator: &Pubkey, stretch_goals: Vec<StretchGoalTarget>) -> Instruction {
+    instruction(
+        gkescrow::accounts::SetStretchGoals {
+            campaign: *campaign,
+            creator: *creator,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::SetStretchGoals { stretch_goals },
+    )
+}
+
+pub fn invite_supporter(campaign: &Pubkey, creator: &Pubkey, supporter: &Pubkey) -> Instruction {
+    instruction(
+        gkescrow::accounts::InviteSupporter {
+            campaign: *campaign,
+            creator: *creator,
+            supporter: *supporter,
+            invitation: pda::invitation(campaign, supporter).0,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::InviteSupporter {},
+    )
+}
+
+pub fn revoke_invitation(campaign: &Pubkey, creator: &Pubkey, supporter: &Pubkey) -> Instruction {
+    instruction(
+        gkescrow::accounts::RevokeInvitation {
+            campaign: *campaign,
+            creator: *creator,
+            invitation: pda::invitation(campaign, supporter).0,
+        },
+        gkescrow::instruction::RevokeInvitation {},
+    )
+}
+
+pub fn transfer_nft_to_escrow(campaign: &Pubkey, creator: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    let (escrow_authority, _) = pda::escrow_authority(campaign);
+    instruction(
+        gkescrow::accounts::TransferNftToEscrow {
+            campaign: *campaign,
+            creator: *creator,
+            nft_mint: *nft_mint,
+            creator_token_account: ata(creator, nft_mint),
+            escrow_authority,
+            escrow_token_account: ata(&escrow_authority, nft_mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::TransferNftToEscrow {},
+    )
+}
+
+pub fn claim_nft_from_escrow(campaign: &Pubkey, supporter: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    let (escrow_authority, _) = pda::escrow_authority(campaign);
+    instruction(
+        gkescrow::accounts::ClaimNftFromEscrow {
+            campaign: *campaign,
+            platform_config: pda::platform_config().0,
+            supporter_funding: pda::supporter_funding(campaign, supporter).0,
+            supporter_profile: pda::supporter_profile(supporter).0,
+            supporter: *supporter,
+            nft_mint: *nft_mint,
+            escrow_authority,
+            escrow_token_account: ata(&escrow_authority, nft_mint),
+            supporter_token_account: ata(supporter, nft_mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::ClaimNftFromEscrow {},
+    )
+}
+
+pub fn reclaim_escrowed_nft(campaign: &Pubkey, creator: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    let (escrow_authority, _) = pda::escrow_authority(campaign);
+    instruction(
+        gkescrow::accounts::ReclaimEscrowedNft {
+            campaign: *campaign,
+            creator: *creator,
+            nft_mint: *nft_mint,
+            creator_token_account: ata(creator, nft_mint),
+            escrow_authority,
+            escrow_token_account: ata(&escrow_authority, nft_mint),
+            token_program: token::ID,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::ReclaimEscrowedNft {},
+    )
+}
+
+// Legacy instruction that records an edition minted by the frontend
+pub fn update_supporter_nft_mint(
+    campaign: &Pubkey,
+    creator: &Pubkey,
+    supporter: &Pubkey,
+    master_edition_mint: &Pubkey,
+    edition_mint: &Pubkey,
+    mint_authority: &Pubkey,
+    edition_number: u64,
+) -> Instruction {
+    instruction(
+        gkescrow::accounts::UpdateSupporterNftMint {
+            campaign: *campaign,
+            platform_config: pda::platform_config().0,
+            supporter_funding: pda::supporter_funding(campaign, supporter).0,
+            supporter_profile: pda::supporter_profile(supporter).0,
+            creator: *creator,
+            authority: *supporter,
+            master_edition_mint: *master_edition_mint,
+            edition_mint: *edition_mint,
+            edition: pda::master_edition(edition_mint).0,
+            mint_authority: *mint_authority,
+            recipient_token_account: ata(supporter, edition_mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            token_metadata_program: metadata::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::UpdateSupporterNftMint { edition_number },
+    )
+}
+
+pub fn initialize_badge_mint(campaign: &Pubkey, creator: &Pubkey, reward_mode: RewardMode) -> Instruction {
+    instruction(
+        gkescrow::accounts::InitializeBadgeMint {
+            campaign: *campaign,
+            creator: *creator,
+            badge_authority: pda::badge_authority(campaign).0,
+            badge_mint: pda::badge_mint(campaign).0,
+            token_program: badge_token_program(reward_mode),
+            system_program: system_program::ID,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::InitializeBadgeMint {},
+    )
+}
+
+pub fn claim_supporter_badge(campaign: &Pubkey, supporter: &Pubkey, reward_mode: RewardMode) -> Instruction {
+    let token_program = badge_token_program(reward_mode);
+    let (badge_mint, _) = pda::badge_mint(campaign);
+    instruction(
+        gkescrow::accounts::ClaimSupporterBadge {
+            campaign: *campaign,
+            platform_config: pda::platform_config().0,
+            supporter_funding: pda::supporter_funding(campaign, supporter).0,
+            supporter_profile: pda::supporter_profile(supporter).0,
+            supporter: *supporter,
+            badge_authority: pda::badge_authority(campaign).0,
+            badge_mint,
+            supporter_token_account: get_associated_token_address_with_program_id(
+                supporter,
+                &badge_mint,
+                &token_program,
+            ),
+            token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::ClaimSupporterBadge {},
+    )
+}
+
+pub fn initialize_platform_collection(admin: &Pubkey, uri: String) -> Instruction {
+    let (platform_authority, _) = pda::platform_authority();
+    let (collection_mint, _) = pda::platform_collection();
+    instruction(
+        gkescrow::accounts::InitializePlatformCollection {
+            admin: *admin,
+            platform_authority,
+            collection_mint,
+            collection_token_account: ata(&platform_authority, &collection_mint),
+            collection_metadata: pda::metadata(&collection_mint).0,
+            collection_master_edition: pda::master_edition(&collection_mint).0,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            token_metadata_program: metadata::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::InitializePlatformCollection { uri },
+    )
+}
+
+pub fn initialize_campaign_collection(campaign: &Pubkey, creator: &Pubkey, uri: String) -> Instruction {
+    let (collection_mint, _) = pda::collection_mint(campaign);
+    instruction(
+        gkescrow::accounts::InitializeCampaignCollection {
+            campaign: *campaign,
+            creator: *creator,
+            collection_mint,
+            collection_token_account: ata(campaign, &collection_mint),
+            collection_metadata: pda::metadata(&collection_mint).0,
+            collection_master_edition: pda::master_edition(&collection_mint).0,
+            platform_collection_mint: pda::platform_collection().0,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            token_metadata_program: metadata::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::InitializeCampaignCollection { uri },
+    )
+}
+
+pub fn set_campaign_collection(campaign: &Pubkey, creator: &Pubkey, collection_mint: &Pubkey) -> Instruction {
+    instruction(
+        gkescrow::accounts::SetCampaignCollection {
+            campaign: *campaign,
+            creator: *creator,
+            collection_mint: *collection_mint,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::SetCampaignCollection {},
+    )
+}
+
+// `supporter` is required when the reward is a supporter's printed edition.
+// `collection_authority_record` is required for referenced (not program-created) collections.
+pub fn verify_reward_collection(
+    campaign: &Pubkey,
+    payer: &Pubkey,
+    reward_mint: &Pubkey,
+    collection_mint: &Pubkey,
+    supporter: Option<&Pubkey>,
+    collection_authority_record: Option<&Pubkey>,
+) -> Instruction {
+    instruction(
+        gkescrow::accounts::VerifyRewardCollection {
+            campaign: *campaign,
+            payer: *payer,
+            supporter_funding: supporter.map(|supporter| pda::supporter_funding(campaign, supporter).0),
+            reward_mint: *reward_mint,
+            reward_metadata: pda::metadata(reward_mint).0,
+            collection_mint: *collection_mint,
+            collection_metadata: pda::metadata(collection_mint).0,
+            collection_master_edition: pda::master_edition(collection_mint).0,
+            collection_authority_record: collection_authority_record.copied(),
+            token_metadata_program: metadata::ID,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::VerifyRewardCollection {},
+    )
+}
+
+pub fn verify_campaign_collection(campaign: &Pubkey, payer: &Pubkey, collection_mint: &Pubkey) -> Instruction {
+    let (platform_collection_mint, _) = pda::platform_collection();
+    instruction(
+        gkescrow::accounts::VerifyCampaignCollection {
+            campaign: *campaign,
+            payer: *payer,
+            collection_mint: *collection_mint,
+            collection_metadata: pda::metadata(collection_mint).0,
+            platform_authority: pda::platform_authority().0,
+            platform_collection_mint,
+            platform_collection_metadata: pda::metadata(&platform_collection_mint).0,
+            platform_collection_master_edition: pda::master_edition(&platform_collection_mint).0,
+            token_metadata_program: metadata::ID,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::VerifyCampaignCollection {},
+    )
+}
+
+pub fn mark_campaign_delivered(campaign: &Pubkey, creator: &Pubkey) -> Instruction {
+    instruction(
+        gkescrow::accounts::MarkCampaignDelivered {
+            campaign: *campaign,
+            creator: *creator,
+        },
+        gkescrow::instruction::MarkCampaignDelivered {},
+    )
+}
+
+pub fn set_outcome_metadata(
+    campaign: &Pubkey,
+    creator: &Pubkey,
+    funded_uri: String,
+    delivered_uri: String,
+    failed_uri: String,
+) -> Instruction {
+    instruction(
+        gkescrow::accounts::SetOutcomeMetadata {
+            campaign: *campaign,
+            creator: *creator,
+            outcome_metadata: pda::outcome_metadata(campaign).0,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::SetOutcomeMetadata {
+            funded_uri,
+            delivered_uri,
+            failed_uri,
+        },
+    )
+}
+
+// `supporter` is required when the reward is a supporter's printed edition
+pub fn update_reward_metadata(campaign: &Pubkey, reward_mint: &Pubkey, supporter: Option<&Pubkey>) -> Instruction {
+    instruction(
+        gkescrow::accounts::UpdateRewardMetadata {
+            campaign: *campaign,
+            outcome_metadata: pda::outcome_metadata(campaign).0,
+            supporter_funding: supporter.map(|supporter| pda::supporter_funding(campaign, supporter).0),
+            reward_mint: *reward_mint,
+            reward_metadata: pda::metadata(reward_mint).0,
+            token_metadata_program: metadata::ID,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::UpdateRewardMetadata {},
+    )
+}
+
+pub fn claim_share_tokens(campaign: &Pubkey, supporter: &Pubkey) -> Instruction {
+    let (share_mint, _) = pda::share_mint(campaign);
+    instruction(
+        gkescrow::accounts::ClaimShareTokens {
+            campaign: *campaign,
+            platform_config: pda::platform_config().0,
+            supporter_funding: pda::supporter_funding(campaign, supporter).0,
+            supporter: *supporter,
+            share_mint,
+            supporter_share_account: ata(supporter, &share_mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority().0,
+            program: gkescrow::ID,
+        },
+        gkescrow::instruction::ClaimShareTokens {},
+    )
+}
+
+pub fn update_creator_profile(
+    creator: &Pubkey,
+    display_name: String,
+    avatar_uri: String,
+    socials_hash: [u8; 32],
+) -> Instruction {
+    instruction(
+        gkescrow::accounts::UpdateCreatorProfile {
+            creator: *creator,
+            creator_profile: pda::creator_profile(creator).0,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::UpdateCreatorProfile {
+            display_name,
+            avatar_uri,
+            socials_hash,
+        },
+    )
+}
+
+pub fn cancel_campaign(campaign: &Pubkey, creator: &Pubkey) -> Instruction {
+    instruction(
+        gkescrow::accounts::CancelCampaign {
+            campaign: *campaign,
+            creator: *creator,
+            creator_profile: pda::creator_profile(creator).0,
+        },
+        gkescrow::instruction::CancelCampaign {},
+    )
+}
+
+// Permissionless; `creator` is the campaign creator whose profile is updated
+pub fn record_campaign_failure(campaign: &Pubkey, creator: &Pubkey) -> Instruction {
+    instruction(
+        gkescrow::accounts::RecordCampaignFailure {
+            campaign: *campaign,
+            creator_profile: pda::creator_profile(creator).0,
+        },
+        gkescrow::instruction::RecordCampaignFailure {},
+    )
+}
+
+// `sequence` is the campaign's current `updates_count`, the number the new update gets
+pub fn post_update(
+    campaign: &Pubkey,
+    creator: &Pubkey,
+    sequence: u32,
+    content: CampaignContent,
+    is_milestone: bool,
+) -> Instruction {
+    instruction(
+        gkescrow::accounts::PostUpdate {
+            campaign: *campaign,
+            update: pda::campaign_update(campaign, sequence).0,
+            creator: *creator,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::PostUpdate { content, is_milestone },
+    )
+}
+
+// Fields left as `None` keep their current value
+pub fn update_campaign_metadata(
+    campaign: &Pubkey,
+    creator: &Pubkey,
+    project_name: Option<String>,
+    description: Option<String>,
+    content: Option<CampaignContent>,
+    nft_uri: Option<String>,
+) -> Instruction {
+    instruction(
+        gkescrow::accounts::UpdateCampaignMetadata {
+            campaign: *campaign,
+            creator: *creator,
+            system_program: system_program::ID,
+        },
+        gkescrow::instruction::UpdateCampaignMetadata {
+            project_name,
+            description,
+            content,
+            nft_uri,
+        },
+    )
+}
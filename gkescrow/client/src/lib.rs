@@ -0,0 +1,55 @@
+//! Rust client for the gkescrow crowdfunding program: PDA derivation,
+//! instruction builders, account fetching, event decoding, off-chain content
+//! verification, allowlist proofs and a campaign status calculator that
+//! mirrors the on-chain rules.
+
+pub mod accounts;
+pub mod allowlist;
+pub mod content;
+pub mod events;
+pub mod instructions;
+pub mod pda;
+pub mod status;
+
+// Program account and argument types, re-exported for convenience
+pub use gkescrow::{
+    Campaign, CampaignAccess, CampaignContent, CampaignOutcome, CampaignRegistry, CampaignRegistryPage, CampaignUpdate,
+    CreatorProfile, CreatorVerification, Dispute, DisputeResolution, DisputeStatus, Invitation, OutcomeMetadata,
+    PlatformConfig, PledgeStatus, RewardMode, StretchGoal, StretchGoalTarget, SupporterFunding, SupporterProfile,
+    TakedownReason, DEFAULT_DISPUTE_WINDOW_SECONDS, ID, MAX_STRETCH_GOALS,
+};
+
+use anchor_lang::prelude::Pubkey;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("RPC error: {0}")]
+    Rpc(Box<solana_rpc_client_api::client_error::Error>),
+
+    #[error("Failed to deserialize account: {0}")]
+    Deserialize(anchor_lang::error::Error),
+
+    #[error("Account {0} is not owned by the gkescrow program")]
+    InvalidOwner(Pubkey),
+
+    #[error("Account {0} does not exist")]
+    AccountNotFound(Pubkey),
+
+    #[error("Campaign content is not valid JSON: {0}")]
+    InvalidContent(serde_json::Error),
+
+    #[error("Campaign content does not match its on-chain hash")]
+    ContentHashMismatch,
+
+    #[error("Unsupported content URI: {0}")]
+    UnsupportedUri(String),
+
+    #[error("Failed to fetch campaign content: {0}")]
+    Http(Box<reqwest::Error>),
+}
+
+impl From<solana_rpc_client_api::client_error::Error> for ClientError {
+    fn from(error: solana_rpc_client_api::client_error::Error) -> Self {
+        ClientError::Rpc(Box::new(error))
+    }
+}
@@ -0,0 +1,122 @@
+// PDA derivation helpers, mirroring the seeds used by the gkescrow program
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::metadata;
+
+// `creator_index` is the creator's campaign number, `Campaign::creator_index`
+pub fn campaign(creator: &Pubkey, creator_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"campaign", creator.as_ref(), creator_index.to_le_bytes().as_ref()],
+        &gkescrow::ID,
+    )
+}
+
+pub fn creator_profile(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"creator-profile", creator.as_ref()], &gkescrow::ID)
+}
+
+pub fn creator_verification(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"creator-verification", creator.as_ref()], &gkescrow::ID)
+}
+
+pub fn invitation(campaign: &Pubkey, supporter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"invitation", campaign.as_ref(), supporter.as_ref()], &gkescrow::ID)
+}
+
+pub fn supporter_funding(campaign: &Pubkey, supporter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"supporter-funding", campaign.as_ref(), supporter.as_ref()],
+        &gkescrow::ID,
+    )
+}
+
+pub fn campaign_registry() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"campaign-registry"], &gkescrow::ID)
+}
+
+// Progress update `sequence` of a campaign, numbered from 0
+pub fn campaign_update(campaign: &Pubkey, sequence: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"campaign-update", campaign.as_ref(), sequence.to_le_bytes().as_ref()],
+        &gkescrow::ID,
+    )
+}
+
+pub fn dispute(campaign: &Pubkey, supporter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"dispute", campaign.as_ref(), supporter.as_ref()],
+        &gkescrow::ID,
+    )
+}
+
+pub fn campaign_registry_page(page_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"campaign-registry-page", page_index.to_le_bytes().as_ref()],
+        &gkescrow::ID,
+    )
+}
+
+pub fn supporter_profile(supporter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"supporter-profile", supporter.as_ref()], &gkescrow::ID)
+}
+
+pub fn escrow_authority(campaign: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"escrow", campaign.as_ref()], &gkescrow::ID)
+}
+
+pub fn treasury() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"treasury"], &gkescrow::ID)
+}
+
+pub fn badge_authority(campaign: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"badge-authority", campaign.as_ref()], &gkescrow::ID)
+}
+
+pub fn badge_mint(campaign: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"badge-mint", campaign.as_ref()], &gkescrow::ID)
+}
+
+pub fn share_mint(campaign: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"share-mint", campaign.as_ref()], &gkescrow::ID)
+}
+
+pub fn collection_mint(campaign: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"collection-mint", campaign.as_ref()], &gkescrow::ID)
+}
+
+pub fn outcome_metadata(campaign: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"outcome-metadata", campaign.as_ref()], &gkescrow::ID)
+}
+
+pub fn platform_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"platform-config"], &gkescrow::ID)
+}
+
+pub fn platform_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"platform-authority"], &gkescrow::ID)
+}
+
+pub fn platform_collection() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"platform-collection"], &gkescrow::ID)
+}
+
+// Signer PDA used by instructions that emit events through emit_cpi!
+pub fn event_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"__event_authority"], &gkescrow::ID)
+}
+
+// Metaplex token metadata account for a mint
+pub fn metadata(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"metadata", metadata::ID.as_ref(), mint.as_ref()],
+        &metadata::ID,
+    )
+}
+
+// Metaplex master edition account for a mint
+pub fn master_edition(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"metadata", metadata::ID.as_ref(), mint.as_ref(), b"edition"],
+        &metadata::ID,
+    )
+}
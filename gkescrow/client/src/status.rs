@@ -0,0 +1,526 @@
+// Campaign status calculator mirroring the checks the program makes on-chain.
+// `now` is a unix timestamp, normally the cluster clock.
+
+use anchor_lang::prelude::Pubkey;
+use gkescrow::{
+    Campaign, PledgeStatus, RewardMode, StretchGoal, SupporterFunding, PLATFORM_FEE_DENOMINATOR, PLATFORM_FEE_NUMERATOR,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CampaignStatus {
+    TakenDown,   // Removed by a moderator; every supporter can claim a refund
+    Inactive,    // Campaign was deactivated
+    Frozen,      // Frozen by the admin; only refunds are open
+    Active,      // Accepting contributions, goal not reached yet
+    GoalReached, // Goal reached while still accepting contributions
+    Successful,  // Ended with the goal reached
+    Delivered,   // Creator marked the funded project as delivered
+    Failed,      // Ended below the goal; supporters can claim refunds
+}
+
+pub fn status(campaign: &Campaign, now: i64) -> CampaignStatus {
+    let ended = now > campaign.end_timestamp;
+    if campaign.takedown_reason.is_some() {
+        CampaignStatus::TakenDown
+    } else if !campaign.is_active {
+        CampaignStatus::Inactive
+    } else if campaign.is_frozen {
+        CampaignStatus::Frozen
+    } else if campaign.is_delivered {
+        CampaignStatus::Delivered
+    } else if campaign.is_funded {
+        if ended {
+            CampaignStatus::Successful
+        } else {
+            CampaignStatus::GoalReached
+        }
+    } else if ended {
+        CampaignStatus::Failed
+    } else {
+        CampaignStatus::Active
+    }
+}
+
+// Checks below cover the campaign itself; while the platform is paused
+// (`PlatformConfig::paused`) funding, withdrawals and reward claims all fail.
+
+// fund_campaign: SOL campaign, active, not frozen and not past the end date. Private
+// campaigns also need an allowlist proof or invitation, depending on `Campaign::access`.
+pub fn can_fund(campaign: &Campaign, now: i64) -> bool {
+    campaign.pledge_mint == Pubkey::default()
+        && campaign.is_active
+        && !campaign.is_frozen
+        && now <= campaign.end_timestamp
+}
+
+// withdraw_funds: SOL campaign with the goal reached, dispute window over with no open
+// disputes, not frozen or taken down
+pub fn can_withdraw(campaign: &Campaign, now: i64) -> bool {
+    campaign.pledge_mint == Pubkey::default()
+        && campaign.is_funded
+        && now > campaign.dispute_deadline
+        && campaign.open_disputes == 0
+        && !campaign.is_frozen
+        && campaign.takedown_reason.is_none()
+}
+
+// collect_pledges: token campaign with pledges left to settle, same release conditions as withdraw_funds
+pub fn can_collect_pledges(campaign: &Campaign, now: i64) -> bool {
+    campaign.pledge_mint != Pubkey::default()
+        && campaign.pledges_pending > 0
+        && campaign.is_funded
+        && now > campaign.dispute_deadline
+        && campaign.open_disputes == 0
+        && !campaign.is_frozen
+        && campaign.takedown_reason.is_none()
+}
+
+// withdraw_contribution: campaign still running below its goal, not frozen or taken down, no rewards or shares
+// claimed, within `PlatformConfig::withdrawal_cooling_off_seconds` of funding if set.
+// A contribution with a dispute on file can't be withdrawn either.
+pub fn can_withdraw_contribution(
+    campaign: &Campaign,
+    funding: &SupporterFunding,
+    cooling_off_seconds: Option<i64>,
+    now: i64,
+) -> bool {
+    let cooling_off = cooling_off_seconds.is_none_or(|seconds| now <= funding.funded_at.saturating_add(seconds));
+    campaign.is_active
+        && !campaign.is_funded
+        && now <= campaign.end_timestamp
+        && !campaign.is_frozen
+        && campaign.takedown_reason.is_none()
+        && !funding.is_claimed
+        && !funding.nft_minted
+        && funding.shares_minted == 0
+        && cooling_off
+}
+
+// file_dispute: funded campaign that ended and is within its dispute window, contribution not returned
+pub fn can_file_dispute(campaign: &Campaign, funding: &SupporterFunding, now: i64) -> bool {
+    campaign.is_funded
+        && now > campaign.end_timestamp
+        && now <= campaign.dispute_deadline
+        && campaign.takedown_reason.is_none()
+        && !funding.is_claimed
+}
+
+// claim_refund: taken down, or ended below the goal or cancelled; not refunded yet.
+// Only collected pledges were charged, so only they are refunded from the pledge escrow.
+pub fn can_refund(campaign: &Campaign, funding: &SupporterFunding, now: i64) -> bool {
+    let failed = (now > campaign.end_timestamp || !campaign.is_active) && !campaign.is_funded;
+    (campaign.takedown_reason.is_some() || failed) && !funding.is_claimed && is_paid(funding)
+}
+
+// Rewards need the contribution paid: SOL up front, pledges once collected
+fn is_paid(funding: &SupporterFunding) -> bool {
+    matches!(funding.pledge_status, None | Some(PledgeStatus::Collected))
+}
+
+// cancel_campaign: still running and below the goal
+pub fn can_cancel(campaign: &Campaign, now: i64) -> bool {
+    campaign.is_active && !campaign.is_funded && now <= campaign.end_timestamp
+}
+
+// record_campaign_failure: ended below the goal with no outcome counted yet
+pub fn can_record_failure(campaign: &Campaign, now: i64) -> bool {
+    now > campaign.end_timestamp && !campaign.is_funded && !campaign.outcome_recorded
+}
+
+// claim_nft_from_escrow: funded, unfrozen, not taken down transferable campaign with the NFT escrowed and editions left
+pub fn can_claim_nft(campaign: &Campaign, funding: &SupporterFunding, now: i64) -> bool {
+    campaign.is_funded
+        && !campaign.is_frozen
+        && campaign.takedown_reason.is_none()
+        && campaign.reward_mode == RewardMode::Transferable
+        && campaign.nft_in_escrow
+        && now <= campaign.claim_deadline
+        && !funding.is_claimed
+        && is_paid(funding)
+        && !funding.nft_minted
+        && campaign.editions_minted < campaign.max_editions
+}
+
+// claim_supporter_badge: funded, unfrozen, not taken down soulbound campaign, badge not claimed yet
+pub fn can_claim_badge(campaign: &Campaign, funding: &SupporterFunding) -> bool {
+    campaign.is_funded
+        && !campaign.is_frozen
+        && campaign.takedown_reason.is_none()
+        && campaign.reward_mode != RewardMode::Transferable
+        && !funding.is_claimed
+        && is_paid(funding)
+        && !funding.nft_minted
+}
+
+// claim_share_tokens: campaign issues shares and is funded, dispute window over with no open
+// disputes, not frozen or taken down, contribution not refunded or already claimed
+pub fn can_claim_shares(campaign: &Campaign, funding: &SupporterFunding, now: i64) -> bool {
+    campaign.share_mint != Default::default()
+        && campaign.is_funded
+        && now > campaign.dispute_deadline
+        && campaign.open_disputes == 0
+        && !campaign.is_frozen
+        && campaign.takedown_reason.is_none()
+        && !funding.is_claimed
+        && funding.shares_minted == 0
+}
+
+// reclaim_escrowed_nft: after the claim deadline, or once the campaign failed or was deactivated
+pub fn can_reclaim_nft(campaign: &Campaign, now: i64) -> bool {
+    let failed = now > campaign.end_timestamp && !campaign.is_funded;
+    campaign.nft_in_escrow && (now > campaign.claim_deadline || !campaign.is_active || failed)
+}
+
+// First stretch goal not reached yet, the next reward tier supporters can unlock
+pub fn next_stretch_goal(campaign: &Campaign) -> Option<&StretchGoal> {
+    campaign.stretch_goals.iter().find(|goal| goal.reached_at == 0)
+}
+
+// Split the withdrawable amount, the campaign balance above its rent-exempt minimum,
+// into (creator amount, platform fee) the way withdraw_funds does
+pub fn withdrawal_split(withdrawable: u64) -> Option<(u64, u64)> {
+    let platform_fee = withdrawable
+        .checked_mul(PLATFORM_FEE_NUMERATOR)?
+        .checked_div(PLATFORM_FEE_DENOMINATOR)?;
+    Some((withdrawable.checked_sub(platform_fee)?, platform_fee))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gkescrow::{CampaignAccess, TakedownReason};
+
+    // Timeline shared by every case: funded at 50, ends at 100, disputes close
+    // at 150 and the claim window at 200
+    const END: i64 = 100;
+    const DISPUTE_DEADLINE: i64 = 150;
+    const CLAIM_DEADLINE: i64 = 200;
+
+    type Setup = fn(&mut Campaign, &mut SupporterFunding);
+    type Case = (&'static str, Setup, i64, bool);
+
+    // Running SOL campaign below its goal, with a transferable reward not yet escrowed
+    fn campaign() -> Campaign {
+        Campaign {
+            creator: Pubkey::new_unique(),
+            bump: 255,
+            id: 0,
+            creator_index: 0,
+            verification_tier: 0,
+            project_name: "Project".to_string(),
+            description: String::new(),
+            content_uri: String::new(),
+            content_hash: [0; 32],
+            funding_goal_lamports: 1_000,
+            raised_lamports: 500,
+            supporters_count: 1,
+            updates_count: 0,
+            is_active: true,
+            is_funded: false,
+            is_delivered: false,
+            outcome_recorded: false,
+            is_frozen: false,
+            takedown_reason: None,
+            created_at: 0,
+            end_timestamp: END,
+            claim_deadline: CLAIM_DEADLINE,
+            dispute_deadline: DISPUTE_DEADLINE,
+            open_disputes: 0,
+            access: CampaignAccess::Public,
+            stretch_goals: Vec::new(),
+            nft_name: String::new(),
+            nft_symbol: String::new(),
+            nft_uri: String::new(),
+            nft_mint: Pubkey::new_unique(),
+            nft_in_escrow: false,
+            reward_mode: RewardMode::Transferable,
+            collection_mint: Pubkey::default(),
+            share_mint: Pubkey::default(),
+            pledge_mint: Pubkey::default(),
+            pledges_pending: 0,
+            pledges_failed: 0,
+            max_editions: 5,
+            editions_minted: 0,
+        }
+    }
+
+    // SOL contribution with nothing claimed
+    fn funding() -> SupporterFunding {
+        SupporterFunding {
+            campaign: Pubkey::new_unique(),
+            supporter: Pubkey::new_unique(),
+            amount_lamports: 500,
+            funded_at: 50,
+            is_claimed: false,
+            nft_minted: false,
+            edition_number: 0,
+            edition_mint: Pubkey::default(),
+            reward_tier: 0,
+            shares_minted: 0,
+            pledge_source: Pubkey::default(),
+            pledge_status: None,
+        }
+    }
+
+    fn check(predicate: impl Fn(&Campaign, &SupporterFunding, i64) -> bool, cases: &[Case]) {
+        for (name, setup, now, expected) in cases {
+            let (mut campaign, mut funding) = (campaign(), funding());
+            setup(&mut campaign, &mut funding);
+            assert_eq!(predicate(&campaign, &funding, *now), *expected, "{}", name);
+        }
+    }
+
+    fn none(_: &mut Campaign, _: &mut SupporterFunding) {}
+
+    fn funded(campaign: &mut Campaign, _: &mut SupporterFunding) {
+        campaign.is_funded = true;
+        campaign.raised_lamports = campaign.funding_goal_lamports;
+    }
+
+    fn frozen(campaign: &mut Campaign, _: &mut SupporterFunding) {
+        campaign.is_frozen = true;
+    }
+
+    fn taken_down(campaign: &mut Campaign, _: &mut SupporterFunding) {
+        campaign.is_active = false;
+        campaign.takedown_reason = Some(TakedownReason::Fraud);
+    }
+
+    fn cancelled(campaign: &mut Campaign, _: &mut SupporterFunding) {
+        campaign.is_active = false;
+    }
+
+    fn refunded(_: &mut Campaign, funding: &mut SupporterFunding) {
+        funding.is_claimed = true;
+    }
+
+    fn pledged(campaign: &mut Campaign, funding: &mut SupporterFunding) {
+        campaign.pledge_mint = Pubkey::new_unique();
+        campaign.pledges_pending = 1;
+        funding.pledge_source = Pubkey::new_unique();
+        funding.pledge_status = Some(PledgeStatus::Pending);
+    }
+
+    #[test]
+    fn status_table() {
+        let cases: &[(&str, Setup, i64, CampaignStatus)] = &[
+            ("running", none, 50, CampaignStatus::Active),
+            ("goal reached while running", funded, 50, CampaignStatus::GoalReached),
+            ("ended funded", funded, 101, CampaignStatus::Successful),
+            ("ended below goal", none, 101, CampaignStatus::Failed),
+            ("delivered", |c, f| { funded(c, f); c.is_delivered = true; }, 101, CampaignStatus::Delivered),
+            ("frozen", frozen, 50, CampaignStatus::Frozen),
+            ("cancelled", cancelled, 50, CampaignStatus::Inactive),
+            ("taken down", taken_down, 50, CampaignStatus::TakenDown),
+        ];
+        for (name, setup, now, expected) in cases {
+            let (mut campaign, mut funding) = (campaign(), funding());
+            setup(&mut campaign, &mut funding);
+            assert_eq!(status(&campaign, *now), *expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn can_fund_table() {
+        check(|c, _, now| can_fund(c, now), &[
+            ("running", none, 50, true),
+            ("last second", none, END, true),
+            ("ended", none, END + 1, false),
+            ("frozen", frozen, 50, false),
+            ("cancelled", cancelled, 50, false),
+            ("goal reached", funded, 50, true),
+            ("pledge campaign", pledged, 50, false),
+        ]);
+    }
+
+    #[test]
+    fn can_withdraw_table() {
+        check(|c, _, now| can_withdraw(c, now), &[
+            ("after dispute window", funded, DISPUTE_DEADLINE + 1, true),
+            ("dispute window open", funded, DISPUTE_DEADLINE, false),
+            ("not funded", none, DISPUTE_DEADLINE + 1, false),
+            ("open disputes", |c, f| { funded(c, f); c.open_disputes = 1; }, DISPUTE_DEADLINE + 1, false),
+            ("frozen", |c, f| { funded(c, f); frozen(c, f); }, DISPUTE_DEADLINE + 1, false),
+            ("taken down", |c, f| { funded(c, f); taken_down(c, f); }, DISPUTE_DEADLINE + 1, false),
+            ("pledge campaign", |c, f| { funded(c, f); pledged(c, f); }, DISPUTE_DEADLINE + 1, false),
+        ]);
+    }
+
+    #[test]
+    fn can_collect_pledges_table() {
+        check(|c, _, now| can_collect_pledges(c, now), &[
+            ("after dispute window", |c, f| { funded(c, f); pledged(c, f); }, DISPUTE_DEADLINE + 1, true),
+            ("dispute window open", |c, f| { funded(c, f); pledged(c, f); }, DISPUTE_DEADLINE, false),
+            ("SOL campaign", funded, DISPUTE_DEADLINE + 1, false),
+            ("not funded", pledged, DISPUTE_DEADLINE + 1, false),
+            (
+                "nothing pending",
+                |c, f| { funded(c, f); pledged(c, f); c.pledges_pending = 0; },
+                DISPUTE_DEADLINE + 1,
+                false,
+            ),
+            (
+                "open disputes",
+                |c, f| { funded(c, f); pledged(c, f); c.open_disputes = 1; },
+                DISPUTE_DEADLINE + 1,
+                false,
+            ),
+            ("frozen", |c, f| { funded(c, f); pledged(c, f); frozen(c, f); }, DISPUTE_DEADLINE + 1, false),
+            ("taken down", |c, f| { funded(c, f); pledged(c, f); taken_down(c, f); }, DISPUTE_DEADLINE + 1, false),
+        ]);
+    }
+
+    #[test]
+    fn can_withdraw_contribution_table() {
+        check(|c, f, now| can_withdraw_contribution(c, f, None, now), &[
+            ("running", none, 50, true),
+            ("pending pledge", pledged, 50, true),
+            ("ended", none, END + 1, false),
+            ("goal reached", funded, 50, false),
+            ("frozen", frozen, 50, false),
+            ("cancelled", cancelled, 50, false),
+            ("taken down", taken_down, 50, false),
+            ("refunded", refunded, 50, false),
+            ("reward claimed", |_, f| f.nft_minted = true, 50, false),
+            ("shares claimed", |_, f| f.shares_minted = 500, 50, false),
+        ]);
+        check(|c, f, now| can_withdraw_contribution(c, f, Some(10), now), &[
+            ("within cooling-off", none, 60, true),
+            ("after cooling-off", none, 61, false),
+        ]);
+    }
+
+    #[test]
+    fn can_file_dispute_table() {
+        check(can_file_dispute, &[
+            ("within window", funded, END + 1, true),
+            ("last second", funded, DISPUTE_DEADLINE, true),
+            ("before end", funded, END, false),
+            ("after window", funded, DISPUTE_DEADLINE + 1, false),
+            ("not funded", none, END + 1, false),
+            ("taken down", |c, f| { funded(c, f); taken_down(c, f); }, END + 1, false),
+            ("refunded", |c, f| { funded(c, f); refunded(c, f); }, END + 1, false),
+        ]);
+    }
+
+    #[test]
+    fn can_refund_table() {
+        check(can_refund, &[
+            ("ended below goal", none, END + 1, true),
+            ("running", none, 50, false),
+            ("cancelled", cancelled, 50, true),
+            ("taken down while funded", |c, f| { funded(c, f); taken_down(c, f); }, 50, true),
+            ("ended funded", funded, END + 1, false),
+            ("already refunded", |c, f| { cancelled(c, f); refunded(c, f); }, 50, false),
+            ("pending pledge", |c, f| { pledged(c, f); cancelled(c, f); }, 50, false),
+            (
+                "collected pledge",
+                |c, f| { pledged(c, f); f.pledge_status = Some(PledgeStatus::Collected); },
+                END + 1,
+                true,
+            ),
+        ]);
+    }
+
+    #[test]
+    fn can_cancel_table() {
+        check(|c, _, now| can_cancel(c, now), &[
+            ("running", none, 50, true),
+            ("ended", none, END + 1, false),
+            ("goal reached", funded, 50, false),
+            ("already cancelled", cancelled, 50, false),
+        ]);
+    }
+
+    #[test]
+    fn can_record_failure_table() {
+        check(|c, _, now| can_record_failure(c, now), &[
+            ("ended below goal", none, END + 1, true),
+            ("running", none, END, false),
+            ("ended funded", funded, END + 1, false),
+            ("already recorded", |c, _| c.outcome_recorded = true, END + 1, false),
+        ]);
+    }
+
+    #[test]
+    fn can_claim_nft_table() {
+        fn escrowed(campaign: &mut Campaign, funding: &mut SupporterFunding) {
+            funded(campaign, funding);
+            campaign.nft_in_escrow = true;
+        }
+        check(can_claim_nft, &[
+            ("escrowed", escrowed, END + 1, true),
+            ("last second", escrowed, CLAIM_DEADLINE, true),
+            ("claim window closed", escrowed, CLAIM_DEADLINE + 1, false),
+            ("not escrowed", funded, END + 1, false),
+            ("not funded", |c, f| { escrowed(c, f); c.is_funded = false; }, END + 1, false),
+            ("frozen", |c, f| { escrowed(c, f); frozen(c, f); }, END + 1, false),
+            ("taken down", |c, f| { escrowed(c, f); taken_down(c, f); }, END + 1, false),
+            ("soulbound", |c, f| { escrowed(c, f); c.reward_mode = RewardMode::Soulbound; }, END + 1, false),
+            ("refunded", |c, f| { escrowed(c, f); refunded(c, f); }, END + 1, false),
+            ("pending pledge", |c, f| { escrowed(c, f); pledged(c, f); }, END + 1, false),
+            ("already claimed", |c, f| { escrowed(c, f); f.nft_minted = true; }, END + 1, false),
+            ("sold out", |c, f| { escrowed(c, f); c.editions_minted = c.max_editions; }, END + 1, false),
+        ]);
+    }
+
+    #[test]
+    fn can_claim_badge_table() {
+        fn soulbound(campaign: &mut Campaign, funding: &mut SupporterFunding) {
+            funded(campaign, funding);
+            campaign.reward_mode = RewardMode::Soulbound;
+        }
+        check(|c, f, _| can_claim_badge(c, f), &[
+            ("soulbound", soulbound, 0, true),
+            ("frozen badges", |c, f| { funded(c, f); c.reward_mode = RewardMode::Frozen; }, 0, true),
+            ("transferable", funded, 0, false),
+            ("not funded", |c, f| { soulbound(c, f); c.is_funded = false; }, 0, false),
+            ("frozen", |c, f| { soulbound(c, f); frozen(c, f); }, 0, false),
+            ("taken down", |c, f| { soulbound(c, f); taken_down(c, f); }, 0, false),
+            ("refunded", |c, f| { soulbound(c, f); refunded(c, f); }, 0, false),
+            ("pending pledge", |c, f| { soulbound(c, f); pledged(c, f); }, 0, false),
+            ("already claimed", |c, f| { soulbound(c, f); f.nft_minted = true; }, 0, false),
+        ]);
+    }
+
+    #[test]
+    fn can_claim_shares_table() {
+        fn shares(campaign: &mut Campaign, funding: &mut SupporterFunding) {
+            funded(campaign, funding);
+            campaign.share_mint = Pubkey::new_unique();
+        }
+        check(can_claim_shares, &[
+            ("after dispute window", shares, DISPUTE_DEADLINE + 1, true),
+            ("dispute window open", shares, DISPUTE_DEADLINE, false),
+            ("no share mint", funded, DISPUTE_DEADLINE + 1, false),
+            ("not funded", |c, f| { shares(c, f); c.is_funded = false; }, DISPUTE_DEADLINE + 1, false),
+            ("open disputes", |c, f| { shares(c, f); c.open_disputes = 1; }, DISPUTE_DEADLINE + 1, false),
+            ("frozen", |c, f| { shares(c, f); frozen(c, f); }, DISPUTE_DEADLINE + 1, false),
+            ("taken down", |c, f| { shares(c, f); taken_down(c, f); }, DISPUTE_DEADLINE + 1, false),
+            ("refunded", |c, f| { shares(c, f); refunded(c, f); }, DISPUTE_DEADLINE + 1, false),
+            ("already claimed", |c, f| { shares(c, f); f.shares_minted = 500; }, DISPUTE_DEADLINE + 1, false),
+        ]);
+    }
+
+    #[test]
+    fn can_reclaim_nft_table() {
+        fn escrowed(campaign: &mut Campaign, _: &mut SupporterFunding) {
+            campaign.nft_in_escrow = true;
+        }
+        check(|c, _, now| can_reclaim_nft(c, now), &[
+            ("claim window closed", |c, f| { escrowed(c, f); funded(c, f); }, CLAIM_DEADLINE + 1, true),
+            ("claim window open", |c, f| { escrowed(c, f); funded(c, f); }, CLAIM_DEADLINE, false),
+            ("ended below goal", escrowed, END + 1, true),
+            ("running", escrowed, 50, false),
+            ("cancelled", |c, f| { escrowed(c, f); cancelled(c, f); }, 50, true),
+            ("not escrowed", none, CLAIM_DEADLINE + 1, false),
+        ]);
+    }
+
+    #[test]
+    fn withdrawal_split_takes_the_platform_fee() {
+        assert_eq!(withdrawal_split(1_000_000), Some((975_000, 25_000)));
+        assert_eq!(withdrawal_split(0), Some((0, 0)));
+        assert_eq!(withdrawal_split(u64::MAX), None);
+    }
+}
@@ -0,0 +1,169 @@
+// Read-only HTTP/JSON API over the indexed data.
+//
+//   GET /health
+//   GET /campaigns[?creator=<pubkey>]
+//   GET /campaigns/<campaign>
+//   GET /campaigns/<campaign>/supporters
+//   GET /campaigns/<campaign>/stretch-goals
+//   GET /campaigns/<campaign>/contributions
+//   GET /campaigns/<campaign>/withdrawn-contributions
+//   GET /campaigns/<campaign>/withdrawals
+//   GET /campaigns/<campaign>/refunds
+//   GET /campaigns/<campaign>/updates
+//   GET /campaigns/<campaign>/disputes
+//   GET /supporters/<supporter>
+//   GET /supporters/<supporter>/fundings
+//   GET /supporters/<supporter>/updates[?since=<unix timestamp>]
+//   GET /creators/<creator>
+
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Result};
+use rusqlite::Connection;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::db;
+
+pub fn serve(server: Server, conn: Connection) {
+    for request in server.incoming_requests() {
+        let (status, body) = if *request.method() != Method::Get {
+            (405, json!({ "error": "method not allowed" }))
+        } else {
+            match route(&conn, request.url()) {
+                Ok(Some(body)) => (200, body),
+                Ok(None) => (404, json!({ "error": "not found" })),
+                Err(err) => (400, json!({ "error": format!("{:#}", err) })),
+            }
+        };
+        respond(request, status, body);
+    }
+}
+
+fn respond(request: Request, status: u16, body: Value) {
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header("Content-Type", "application/json"))
+        // The frontend is served from a different origin
+        .with_header(header("Access-Control-Allow-Origin", "*"));
+    if let Err(err) = request.respond(response) {
+        eprintln!("Failed to send response: {}", err);
+    }
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("static header is valid")
+}
+
+fn pubkey(value: &str) -> Result<String> {
+    Pubkey::from_str(value)
+        .map(|key| key.to_string())
+        .map_err(|_| anyhow!("invalid public key: {}", value))
+}
+
+// Ok(None) means the path or the requested account does not exist
+fn route(conn: &Connection, url: &str) -> Result<Option<Value>> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    let rows = match segments.as_slice() {
+        ["health"] => {
+            let cursor = db::cursor(conn)?;
+            return Ok(Some(json!({ "status": "ok", "last_signature": cursor })));
+        }
+        ["campaigns"] => match query.split('&').find_map(|pair| pair.strip_prefix("creator=")) {
+            Some(creator) => db::query_json(
+                conn,
+                "SELECT * FROM campaigns WHERE creator = ?1 ORDER BY id DESC",
+                &[&pubkey(creator)?],
+            )?,
+            None => db::query_json(conn, "SELECT * FROM campaigns ORDER BY id DESC", &[])?,
+        },
+        ["campaigns", campaign] => {
+            let rows = db::query_json(conn, "SELECT * FROM campaigns WHERE address = ?1", &[&pubkey(campaign)?])?;
+            return Ok(rows.into_iter().next());
+        }
+        ["campaigns", campaign, "supporters"] => db::query_json(
+            conn,
+            "SELECT * FROM supporter_fundings WHERE campaign = ?1 ORDER BY funded_at",
+            &[&pubkey(campaign)?],
+        )?,
+        ["campaigns", campaign, "contributions"] => db::query_json(
+            conn,
+            "SELECT * FROM contributions WHERE campaign = ?1 ORDER BY slot, event_index",
+            &[&pubkey(campaign)?],
+        )?,
+        ["campaigns", campaign, "stretch-goals"] => db::query_json(
+            conn,
+            "SELECT * FROM stretch_goals WHERE campaign = ?1 ORDER BY position",
+            &[&pubkey(campaign)?],
+        )?,
+        ["campaigns", campaign, "withdrawn-contributions"] => db::query_json(
+            conn,
+            "SELECT * FROM contribution_withdrawals WHERE campaign = ?1 ORDER BY slot, event_index",
+            &[&pubkey(campaign)?],
+        )?,
+        ["campaigns", campaign, "withdrawals"] => db::query_json(
+            conn,
+            "SELECT * FROM withdrawals WHERE campaign = ?1 ORDER BY slot, event_index",
+            &[&pubkey(campaign)?],
+        )?,
+        ["campaigns", campaign, "refunds"] => db::query_json(
+            conn,
+            "SELECT * FROM refunds WHERE campaign = ?1 ORDER BY slot, event_index",
+            &[&pubkey(campaign)?],
+        )?,
+        ["campaigns", campaign, "updates"] => db::query_json(
+            conn,
+            "SELECT * FROM updates WHERE campaign = ?1 ORDER BY sequence",
+            &[&pubkey(campaign)?],
+        )?,
+        ["campaigns", campaign, "disputes"] => db::query_json(
+            conn,
+            "SELECT * FROM disputes WHERE campaign = ?1 ORDER BY filed_at",
+            &[&pubkey(campaign)?],
+        )?,
+        ["campaigns", campaign, "invitations"] => db::query_json(
+            conn,
+            "SELECT * FROM invitations WHERE campaign = ?1 ORDER BY invited_at",
+            &[&pubkey(campaign)?],
+        )?,
+        ["creators", creator] => {
+            let rows = db::query_json(conn, "SELECT * FROM creator_profiles WHERE creator = ?1", &[&pubkey(creator)?])?;
+            return Ok(rows.into_iter().next());
+        }
+        ["creators", creator, "verification"] => {
+            let rows =
+                db::query_json(conn, "SELECT * FROM creator_verifications WHERE creator = ?1", &[&pubkey(creator)?])?;
+            return Ok(rows.into_iter().next());
+        }
+        ["supporters", supporter] => {
+            let rows =
+                db::query_json(conn, "SELECT * FROM supporter_profiles WHERE supporter = ?1", &[&pubkey(supporter)?])?;
+            return Ok(rows.into_iter().next());
+        }
+        ["supporters", supporter, "fundings"] => db::query_json(
+            conn,
+            "SELECT * FROM supporter_fundings WHERE supporter = ?1 ORDER BY funded_at DESC",
+            &[&pubkey(supporter)?],
+        )?,
+        // Notification feed: updates from every campaign the supporter backed, newest first
+        ["supporters", supporter, "updates"] => {
+            let since = match query.split('&').find_map(|pair| pair.strip_prefix("since=")) {
+                Some(since) => since.parse::<i64>().map_err(|_| anyhow!("invalid timestamp: {}", since))?,
+                None => 0,
+            };
+            db::query_json(
+                conn,
+                "SELECT updates.* FROM updates
+                 JOIN supporter_fundings ON supporter_fundings.campaign = updates.campaign
+                 WHERE supporter_fundings.supporter = ?1 AND updates.posted_at > ?2
+                 ORDER BY updates.posted_at DESC, updates.sequence DESC",
+                &[&pubkey(supporter)?, &since],
+            )?
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(Value::Array(rows)))
+}
@@ -0,0 +1,650 @@
+// SQLite storage for indexed accounts and events.
+// Campaign and SupporterFunding rows mirror the latest on-chain account state;
+// contributions, contribution withdrawals, withdrawals, refunds and creator updates are
+// append-only event history.
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use gkescrow::{Contributed, ContributionWithdrawn, FundsWithdrawn, RefundClaimed, UpdatePosted};
+use gkescrow_client::{
+    content, Campaign, CampaignAccess, CreatorProfile, CreatorVerification, Dispute, DisputeStatus, Invitation,
+    SupporterFunding, SupporterProfile,
+};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::{Map, Value};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS campaigns (
+    address               TEXT PRIMARY KEY,
+    id                    INTEGER NOT NULL,
+    creator               TEXT NOT NULL,
+    creator_index         INTEGER NOT NULL,
+    verification_tier     INTEGER NOT NULL,
+    project_name          TEXT NOT NULL,
+    description           TEXT NOT NULL,
+    content_uri           TEXT NOT NULL,
+    content_hash          TEXT NOT NULL,
+    funding_goal_lamports INTEGER NOT NULL,
+    raised_lamports       INTEGER NOT NULL,
+    supporters_count      INTEGER NOT NULL,
+    updates_count         INTEGER NOT NULL,
+    is_active             INTEGER NOT NULL,
+    is_funded             INTEGER NOT NULL,
+    is_delivered          INTEGER NOT NULL,
+    outcome_recorded      INTEGER NOT NULL,
+    is_frozen             INTEGER NOT NULL,
+    takedown_reason       TEXT,
+    created_at            INTEGER NOT NULL,
+    end_timestamp         INTEGER NOT NULL,
+    claim_deadline        INTEGER NOT NULL,
+    dispute_deadline      INTEGER NOT NULL,
+    open_disputes         INTEGER NOT NULL,
+    access                TEXT NOT NULL,
+    allowlist_root        TEXT,
+    nft_name              TEXT NOT NULL,
+    nft_symbol            TEXT NOT NULL,
+    nft_uri               TEXT NOT NULL,
+    nft_mint              TEXT NOT NULL,
+    nft_in_escrow         INTEGER NOT NULL,
+    reward_mode           TEXT NOT NULL,
+    collection_mint       TEXT,
+    share_mint            TEXT,
+    pledge_mint           TEXT,
+    pledges_pending       INTEGER NOT NULL,
+    pledges_failed        INTEGER NOT NULL,
+    max_editions          INTEGER NOT NULL,
+    editions_minted       INTEGER NOT NULL,
+    updated_slot          INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS campaigns_creator ON campaigns (creator);
+
+-- Mirrors Campaign::stretch_goals; rewritten with its campaign row
+CREATE TABLE IF NOT EXISTS stretch_goals (
+    campaign           TEXT NOT NULL,
+    position           INTEGER NOT NULL,
+    threshold_lamports INTEGER NOT NULL,
+    reward_uri         TEXT NOT NULL,
+    reached_at         INTEGER NOT NULL,
+    PRIMARY KEY (campaign, position)
+);
+
+CREATE TABLE IF NOT EXISTS supporter_fundings (
+    address         TEXT PRIMARY KEY,
+    campaign        TEXT NOT NULL,
+    supporter       TEXT NOT NULL,
+    amount_lamports INTEGER NOT NULL,
+    funded_at       INTEGER NOT NULL,
+    is_claimed      INTEGER NOT NULL,
+    nft_minted      INTEGER NOT NULL,
+    edition_number  INTEGER NOT NULL,
+    edition_mint    TEXT,
+    reward_tier     INTEGER NOT NULL,
+    shares_minted   INTEGER NOT NULL,
+    pledge_source   TEXT,
+    pledge_status   TEXT,
+    updated_slot    INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS supporter_fundings_campaign ON supporter_fundings (campaign);
+CREATE INDEX IF NOT EXISTS supporter_fundings_supporter ON supporter_fundings (supporter);
+
+CREATE TABLE IF NOT EXISTS creator_profiles (
+    address                 TEXT PRIMARY KEY,
+    creator                 TEXT NOT NULL UNIQUE,
+    display_name            TEXT NOT NULL,
+    avatar_uri              TEXT NOT NULL,
+    socials_hash            TEXT NOT NULL,
+    campaign_count          INTEGER NOT NULL,
+    campaigns_succeeded     INTEGER NOT NULL,
+    campaigns_failed        INTEGER NOT NULL,
+    campaigns_cancelled     INTEGER NOT NULL,
+    campaigns_taken_down    INTEGER NOT NULL,
+    total_raised_lamports   INTEGER NOT NULL,
+    total_refunded_lamports INTEGER NOT NULL,
+    updated_slot            INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS creator_verifications (
+    address      TEXT PRIMARY KEY,
+    creator      TEXT NOT NULL UNIQUE,
+    attester     TEXT NOT NULL,
+    tier         INTEGER NOT NULL,
+    issued_at    INTEGER NOT NULL,
+    expires_at   INTEGER NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS invitations (
+    address      TEXT PRIMARY KEY,
+    campaign     TEXT NOT NULL,
+    supporter    TEXT NOT NULL,
+    invited_at   INTEGER NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS invitations_campaign ON invitations (campaign);
+
+CREATE TABLE IF NOT EXISTS disputes (
+    address       TEXT PRIMARY KEY,
+    campaign      TEXT NOT NULL,
+    supporter     TEXT NOT NULL,
+    evidence_hash TEXT NOT NULL,
+    filed_at      INTEGER NOT NULL,
+    status        TEXT NOT NULL,
+    resolved_at   INTEGER,
+    updated_slot  INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS disputes_campaign ON disputes (campaign);
+
+CREATE TABLE IF NOT EXISTS supporter_profiles (
+    address                    TEXT PRIMARY KEY,
+    supporter                  TEXT NOT NULL UNIQUE,
+    total_contributed_lamports INTEGER NOT NULL,
+    campaigns_backed           INTEGER NOT NULL,
+    refunds_received           INTEGER NOT NULL,
+    total_refunded_lamports    INTEGER NOT NULL,
+    nfts_claimed               INTEGER NOT NULL,
+    updated_slot               INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS transactions (
+    signature    TEXT PRIMARY KEY,
+    slot         INTEGER NOT NULL,
+    block_time   INTEGER,
+    instructions TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS contributions (
+    signature       TEXT NOT NULL,
+    event_index     INTEGER NOT NULL,
+    campaign        TEXT NOT NULL,
+    supporter       TEXT NOT NULL,
+    amount_lamports INTEGER NOT NULL,
+    raised_lamports INTEGER NOT NULL,
+    slot            INTEGER NOT NULL,
+    block_time      INTEGER,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS contributions_campaign ON contributions (campaign);
+
+CREATE TABLE IF NOT EXISTS contribution_withdrawals (
+    signature       TEXT NOT NULL,
+    event_index     INTEGER NOT NULL,
+    campaign        TEXT NOT NULL,
+    supporter       TEXT NOT NULL,
+    amount          INTEGER NOT NULL,
+    raised_lamports INTEGER NOT NULL,
+    slot            INTEGER NOT NULL,
+    block_time      INTEGER,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS contribution_withdrawals_campaign ON contribution_withdrawals (campaign);
+
+CREATE TABLE IF NOT EXISTS withdrawals (
+    signature      TEXT NOT NULL,
+    event_index    INTEGER NOT NULL,
+    campaign       TEXT NOT NULL,
+    creator        TEXT NOT NULL,
+    creator_amount INTEGER NOT NULL,
+    platform_fee   INTEGER NOT NULL,
+    slot           INTEGER NOT NULL,
+    block_time     INTEGER,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS withdrawals_campaign ON withdrawals (campaign);
+
+CREATE TABLE IF NOT EXISTS refunds (
+    signature       TEXT NOT NULL,
+    event_index     INTEGER NOT NULL,
+    campaign        TEXT NOT NULL,
+    supporter       TEXT NOT NULL,
+    amount_lamports INTEGER NOT NULL,
+    shares_burned   INTEGER NOT NULL,
+    slot            INTEGER NOT NULL,
+    block_time      INTEGER,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS refunds_campaign ON refunds (campaign);
+
+CREATE TABLE IF NOT EXISTS updates (
+    address      TEXT PRIMARY KEY,
+    campaign     TEXT NOT NULL,
+    sequence     INTEGER NOT NULL,
+    posted_at    INTEGER NOT NULL,
+    content_uri  TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    is_milestone INTEGER NOT NULL,
+    signature    TEXT NOT NULL,
+    slot         INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS updates_campaign ON updates (campaign);
+
+-- Newest transaction processed, where the next poll resumes
+CREATE TABLE IF NOT EXISTS cursor (
+    id        INTEGER PRIMARY KEY CHECK (id = 0),
+    signature TEXT NOT NULL,
+    slot      INTEGER NOT NULL
+);
+";
+
+// Where an event was found, stored alongside it
+pub struct EventSource<'a> {
+    pub signature: &'a str,
+    pub event_index: usize,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+}
+
+pub fn open(path: &str) -> Result<Connection> {
+    let conn = Connection::open(path)?;
+    // WAL lets the API read while the indexer writes
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.pragma_update(None, "busy_timeout", 5000)?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+// Zeroed pubkeys mean "not set" on-chain and are stored as NULL
+fn optional_pubkey(key: &Pubkey) -> Option<String> {
+    (*key != Pubkey::default()).then(|| key.to_string())
+}
+
+pub fn cursor(conn: &Connection) -> Result<Option<String>> {
+    Ok(conn
+        .query_row("SELECT signature FROM cursor WHERE id = 0", [], |row| row.get(0))
+        .optional()?)
+}
+
+pub fn set_cursor(conn: &Connection, signature: &str, slot: u64) -> Result<()> {
+    conn.execute(
+        "INSERT INTO cursor (id, signature, slot) VALUES (0, ?1, ?2)
+         ON CONFLICT (id) DO UPDATE SET signature = excluded.signature, slot = excluded.slot",
+        params![signature, slot as i64],
+    )?;
+    Ok(())
+}
+
+pub fn insert_transaction(
+    conn: &Connection,
+    signature: &str,
+    slot: u64,
+    block_time: Option<i64>,
+    instructions: &[&str],
+) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO transactions (signature, slot, block_time, instructions) VALUES (?1, ?2, ?3, ?4)",
+        params![signature, slot as i64, block_time, instructions.join(",")],
+    )?;
+    Ok(())
+}
+
+pub fn upsert_campaign(conn: &Connection, address: &Pubkey, campaign: &Campaign, slot: u64) -> Result<()> {
+    let (access, allowlist_root) = match campaign.access {
+        CampaignAccess::Public => ("Public", None),
+        CampaignAccess::Allowlist { root } => ("Allowlist", Some(content::hash_hex(&root))),
+        CampaignAccess::InviteOnly => ("InviteOnly", None),
+    };
+    conn.execute(
+        "INSERT OR REPLACE INTO campaigns (
+            address, id, creator, creator_index, verification_tier, project_name, description, content_uri,
+            content_hash, funding_goal_lamports, raised_lamports, supporters_count, updates_count, is_active,
+            is_funded, is_delivered, outcome_recorded, is_frozen, takedown_reason, created_at,
+            end_timestamp, claim_deadline, dispute_deadline, open_disputes, access, allowlist_root, nft_name,
+            nft_symbol, nft_uri, nft_mint, nft_in_escrow, reward_mode, collection_mint, share_mint, pledge_mint,
+            pledges_pending, pledges_failed, max_editions, editions_minted, updated_slot
+        ) VALUES (
+            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20,
+            ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34, ?35, ?36, ?37, ?38, ?39, ?40
+        )",
+        params![
+            address.to_string(),
+            campaign.id as i64,
+            campaign.creator.to_string(),
+            campaign.creator_index as i64,
+            campaign.verification_tier,
+            campaign.project_name,
+            campaign.description,
+            campaign.content_uri,
+            content::hash_hex(&campaign.content_hash),
+            campaign.funding_goal_lamports as i64,
+            campaign.raised_lamports as i64,
+            campaign.supporters_count,
+            campaign.updates_count,
+            campaign.is_active,
+            campaign.is_funded,
+            campaign.is_delivered,
+            campaign.outcome_recorded,
+            campaign.is_frozen,
+            campaign.takedown_reason.map(|reason| format!("{:?}", reason)),
+            campaign.created_at,
+            campaign.end_timestamp,
+            campaign.claim_deadline,
+            campaign.dispute_deadline,
+            campaign.open_disputes,
+            access,
+            allowlist_root,
+            campaign.nft_name,
+            campaign.nft_symbol,
+            campaign.nft_uri,
+            campaign.nft_mint.to_string(),
+            campaign.nft_in_escrow,
+            format!("{:?}", campaign.reward_mode),
+            optional_pubkey(&campaign.collection_mint),
+            optional_pubkey(&campaign.share_mint),
+            optional_pubkey(&campaign.pledge_mint),
+            campaign.pledges_pending,
+            campaign.pledges_failed,
+            campaign.max_editions as i64,
+            campaign.editions_minted as i64,
+            slot as i64,
+        ],
+    )?;
+    conn.execute("DELETE FROM stretch_goals WHERE campaign = ?1", params![address.to_string()])?;
+    for (position, goal) in campaign.stretch_goals.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO stretch_goals (campaign, position, threshold_lamports, reward_uri, reached_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                address.to_string(),
+                position as i64,
+                goal.threshold_lamports as i64,
+                goal.reward_uri,
+                goal.reached_at,
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+pub fn upsert_supporter_funding(
+    conn: &Connection,
+    address: &Pubkey,
+    funding: &SupporterFunding,
+    slot: u64,
+) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO supporter_fundings (
+            address, campaign, supporter, amount_lamports, funded_at, is_claimed, nft_minted,
+            edition_number, edition_mint, reward_tier, shares_minted, pledge_source, pledge_status, updated_slot
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+        params![
+            address.to_string(),
+            funding.campaign.to_string(),
+            funding.supporter.to_string(),
+            funding.amount_lamports as i64,
+            funding.funded_at,
+            funding.is_claimed,
+            funding.nft_minted,
+            funding.edition_number as i64,
+            optional_pubkey(&funding.edition_mint),
+            funding.reward_tier,
+            funding.shares_minted as i64,
+            optional_pubkey(&funding.pledge_source),
+            funding.pledge_status.map(|status| format!("{:?}", status)),
+            slot as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn upsert_creator_profile(
+    conn: &Connection,
+    address: &Pubkey,
+    profile: &CreatorProfile,
+    slot: u64,
+) -> Result<()> {
+    let socials_hash: String = profile.socials_hash.iter().map(|byte| format!("{:02x}", byte)).collect();
+    conn.execute(
+        "INSERT OR REPLACE INTO creator_profiles (
+            address, creator, display_name, avatar_uri, socials_hash, campaign_count, campaigns_succeeded,
+            campaigns_failed, campaigns_cancelled, campaigns_taken_down, total_raised_lamports,
+            total_refunded_lamports, updated_slot
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        params![
+            address.to_string(),
+            profile.creator.to_string(),
+            profile.display_name,
+            profile.avatar_uri,
+            socials_hash,
+            profile.campaign_count as i64,
+            profile.campaigns_succeeded as i64,
+            profile.campaigns_failed as i64,
+            profile.campaigns_cancelled as i64,
+            profile.campaigns_taken_down as i64,
+            profile.total_raised_lamports as i64,
+            profile.total_refunded_lamports as i64,
+            slot as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn upsert_creator_verification(
+    conn: &Connection,
+    address: &Pubkey,
+    verification: &CreatorVerification,
+    slot: u64,
+) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO creator_verifications (
+            address, creator, attester, tier, issued_at, expires_at, updated_slot
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            address.to_string(),
+            verification.creator.to_string(),
+            verification.attester.to_string(),
+            verification.tier,
+            verification.issued_at,
+            verification.expires_at,
+            slot as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn upsert_invitation(conn: &Connection, address: &Pubkey, invitation: &Invitation, slot: u64) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO invitations (address, campaign, supporter, invited_at, updated_slot)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            address.to_string(),
+            invitation.campaign.to_string(),
+            invitation.supporter.to_string(),
+            invitation.invited_at,
+            slot as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn upsert_dispute(conn: &Connection, address: &Pubkey, dispute: &Dispute, slot: u64) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO disputes (
+            address, campaign, supporter, evidence_hash, filed_at, status, resolved_at, updated_slot
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            address.to_string(),
+            dispute.campaign.to_string(),
+            dispute.supporter.to_string(),
+            content::hash_hex(&dispute.evidence_hash),
+            dispute.filed_at,
+            format!("{:?}", dispute.status),
+            (dispute.status != DisputeStatus::Open).then_some(dispute.resolved_at),
+            slot as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn upsert_supporter_profile(
+    conn: &Connection,
+    address: &Pubkey,
+    profile: &SupporterProfile,
+    slot: u64,
+) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO supporter_profiles (
+            address, supporter, total_contributed_lamports, campaigns_backed, refunds_received,
+            total_refunded_lamports, nfts_claimed, updated_slot
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            address.to_string(),
+            profile.supporter.to_string(),
+            profile.total_contributed_lamports as i64,
+            profile.campaigns_backed as i64,
+            profile.refunds_received as i64,
+            profile.total_refunded_lamports as i64,
+            profile.nfts_claimed as i64,
+            slot as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+// Drop rows for accounts that no longer exist on-chain
+pub fn delete_account(conn: &Connection, address: &Pubkey) -> Result<()> {
+    let address = address.to_string();
+    conn.execute("DELETE FROM campaigns WHERE address = ?1", params![address])?;
+    conn.execute("DELETE FROM stretch_goals WHERE campaign = ?1", params![address])?;
+    conn.execute("DELETE FROM supporter_fundings WHERE address = ?1", params![address])?;
+    conn.execute("DELETE FROM creator_profiles WHERE address = ?1", params![address])?;
+    conn.execute("DELETE FROM creator_verifications WHERE address = ?1", params![address])?;
+    conn.execute("DELETE FROM supporter_profiles WHERE address = ?1", params![address])?;
+    conn.execute("DELETE FROM disputes WHERE address = ?1", params![address])?;
+    conn.execute("DELETE FROM invitations WHERE address = ?1", params![address])?;
+    Ok(())
+}
+
+pub fn insert_contribution(
+    conn: &Connection,
+    source: &EventSource,
+    event: &Contributed,
+) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO contributions (
+            signature, event_index, campaign, supporter, amount_lamports, raised_lamports, slot, block_time
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            source.signature,
+            source.event_index,
+            event.campaign.to_string(),
+            event.supporter.to_string(),
+            event.amount_lamports as i64,
+            event.raised_lamports as i64,
+            source.slot as i64,
+            source.block_time,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn insert_withdrawal(
+    conn: &Connection,
+    source: &EventSource,
+    event: &FundsWithdrawn,
+) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO withdrawals (
+            signature, event_index, campaign, creator, creator_amount, platform_fee, slot, block_time
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            source.signature,
+            source.event_index,
+            event.campaign.to_string(),
+            event.creator.to_string(),
+            event.creator_amount as i64,
+            event.platform_fee as i64,
+            source.slot as i64,
+            source.block_time,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn insert_contribution_withdrawal(
+    conn: &Connection,
+    source: &EventSource,
+    event: &ContributionWithdrawn,
+) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO contribution_withdrawals (
+            signature, event_index, campaign, supporter, amount, raised_lamports, slot, block_time
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            source.signature,
+            source.event_index,
+            event.campaign.to_string(),
+            event.supporter.to_string(),
+            event.amount as i64,
+            event.raised_lamports as i64,
+            source.slot as i64,
+            source.block_time,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn insert_refund(conn: &Connection, source: &EventSource, event: &RefundClaimed) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO refunds (
+            signature, event_index, campaign, supporter, amount_lamports, shares_burned, slot, block_time
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            source.signature,
+            source.event_index,
+            event.campaign.to_string(),
+            event.supporter.to_string(),
+            event.amount_lamports as i64,
+            event.shares_burned as i64,
+            source.slot as i64,
+            source.block_time,
+        ],
+    )?;
+    Ok(())
+}
+
+// Run a read-only query and return its rows as JSON objects keyed by column name.
+// Flag columns hold SQLite integers and are returned as JSON booleans.
+pub fn insert_update(conn: &Connection, source: &EventSource, event: &UpdatePosted) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO updates (
+            address, campaign, sequence, posted_at, content_uri, content_hash, is_milestone, signature, slot
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            event.update.to_string(),
+            event.campaign.to_string(),
+            event.sequence,
+            event.posted_at,
+            event.content_uri,
+            content::hash_hex(&event.content_hash),
+            event.is_milestone,
+            source.signature,
+            source.slot as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn query_json(conn: &Connection, sql: &str, args: &[&dyn rusqlite::ToSql]) -> Result<Vec<Value>> {
+    let mut stmt = conn.prepare(sql)?;
+    let columns: Vec<String> = stmt.column_names().iter().map(|name| name.to_string()).collect();
+    let mut rows = stmt.query(args)?;
+    let mut results = Vec::new();
+    while let Some(row) = rows.next()? {
+        let mut object = Map::new();
+        for (i, column) in columns.iter().enumerate() {
+            let boolean = column.starts_with("is_")
+                || matches!(column.as_str(), "nft_in_escrow" | "nft_minted" | "outcome_recorded");
+            let value = match row.get::<_, SqlValue>(i)? {
+                SqlValue::Null => Value::Null,
+                SqlValue::Integer(n) if boolean => Value::Bool(n != 0),
+                SqlValue::Integer(n) => Value::from(n),
+                SqlValue::Real(n) => Value::from(n),
+                SqlValue::Text(text) => Value::String(text),
+                SqlValue::Blob(bytes) => Value::from(bytes),
+            };
+            object.insert(column.clone(), value);
+        }
+        results.push(Value::Object(object));
+    }
+    Ok(results)
+}
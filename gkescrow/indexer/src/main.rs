@@ -0,0 +1,53 @@
+// Off-chain indexer for gkescrow: follows program transactions from an RPC
+// endpoint, stores campaigns, supporter funding records, contributions and their
+// withdrawals, creator withdrawals, refunds and creator updates in SQLite, and
+// serves them over a read-only HTTP API, including a per-supporter feed of updates.
+// Works against any RPC endpoint, including a local solana-test-validator.
+
+mod api;
+mod db;
+mod sync;
+
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+
+#[derive(Parser)]
+#[command(name = "gkescrow-indexer", about = "Index GotongKarya campaigns and serve them over HTTP")]
+struct Cli {
+    /// RPC endpoint URL
+    #[arg(short, long, env = "GKESCROW_RPC_URL", default_value = "http://localhost:8899")]
+    url: String,
+
+    /// SQLite database file
+    #[arg(short, long, env = "GKESCROW_INDEXER_DB", default_value = "gkescrow-indexer.db")]
+    database: String,
+
+    /// Address the HTTP API listens on
+    #[arg(short, long, env = "GKESCROW_INDEXER_LISTEN", default_value = "127.0.0.1:8080")]
+    listen: String,
+
+    /// Seconds between polls for new transactions
+    #[arg(long, default_value_t = 2)]
+    poll_interval: u64,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+
+    // Separate connections so API reads never wait on the indexer's writes
+    let indexer_conn = db::open(&cli.database)?;
+    let api_conn = db::open(&cli.database)?;
+
+    let server = tiny_http::Server::http(&cli.listen).map_err(|err| anyhow!("failed to listen on {}: {}", cli.listen, err))?;
+    println!("Serving API on http://{}", cli.listen);
+    thread::spawn(move || api::serve(server, api_conn));
+
+    println!("Indexing {} from {}", gkescrow_client::ID, cli.url);
+    sync::Indexer::new(rpc, indexer_conn).run(Duration::from_secs(cli.poll_interval))
+}
@@ -0,0 +1,242 @@
+// Follows gkescrow transactions from an RPC endpoint and writes them to the database.
+// Each poll walks back from the newest signature to the stored cursor, then
+// processes the new transactions oldest first, one database transaction each.
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Result};
+use gkescrow_client::events::{self, ProgramEvent};
+use gkescrow_client::{
+    accounts, pda, Campaign, CreatorProfile, CreatorVerification, Dispute, Invitation, SupporterFunding,
+    SupporterProfile,
+};
+use rusqlite::Connection;
+use solana_rpc_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_rpc_client_api::config::RpcTransactionConfig;
+use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::option_serializer::OptionSerializer;
+use solana_transaction_status_client_types::{UiInstruction, UiTransactionEncoding};
+
+use crate::db::{self, EventSource};
+
+// Largest page getSignaturesForAddress allows
+const SIGNATURE_PAGE_LIMIT: usize = 1000;
+
+pub struct Indexer {
+    rpc: RpcClient,
+    conn: Connection,
+}
+
+impl Indexer {
+    pub fn new(rpc: RpcClient, conn: Connection) -> Self {
+        Indexer { rpc, conn }
+    }
+
+    pub fn run(mut self, poll_interval: Duration) -> ! {
+        loop {
+            match self.poll() {
+                Ok(0) => {}
+                Ok(count) => println!("Indexed {} transaction(s)", count),
+                Err(err) => eprintln!("Indexing failed, retrying: {}", err),
+            }
+            thread::sleep(poll_interval);
+        }
+    }
+
+    // Index every program transaction newer than the cursor
+    pub fn poll(&mut self) -> Result<usize> {
+        let until = db::cursor(&self.conn)?.map(|signature| Signature::from_str(&signature)).transpose()?;
+        let mut pending = Vec::new();
+        let mut before = None;
+        loop {
+            let page = self.rpc.get_signatures_for_address_with_config(
+                &gkescrow_client::ID,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until,
+                    limit: Some(SIGNATURE_PAGE_LIMIT),
+                    commitment: Some(self.rpc.commitment()),
+                },
+            )?;
+            let Some(oldest) = page.last() else {
+                break;
+            };
+            before = Some(Signature::from_str(&oldest.signature)?);
+            pending.extend(page);
+        }
+
+        // Signatures come newest first
+        for status in pending.iter().rev() {
+            let tx = self.conn.transaction()?;
+            // Failed transactions changed nothing; only the cursor moves past them
+            if status.err.is_none() {
+                index_transaction(&self.rpc, &tx, &status.signature)?;
+            }
+            db::set_cursor(&tx, &status.signature, status.slot)?;
+            tx.commit()?;
+        }
+        Ok(pending.len())
+    }
+}
+
+fn index_transaction(rpc: &RpcClient, conn: &Connection, signature: &str) -> Result<()> {
+    let confirmed = rpc.get_transaction_with_config(
+        &Signature::from_str(signature)?,
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(rpc.commitment()),
+            max_supported_transaction_version: Some(0),
+        },
+    )?;
+    let meta = confirmed
+        .transaction
+        .meta
+        .ok_or_else(|| anyhow!("transaction {} has no status meta", signature))?;
+    let transaction = confirmed
+        .transaction
+        .transaction
+        .decode()
+        .ok_or_else(|| anyhow!("failed to decode transaction {}", signature))?;
+
+    // Static keys followed by address lookup table keys, as instructions index them
+    let mut keys = transaction.message.static_account_keys().to_vec();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        for key in loaded.writable.iter().chain(&loaded.readonly) {
+            keys.push(Pubkey::from_str(key)?);
+        }
+    }
+    let is_program = |index: u8| keys.get(index as usize) == Some(&gkescrow_client::ID);
+
+    let instructions: Vec<&str> = transaction
+        .message
+        .instructions()
+        .iter()
+        .filter(|ix| is_program(ix.program_id_index))
+        .filter_map(|ix| events::instruction_name(&ix.data))
+        .collect();
+
+    let mut program_events = meta.log_messages.as_ref().map(|logs| events::from_logs(logs)).unwrap_or_default();
+    if let OptionSerializer::Some(inner) = &meta.inner_instructions {
+        for ix in inner.iter().flat_map(|inner| &inner.instructions) {
+            let UiInstruction::Compiled(ix) = ix else {
+                continue;
+            };
+            if !is_program(ix.program_id_index) {
+                continue;
+            }
+            if let Some(event) = bs58::decode(&ix.data).into_vec().ok().and_then(|data| events::decode_cpi(&data)) {
+                program_events.push(event);
+            }
+        }
+    }
+
+    db::insert_transaction(conn, signature, confirmed.slot, confirmed.block_time, &instructions)?;
+
+    let mut campaigns = BTreeSet::new();
+    let mut fundings = BTreeSet::new();
+    let mut creators = BTreeSet::new();
+    let mut supporters = BTreeSet::new();
+    let mut disputes = BTreeSet::new();
+    let mut verifications = BTreeSet::new();
+    let mut invitations = BTreeSet::new();
+    for (event_index, event) in program_events.iter().enumerate() {
+        let source = EventSource {
+            signature,
+            event_index,
+            slot: confirmed.slot,
+            block_time: confirmed.block_time,
+        };
+        match event {
+            ProgramEvent::Contributed(event) => db::insert_contribution(conn, &source, event)?,
+            ProgramEvent::FundsWithdrawn(event) => db::insert_withdrawal(conn, &source, event)?,
+            ProgramEvent::RefundClaimed(event) => db::insert_refund(conn, &source, event)?,
+            ProgramEvent::ContributionWithdrawn(event) => db::insert_contribution_withdrawal(conn, &source, event)?,
+            ProgramEvent::UpdatePosted(event) => db::insert_update(conn, &source, event)?,
+            _ => {}
+        }
+        match event {
+            ProgramEvent::CreatorProfileUpdated(event) => {
+                creators.insert(event.creator);
+            }
+            ProgramEvent::DisputeFiled(event) => {
+                disputes.insert(event.dispute);
+            }
+            ProgramEvent::DisputeResolved(event) => {
+                disputes.insert(event.dispute);
+            }
+            ProgramEvent::SupporterInvited(event) => {
+                invitations.insert(pda::invitation(&event.campaign, &event.supporter).0);
+            }
+            ProgramEvent::InvitationRevoked(event) => {
+                invitations.insert(pda::invitation(&event.campaign, &event.supporter).0);
+            }
+            ProgramEvent::CreatorVerified(event) => {
+                verifications.insert(event.creator);
+            }
+            ProgramEvent::VerificationRevoked(event) => {
+                verifications.insert(event.creator);
+            }
+            _ => {}
+        }
+        if let Some(campaign) = event.campaign() {
+            campaigns.insert(campaign);
+            if let Some(supporter) = event.supporter() {
+                fundings.insert(pda::supporter_funding(&campaign, &supporter).0);
+                supporters.insert(supporter);
+            }
+        }
+    }
+
+    // Account rows hold the latest state, so re-read whatever the events touched.
+    // Campaign changes can also move their creator's stats.
+    for address in &campaigns {
+        if let Some(campaign) = refresh::<Campaign>(rpc, conn, address, db::upsert_campaign)? {
+            creators.insert(campaign.creator);
+        }
+    }
+    for address in &fundings {
+        refresh::<SupporterFunding>(rpc, conn, address, db::upsert_supporter_funding)?;
+    }
+    for address in &disputes {
+        refresh::<Dispute>(rpc, conn, address, db::upsert_dispute)?;
+    }
+    for address in &invitations {
+        refresh::<Invitation>(rpc, conn, address, db::upsert_invitation)?;
+    }
+    // Revoked verifications are closed, so the refresh drops their rows
+    for creator in &verifications {
+        let address = pda::creator_verification(creator).0;
+        refresh::<CreatorVerification>(rpc, conn, &address, db::upsert_creator_verification)?;
+    }
+    for supporter in &supporters {
+        refresh::<SupporterProfile>(rpc, conn, &pda::supporter_profile(supporter).0, db::upsert_supporter_profile)?;
+    }
+    for creator in &creators {
+        refresh::<CreatorProfile>(rpc, conn, &pda::creator_profile(creator).0, db::upsert_creator_profile)?;
+    }
+    Ok(())
+}
+
+fn refresh<T: anchor_lang::AccountDeserialize>(
+    rpc: &RpcClient,
+    conn: &Connection,
+    address: &Pubkey,
+    upsert: fn(&Connection, &Pubkey, &T, u64) -> Result<()>,
+) -> Result<Option<T>> {
+    let response = rpc.get_account_with_commitment(address, rpc.commitment())?;
+    match response.value {
+        Some(account) if account.owner == gkescrow_client::ID => {
+            let data: T = accounts::deserialize(&account.data)?;
+            upsert(conn, address, &data, response.context.slot)?;
+            Ok(Some(data))
+        }
+        _ => {
+            db::delete_account(conn, address)?;
+            Ok(None)
+        }
+    }
+}
@@ -1,9 +1,15 @@
 use anchor_lang::prelude::*;
-use std::str::FromStr;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::program_option::COption;
 
 // Import necessary SPL token libraries
-use anchor_spl::token::{self, Token, MintTo, Mint, TokenAccount};
+use anchor_spl::token::{self, Token, Mint, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
+use anchor_spl::token_interface::spl_token_2022::extension::ExtensionType;
 use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::{self, Metadata, MetadataAccount};
+use anchor_spl::metadata::mpl_token_metadata::accounts::{Edition, MasterEdition};
+use anchor_spl::metadata::mpl_token_metadata::types::{Collection, CollectionDetails, DataV2, Key as MetadataKey};
 
 // Define Metaplex Token Metadata program ID
 pub const METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
@@ -12,26 +18,76 @@ pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
 declare_id!("6saaps2jtMCng2XxkuBG3Cizvcsfy2LBm8AGicK2NamF");
 
 // Platform fee percentage (2.5% = 25/1000)
-const PLATFORM_FEE_NUMERATOR: u64 = 25;
-const PLATFORM_FEE_DENOMINATOR: u64 = 1000;
+pub const PLATFORM_FEE_NUMERATOR: u64 = 25;
+pub const PLATFORM_FEE_DENOMINATOR: u64 = 1000;
+
+// Window after the campaign ends during which supporters can claim escrowed NFTs
+// (10 minutes for hackathon demo)
+const NFT_CLAIM_WINDOW_SECONDS: i64 = 10 * 60;
+
+// Platform-wide parent collection that every campaign collection is verified into
+pub const PLATFORM_COLLECTION_NAME: &str = "GotongKarya Supporters";
+pub const PLATFORM_COLLECTION_SYMBOL: &str = "GKS";
+
+// Share tokens use SOL's decimals so one token represents one contributed SOL
+const SHARE_TOKEN_DECIMALS: u8 = 9;
+
+// String limits in bytes. NFT name, symbol and URI match the Metaplex Token Metadata limits.
+pub const MAX_PROJECT_NAME_LEN: usize = 64;
+pub const MAX_DESCRIPTION_LEN: usize = 280; // Short summary; the full description lives at the content URI
+pub const MAX_NFT_NAME_LEN: usize = 32;
+pub const MAX_NFT_SYMBOL_LEN: usize = 10;
+pub const MAX_URI_LEN: usize = 200;
+
+// Creator profile limits; profiles are allocated at full size up front
+pub const MAX_DISPLAY_NAME_LEN: usize = 32;
+pub const MAX_AVATAR_URI_LEN: usize = MAX_URI_LEN;
+
+// Window after a campaign ends during which supporters can file disputes and the
+// creator can't withdraw. The admin can change it for new campaigns.
+pub const DEFAULT_DISPUTE_WINDOW_SECONDS: i64 = 3 * 24 * 60 * 60;
+pub const MAX_DISPUTE_WINDOW_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+// Campaign addresses stored per registry page
+pub const CAMPAIGNS_PER_PAGE: u64 = 64;
+
+// Stretch goals a campaign can announce above its funding goal
+pub const MAX_STRETCH_GOALS: usize = 5;
 
 // Admin public key for treasury management
-pub const ADMIN_PUBKEY: &str = "ZaikXX6zRGseZdyGnpdBaTkBdetDNgZcGEqzeZgAXtM";
+pub const ADMIN_PUBKEY: Pubkey = pubkey!("ZaikXX6zRGseZdyGnpdBaTkBdetDNgZcGEqzeZgAXtM");
 
 #[program]
 pub mod gkescrow {
     use super::*;
 
     // Create a new campaign with NFT reward - this creates a master edition NFT
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_campaign(
         ctx: Context<InitializeCampaign>,
         project_name: String,
         description: String,
+        content: CampaignContent,
         funding_goal_lamports: u64,
         nft_name: String,
         nft_symbol: String,
         nft_uri: String,
+        reward_mode: RewardMode,
+        access: CampaignAccess,
     ) -> Result<()> {
+        validate_string(&project_name, MAX_PROJECT_NAME_LEN, ErrorCode::EmptyProjectName, ErrorCode::ProjectNameTooLong)?;
+        validate_description(&description)?;
+        validate_content(&content)?;
+        validate_string(&nft_name, MAX_NFT_NAME_LEN, ErrorCode::EmptyNftName, ErrorCode::NftNameTooLong)?;
+        validate_string(&nft_symbol, MAX_NFT_SYMBOL_LEN, ErrorCode::EmptyNftSymbol, ErrorCode::NftSymbolTooLong)?;
+        validate_uri(&nft_uri)?;
+        validate_access(&access)?;
+
+        // Share tokens are issued per contributed SOL, which pledge campaigns don't take
+        if ctx.accounts.share_mint.is_some() && ctx.accounts.pledge_mint.is_some() {
+            return Err(ErrorCode::SharesNotSupportedForPledges.into());
+        }
+
         let campaign = &mut ctx.accounts.campaign;
         let creator = &ctx.accounts.creator;
 
@@ -39,41 +95,120 @@ pub mod gkescrow {
         let current_time = Clock::get()?.unix_timestamp;
         let end_timestamp = current_time + (10 * 60); // 10 minutes in seconds
 
+        // Goals above the platform threshold need an unexpired creator verification.
+        // The threshold is in lamports, so token pledge goals aren't compared to it.
+        let requires_verification = ctx.accounts.pledge_mint.is_none()
+            && ctx.accounts.platform_config.verification_threshold_lamports
+                .is_some_and(|threshold| funding_goal_lamports > threshold);
+        let verification_tier = match ctx.accounts.creator_verification.as_deref() {
+            Some(verification) if verification.expires_at > current_time => verification.tier,
+            Some(_) if requires_verification => return Err(ErrorCode::VerificationExpired.into()),
+            None if requires_verification => return Err(ErrorCode::CreatorNotVerified.into()),
+            _ => 0,
+        };
+
+        // Append the campaign to the registry; its position becomes the campaign id
+        let registry = &mut ctx.accounts.campaign_registry;
+        let registry_page = &mut ctx.accounts.registry_page;
+        let campaign_id = registry.campaign_count;
+        registry.bump = ctx.bumps.campaign_registry;
+        registry.campaign_count = campaign_id.checked_add(1).ok_or(ErrorCode::CountOverflow)?;
+        registry_page.page_index = campaign_id / CAMPAIGNS_PER_PAGE;
+        registry_page.campaigns.push(campaign.key());
+
+        // The creator's campaign count is part of the campaign seeds
+        let creator_profile = &mut ctx.accounts.creator_profile;
+        let creator_index = creator_profile.campaign_count;
+        creator_profile.creator = creator.key();
+        creator_profile.bump = ctx.bumps.creator_profile;
+        creator_profile.campaign_count = creator_index.checked_add(1).ok_or(ErrorCode::CountOverflow)?;
+        ctx.accounts.platform_config.bump = ctx.bumps.platform_config;
+
         // Initialize campaign data
         campaign.creator = creator.key();
+        campaign.bump = ctx.bumps.campaign;
+        campaign.id = campaign_id;
+        campaign.creator_index = creator_index;
+        campaign.verification_tier = verification_tier;
         campaign.project_name = project_name;
         campaign.description = description;
+        campaign.content_uri = content.uri;
+        campaign.content_hash = content.hash;
         campaign.funding_goal_lamports = funding_goal_lamports;
         campaign.raised_lamports = 0;
         campaign.supporters_count = 0;
+        campaign.updates_count = 0;
         campaign.is_active = true;
         campaign.is_funded = false;
+        campaign.is_delivered = false;
+        campaign.outcome_recorded = false;
+        campaign.is_frozen = false;
+        campaign.takedown_reason = None;
         campaign.created_at = current_time;
         campaign.end_timestamp = end_timestamp;
+        campaign.claim_deadline = end_timestamp + NFT_CLAIM_WINDOW_SECONDS;
+        campaign.dispute_deadline = end_timestamp + ctx.accounts.platform_config.dispute_window();
+        campaign.open_disputes = 0;
+        campaign.access = access;
+        campaign.stretch_goals = Vec::new(); // Announced later with set_stretch_goals
         
         // NFT metadata
         campaign.nft_name = nft_name;
         campaign.nft_symbol = nft_symbol;
         campaign.nft_uri = nft_uri;
         campaign.nft_mint = ctx.accounts.nft_mint.key();
+        campaign.reward_mode = reward_mode;
+        campaign.collection_mint = Pubkey::default(); // Set once a collection is created or referenced
+        
+        // Supporter share tokens are optional; no mint means the campaign doesn't issue them
+        campaign.share_mint = ctx.accounts.share_mint.as_ref()
+            .map(|share_mint| share_mint.key())
+            .unwrap_or_default();
+        
+        // Token campaigns take pledges in this mint instead of SOL contributions
+        campaign.pledge_mint = ctx.accounts.pledge_mint.as_ref()
+            .map(|pledge_mint| pledge_mint.key())
+            .unwrap_or_default();
+        campaign.pledges_pending = 0;
+        campaign.pledges_failed = 0;
         
         // Edition NFT settings
         campaign.max_editions = 5; // Fixed at 5 for the hackathon demo
         campaign.editions_minted = 0; // Start with 0 minted
 
+        emit!(CampaignCreated {
+            campaign: campaign.key(),
+            id: campaign.id,
+            creator: campaign.creator,
+            project_name: campaign.project_name.clone(),
+            content_uri: campaign.content_uri.clone(),
+            content_hash: campaign.content_hash,
+            funding_goal_lamports: campaign.funding_goal_lamports,
+            end_timestamp: campaign.end_timestamp,
+            reward_mode: campaign.reward_mode,
+            share_mint: campaign.share_mint,
+            pledge_mint: campaign.pledge_mint,
+            access: campaign.access,
+        });
+
         msg!("Campaign initialized successfully!");
+        msg!("Campaign id: {}", campaign.id);
         msg!("Project: {}", campaign.project_name);
         msg!("Goal: {} lamports", campaign.funding_goal_lamports);
         msg!("NFT: {} ({})", campaign.nft_name, campaign.nft_symbol);
+        msg!("Reward mode: {:?}", campaign.reward_mode);
+        msg!("Access: {:?}", campaign.access);
         msg!("End date: {} (unix timestamp)", campaign.end_timestamp);
 
         Ok(())
     }
 
-    // Fund a campaign and receive NFT
+    // Fund a campaign and receive NFT. `allowlist_proof` is only read for
+    // allowlisted campaigns and can be empty otherwise.
     pub fn fund_campaign(
         ctx: Context<FundCampaign>,
         amount_lamports: u64,
+        allowlist_proof: Vec<[u8; 32]>,
     ) -> Result<()> {
         let campaign = &mut ctx.accounts.campaign;
         let supporter = &ctx.accounts.supporter;
@@ -89,6 +224,8 @@ pub mod gkescrow {
             return Err(ErrorCode::CampaignEnded.into());
         }
 
+        check_access(campaign, &supporter.key(), &allowlist_proof, ctx.accounts.invitation.is_some())?;
+
         // Transfer SOL from supporter to campaign escrow
         anchor_lang::system_program::transfer(
             CpiContext::new(
@@ -111,6 +248,10 @@ pub mod gkescrow {
         supporter_funding.nft_minted = false;
         supporter_funding.edition_number = 0; // Will be set when NFT is minted
         supporter_funding.edition_mint = Pubkey::default(); // Initialize to zero, will be set when minted
+        supporter_funding.reward_tier = 0; // Set when the reward is claimed
+        supporter_funding.shares_minted = 0; // Set when share tokens are claimed
+        supporter_funding.pledge_source = Pubkey::default();
+        supporter_funding.pledge_status = None;
 
         // Update campaign stats
         campaign.raised_lamports = campaign.raised_lamports.checked_add(amount_lamports)
@@ -118,10 +259,37 @@ pub mod gkescrow {
         campaign.supporters_count = campaign.supporters_count.checked_add(1)
             .ok_or(ErrorCode::CountOverflow)?;
 
+        // Update the supporter's portfolio
+        let supporter_profile = &mut ctx.accounts.supporter_profile;
+        supporter_profile.supporter = supporter.key();
+        supporter_profile.bump = ctx.bumps.supporter_profile;
+        supporter_profile.total_contributed_lamports = supporter_profile.total_contributed_lamports
+            .checked_add(amount_lamports)
+            .ok_or(ErrorCode::AmountOverflow)?;
+        supporter_profile.campaigns_backed = supporter_profile.campaigns_backed
+            .checked_add(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+
+        emit!(Contributed {
+            campaign: campaign.key(),
+            supporter: supporter.key(),
+            amount_lamports,
+            raised_lamports: campaign.raised_lamports,
+            supporters_count: campaign.supporters_count,
+        });
+
         // Check if campaign is now fully funded
         if campaign.raised_lamports >= campaign.funding_goal_lamports {
+            if !campaign.is_funded {
+                emit!(GoalReached {
+                    campaign: campaign.key(),
+                    funding_goal_lamports: campaign.funding_goal_lamports,
+                    raised_lamports: campaign.raised_lamports,
+                });
+            }
             campaign.is_funded = true;
         }
+        reach_stretch_goals(campaign, current_time);
 
         // In a real implementation, this would mint an NFT to the supporter
         // For now, we'll just record the funding in the supporter_funding account
@@ -136,7 +304,276 @@ pub mod gkescrow {
         Ok(())
     }
 
-    // Withdraw funds if campaign is fully funded (creator only)
+    // Pledge tokens to a token campaign. Nothing moves yet: the supporter approves
+    // the campaign PDA as delegate for the amount and collect_pledges pulls the
+    // tokens once the campaign has succeeded. A token account has a single
+    // delegate, so approving another delegate from the same account afterwards
+    // makes this pledge fail to collect.
+    pub fn pledge(ctx: Context<Pledge>, amount: u64, allowlist_proof: Vec<[u8; 32]>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let supporter = &ctx.accounts.supporter;
+
+        if !campaign.is_active {
+            return Err(ErrorCode::CampaignNotActive.into());
+        }
+
+        let current_time = Clock::get()?.unix_timestamp;
+        if current_time > campaign.end_timestamp {
+            return Err(ErrorCode::CampaignEnded.into());
+        }
+
+        check_access(campaign, &supporter.key(), &allowlist_proof, ctx.accounts.invitation.is_some())?;
+
+        token::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Approve {
+                    to: ctx.accounts.supporter_token_account.to_account_info(),
+                    delegate: campaign.to_account_info(),
+                    authority: supporter.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let supporter_funding = &mut ctx.accounts.supporter_funding;
+        supporter_funding.campaign = campaign.key();
+        supporter_funding.supporter = supporter.key();
+        supporter_funding.amount_lamports = amount; // In pledge mint base units
+        supporter_funding.funded_at = current_time;
+        supporter_funding.is_claimed = false;
+        supporter_funding.nft_minted = false;
+        supporter_funding.edition_number = 0;
+        supporter_funding.edition_mint = Pubkey::default();
+        supporter_funding.reward_tier = 0;
+        supporter_funding.shares_minted = 0;
+        supporter_funding.pledge_source = ctx.accounts.supporter_token_account.key();
+        supporter_funding.pledge_status = Some(PledgeStatus::Pending);
+
+        // Pledges count towards the goal until they fail to collect
+        campaign.raised_lamports = campaign.raised_lamports.checked_add(amount)
+            .ok_or(ErrorCode::AmountOverflow)?;
+        campaign.supporters_count = campaign.supporters_count.checked_add(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+        campaign.pledges_pending = campaign.pledges_pending.checked_add(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+
+        // Token amounts aren't lamports, so the portfolio only counts the campaign
+        let supporter_profile = &mut ctx.accounts.supporter_profile;
+        supporter_profile.supporter = supporter.key();
+        supporter_profile.bump = ctx.bumps.supporter_profile;
+        supporter_profile.campaigns_backed = supporter_profile.campaigns_backed
+            .checked_add(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+
+        emit!(Pledged {
+            campaign: campaign.key(),
+            supporter: supporter.key(),
+            amount,
+            raised_lamports: campaign.raised_lamports,
+            supporters_count: campaign.supporters_count,
+        });
+
+        if campaign.raised_lamports >= campaign.funding_goal_lamports {
+            if !campaign.is_funded {
+                emit!(GoalReached {
+                    campaign: campaign.key(),
+                    funding_goal_lamports: campaign.funding_goal_lamports,
+                    raised_lamports: campaign.raised_lamports,
+                });
+            }
+            campaign.is_funded = true;
+        }
+        reach_stretch_goals(campaign, current_time);
+
+        msg!("Pledge recorded!");
+        msg!("Supporter: {}", supporter.key());
+        msg!("Amount: {}", amount);
+        msg!("Campaign pledged: {}/{}", campaign.raised_lamports, campaign.funding_goal_lamports);
+
+        Ok(())
+    }
+
+    // Permissionless crank that pulls pledged tokens once a token campaign has
+    // succeeded and its dispute window has closed. Remaining accounts are pairs of
+    // a SupporterFunding and its pledge source token account. A pledge whose
+    // approval or balance no longer covers the amount is marked failed and taken
+    // out of the raised total rather than failing the whole batch.
+    // Collected tokens wait in the campaign's pledge escrow. They are released to
+    // the creator once every pledge is settled with the goal still met; if failed
+    // pledges drop the total below the goal, the campaign fails instead and the
+    // collected pledges are refunded from escrow through claim_refund.
+    pub fn collect_pledges<'info>(ctx: Context<'_, '_, 'info, 'info, CollectPledges<'info>>) -> Result<()> {
+        let campaign_info = ctx.accounts.campaign.to_account_info();
+        let campaign = &mut ctx.accounts.campaign;
+
+        if !campaign.is_funded {
+            return Err(ErrorCode::CampaignNotFunded.into());
+        }
+
+        // Same release conditions as withdraw_funds
+        let current_time = Clock::get()?.unix_timestamp;
+        if current_time <= campaign.dispute_deadline {
+            return Err(ErrorCode::DisputeWindowOpen.into());
+        }
+
+        if campaign.open_disputes > 0 {
+            return Err(ErrorCode::DisputesOpen.into());
+        }
+
+        if !ctx.remaining_accounts.len().is_multiple_of(2) {
+            return Err(ErrorCode::InvalidPledgeAccounts.into());
+        }
+
+        // The campaign PDA is the approved delegate on every pledge
+        let creator_key = campaign.creator;
+        let creator_index = campaign.creator_index.to_le_bytes();
+        let seeds = &[
+            b"campaign".as_ref(),
+            creator_key.as_ref(),
+            creator_index.as_ref(),
+            &[campaign.bump],
+        ];
+
+        for pledge in ctx.remaining_accounts.chunks(2) {
+            let (funding_info, source_info) = (&pledge[0], &pledge[1]);
+            let mut supporter_funding = Account::<SupporterFunding>::try_from(funding_info)?;
+            if supporter_funding.campaign != campaign.key() {
+                return Err(ErrorCode::InvalidCampaign.into());
+            }
+
+            // Settled by an earlier crank or cancelled through a dispute
+            if supporter_funding.pledge_status != Some(PledgeStatus::Pending) {
+                continue;
+            }
+
+            if source_info.key() != supporter_funding.pledge_source {
+                return Err(ErrorCode::InvalidPledgeSource.into());
+            }
+
+            let amount = supporter_funding.amount_lamports;
+            let collectable = source_info.owner == &token::ID
+                && TokenAccount::try_deserialize(&mut &source_info.try_borrow_data()?[..]).is_ok_and(|source| {
+                    source.mint == campaign.pledge_mint
+                        && source.delegate == COption::Some(campaign.key())
+                        && source.delegated_amount >= amount
+                        && source.amount >= amount
+                        && !source.is_frozen()
+                });
+
+            if collectable {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        token::Transfer {
+                            from: source_info.clone(),
+                            to: ctx.accounts.pledge_escrow.to_account_info(),
+                            authority: campaign_info.clone(),
+                        },
+                        &[seeds],
+                    ),
+                    amount,
+                )?;
+
+                supporter_funding.pledge_status = Some(PledgeStatus::Collected);
+
+                emit!(PledgeCollected {
+                    campaign: campaign.key(),
+                    supporter: supporter_funding.supporter,
+                    amount,
+                });
+            } else {
+                supporter_funding.pledge_status = Some(PledgeStatus::Failed);
+                campaign.raised_lamports = campaign.raised_lamports
+                    .checked_sub(amount)
+                    .ok_or(ErrorCode::AmountOverflow)?;
+                campaign.pledges_failed = campaign.pledges_failed
+                    .checked_add(1)
+                    .ok_or(ErrorCode::CountOverflow)?;
+
+                emit!(PledgeFailed {
+                    campaign: campaign.key(),
+                    supporter: supporter_funding.supporter,
+                    amount,
+                });
+            }
+
+            campaign.pledges_pending = campaign.pledges_pending
+                .checked_sub(1)
+                .ok_or(ErrorCode::CountOverflow)?;
+            supporter_funding.exit(&crate::ID)?;
+
+            // Nothing more is collected once the goal can no longer be met
+            if campaign.raised_lamports < campaign.funding_goal_lamports {
+                break;
+            }
+        }
+
+        let creator_profile = &mut ctx.accounts.creator_profile;
+        if campaign.raised_lamports < campaign.funding_goal_lamports {
+            // Pending pledges are never charged; collected ones become refundable
+            campaign.is_funded = false;
+            creator_profile.campaigns_failed = creator_profile.campaigns_failed
+                .checked_add(1)
+                .ok_or(ErrorCode::CountOverflow)?;
+            campaign.outcome_recorded = true;
+
+            emit!(CampaignFailed {
+                campaign: campaign.key(),
+                creator: campaign.creator,
+                raised_lamports: campaign.raised_lamports,
+            });
+        } else if campaign.pledges_pending == 0 && !campaign.outcome_recorded {
+            // Every pledge is settled with the goal met: release the escrow,
+            // which holds exactly the raised total, minus the 2.5% platform fee
+            let platform_fee = campaign.raised_lamports
+                .checked_mul(PLATFORM_FEE_NUMERATOR)
+                .ok_or(ErrorCode::AmountOverflow)?
+                .checked_div(PLATFORM_FEE_DENOMINATOR)
+                .ok_or(ErrorCode::AmountOverflow)?;
+            let creator_amount = campaign.raised_lamports.checked_sub(platform_fee)
+                .ok_or(ErrorCode::AmountOverflow)?;
+
+            for (to, transfer_amount) in [
+                (ctx.accounts.creator_token_account.to_account_info(), creator_amount),
+                (ctx.accounts.treasury_token_account.to_account_info(), platform_fee),
+            ] {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        token::Transfer {
+                            from: ctx.accounts.pledge_escrow.to_account_info(),
+                            to,
+                            authority: campaign_info.clone(),
+                        },
+                        &[seeds],
+                    ),
+                    transfer_amount,
+                )?;
+            }
+
+            creator_profile.campaigns_succeeded = creator_profile.campaigns_succeeded
+                .checked_add(1)
+                .ok_or(ErrorCode::CountOverflow)?;
+            campaign.outcome_recorded = true;
+
+            emit!(FundsWithdrawn {
+                campaign: campaign.key(),
+                creator: campaign.creator,
+                creator_amount,
+                platform_fee,
+            });
+        }
+
+        msg!("Pledges collected!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Raised: {}", campaign.raised_lamports);
+        msg!("Pending pledges: {}", campaign.pledges_pending);
+
+        Ok(())
+    }
+
+    // Withdraw funds of a fully funded campaign once its dispute window has closed (creator only)
     pub fn withdraw_funds(ctx: Context<WithdrawFunds>) -> Result<()> {
         let campaign = &mut ctx.accounts.campaign;
         let creator = &ctx.accounts.creator;
@@ -152,18 +589,36 @@ pub mod gkescrow {
             return Err(ErrorCode::Unauthorized.into());
         }
 
-        // Get campaign balance
-        let campaign_balance = **campaign.to_account_info().lamports.borrow();
+        // Supporters get the dispute window to object before funds are released
+        let current_time = Clock::get()?.unix_timestamp;
+        if current_time <= campaign.dispute_deadline {
+            return Err(ErrorCode::DisputeWindowOpen.into());
+        }
+
+        if campaign.open_disputes > 0 {
+            return Err(ErrorCode::DisputesOpen.into());
+        }
+
+        // Everything above the rent-exempt minimum is withdrawable; the rent keeps
+        // the campaign account alive for its rewards, disputes and history
+        let campaign_info = campaign.to_account_info();
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(campaign_info.data_len());
+        let withdrawable = campaign_info.lamports()
+            .checked_sub(rent_exempt_minimum)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        if withdrawable == 0 {
+            return Err(ErrorCode::InsufficientFunds.into());
+        }
         
         // Calculate 2.5% platform fee
-        let platform_fee = campaign_balance
+        let platform_fee = withdrawable
             .checked_mul(PLATFORM_FEE_NUMERATOR)
             .ok_or(ErrorCode::AmountOverflow)?
             .checked_div(PLATFORM_FEE_DENOMINATOR)
             .ok_or(ErrorCode::AmountOverflow)?;
         
-        // Calculate creator amount (total - platform fee)
-        let creator_amount = campaign_balance.checked_sub(platform_fee)
+        // Calculate creator amount (withdrawable - platform fee)
+        let creator_amount = withdrawable.checked_sub(platform_fee)
             .ok_or(ErrorCode::AmountOverflow)?;
         
         // Transfer platform fee to treasury
@@ -174,6 +629,25 @@ pub mod gkescrow {
         **campaign.to_account_info().try_borrow_mut_lamports()? -= creator_amount;
         **creator.to_account_info().try_borrow_mut_lamports()? += creator_amount;
 
+        // Count the success in the creator's track record on the first withdrawal
+        if !campaign.outcome_recorded {
+            let creator_profile = &mut ctx.accounts.creator_profile;
+            creator_profile.campaigns_succeeded = creator_profile.campaigns_succeeded
+                .checked_add(1)
+                .ok_or(ErrorCode::CountOverflow)?;
+            creator_profile.total_raised_lamports = creator_profile.total_raised_lamports
+                .checked_add(campaign.raised_lamports)
+                .ok_or(ErrorCode::AmountOverflow)?;
+            campaign.outcome_recorded = true;
+        }
+
+        emit!(FundsWithdrawn {
+            campaign: campaign.key(),
+            creator: creator.key(),
+            creator_amount,
+            platform_fee,
+        });
+
         msg!("Funds withdrawn successfully!");
         msg!("Creator: {}", creator.key());
         msg!("Creator amount: {} lamports", creator_amount);
@@ -184,19 +658,28 @@ pub mod gkescrow {
 
     // Refund if campaign failed to meet goal after end date
     pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
-        let campaign = &ctx.accounts.campaign;
+        let campaign = &mut ctx.accounts.campaign;
         let supporter_funding = &mut ctx.accounts.supporter_funding;
         let supporter = &ctx.accounts.supporter;
         
-        // Ensure campaign has ended
-        let current_time = Clock::get()?.unix_timestamp;
-        if current_time <= campaign.end_timestamp {
-            return Err(ErrorCode::CampaignStillActive.into());
+        // Pledges are only charged once collected; collected ones are refunded from
+        // the pledge escrow when failed pledges sink the campaign below its goal
+        if !supporter_funding.is_paid() {
+            return Err(ErrorCode::PledgeNotRefundable.into());
         }
         
-        // Ensure campaign is not fully funded
-        if campaign.is_funded {
-            return Err(ErrorCode::CampaignAlreadyFunded.into());
+        // Taken-down campaigns refund regardless of funding state or end date
+        if campaign.takedown_reason.is_none() {
+            // Ensure campaign has ended; cancelled campaigns refund right away
+            let current_time = Clock::get()?.unix_timestamp;
+            if current_time <= campaign.end_timestamp && campaign.is_active {
+                return Err(ErrorCode::CampaignStillActive.into());
+            }
+            
+            // Ensure campaign is not fully funded
+            if campaign.is_funded {
+                return Err(ErrorCode::CampaignAlreadyFunded.into());
+            }
         }
         
         // Ensure refund hasn't been claimed yet
@@ -207,13 +690,111 @@ pub mod gkescrow {
         // Calculate refund amount
         let refund_amount = supporter_funding.amount_lamports;
         
-        // Transfer SOL from campaign account back to supporter
-        **campaign.to_account_info().try_borrow_mut_lamports()? -= refund_amount;
-        **supporter.to_account_info().try_borrow_mut_lamports()? += refund_amount;
+        // Share tokens issued for this contribution are burned before the refund
+        let shares_burned = supporter_funding.shares_minted;
+        if supporter_funding.shares_minted > 0 {
+            let (Some(share_mint), Some(supporter_share_account), Some(token_program)) = (
+                ctx.accounts.share_mint.as_ref(),
+                ctx.accounts.supporter_share_account.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ) else {
+                return Err(ErrorCode::ShareAccountsRequired.into());
+            };
+            
+            token::burn(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    token::Burn {
+                        mint: share_mint.to_account_info(),
+                        from: supporter_share_account.to_account_info(),
+                        authority: supporter.to_account_info(),
+                    },
+                ),
+                supporter_funding.shares_minted,
+            )?;
+            
+            msg!("Share tokens burned: {}", supporter_funding.shares_minted);
+            supporter_funding.shares_minted = 0;
+        }
+        
+        let is_pledge = supporter_funding.pledge_status.is_some();
+        if is_pledge {
+            let (Some(pledge_escrow), Some(pledge_source), Some(token_program)) = (
+                ctx.accounts.pledge_escrow.as_ref(),
+                ctx.accounts.pledge_source.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ) else {
+                return Err(ErrorCode::InvalidPledgeAccounts.into());
+            };
+            
+            // Create seeds for campaign PDA signing
+            let creator_key = campaign.creator;
+            let creator_index = campaign.creator_index.to_le_bytes();
+            let seeds = &[
+                b"campaign".as_ref(),
+                creator_key.as_ref(),
+                creator_index.as_ref(),
+                &[campaign.bump],
+            ];
+            
+            // Return the collected tokens to the account they were pledged from
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    token::Transfer {
+                        from: pledge_escrow.to_account_info(),
+                        to: pledge_source.to_account_info(),
+                        authority: campaign.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                refund_amount,
+            )?;
+        } else {
+            // Transfer SOL from campaign account back to supporter
+            let campaign_lamports = campaign.to_account_info().lamports()
+                .checked_sub(refund_amount)
+                .ok_or(ErrorCode::InsufficientFunds)?;
+            let supporter_lamports = supporter.lamports()
+                .checked_add(refund_amount)
+                .ok_or(ErrorCode::AmountOverflow)?;
+            **campaign.to_account_info().try_borrow_mut_lamports()? = campaign_lamports;
+            **supporter.to_account_info().try_borrow_mut_lamports()? = supporter_lamports;
+        }
         
         // Mark as claimed
         supporter_funding.is_claimed = true;
         
+        // Refunds count against the creator; the first one also records the failure.
+        // Token refunds aren't lamports, so they only count towards the failure.
+        let creator_profile = &mut ctx.accounts.creator_profile;
+        if !is_pledge {
+            creator_profile.total_refunded_lamports = creator_profile.total_refunded_lamports
+                .checked_add(refund_amount)
+                .ok_or(ErrorCode::AmountOverflow)?;
+        }
+        if !campaign.outcome_recorded {
+            creator_profile.campaigns_failed = creator_profile.campaigns_failed
+                .checked_add(1)
+                .ok_or(ErrorCode::CountOverflow)?;
+            campaign.outcome_recorded = true;
+        }
+        
+        let supporter_profile = &mut ctx.accounts.supporter_profile;
+        supporter_profile.refunds_received = supporter_profile.refunds_received
+            .checked_add(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+        supporter_profile.total_refunded_lamports = supporter_profile.total_refunded_lamports
+            .checked_add(refund_amount)
+            .ok_or(ErrorCode::AmountOverflow)?;
+        
+        emit!(RefundClaimed {
+            campaign: campaign.key(),
+            supporter: supporter.key(),
+            amount_lamports: refund_amount,
+            shares_burned,
+        });
+        
         msg!("Refund claimed successfully!");
         msg!("Supporter: {}", supporter.key());
         msg!("Amount refunded: {} lamports", refund_amount);
@@ -227,12 +808,6 @@ pub mod gkescrow {
         let admin = &ctx.accounts.admin;
         let treasury = &ctx.accounts.treasury;
         
-        // Verify the admin is the correct authorized admin
-        let admin_pubkey = Pubkey::from_str(ADMIN_PUBKEY).unwrap();
-        if admin.key() != admin_pubkey {
-            return Err(ErrorCode::Unauthorized.into());
-        }
-        
         // Check that treasury has enough funds
         let treasury_balance = **treasury.to_account_info().lamports.borrow();
         if treasury_balance < amount {
@@ -260,12 +835,54 @@ pub mod gkescrow {
             &[treasury_seeds],
         )?;
         
+        emit!(TreasuryWithdrawn {
+            admin: admin.key(),
+            amount,
+        });
+        
         msg!("Treasury funds withdrawn successfully!");
         msg!("Admin: {}", admin.key());
         
         Ok(())
     }
     
+    // Admin function to withdraw platform fees collected from token pledges
+    pub fn withdraw_treasury_tokens(ctx: Context<WithdrawTreasuryTokens>, amount: u64) -> Result<()> {
+        let admin = &ctx.accounts.admin;
+        
+        if ctx.accounts.treasury_token_account.amount < amount {
+            return Err(ErrorCode::InsufficientFunds.into());
+        }
+        
+        let bump = ctx.bumps.treasury;
+        let treasury_seeds = &[b"treasury".as_ref(), &[bump]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.treasury_token_account.to_account_info(),
+                    to: ctx.accounts.admin_token_account.to_account_info(),
+                    authority: ctx.accounts.treasury.to_account_info(),
+                },
+                &[treasury_seeds],
+            ),
+            amount,
+        )?;
+        
+        emit!(TreasuryTokensWithdrawn {
+            admin: admin.key(),
+            mint: ctx.accounts.mint.key(),
+            amount,
+        });
+        
+        msg!("Treasury tokens withdrawn successfully!");
+        msg!("Mint: {}", ctx.accounts.mint.key());
+        msg!("Amount: {}", amount);
+        
+        Ok(())
+    }
+    
     // Transfer an NFT to the escrow PDA for supporters to claim
     pub fn transfer_nft_to_escrow(ctx: Context<TransferNftToEscrow>) -> Result<()> {
         let campaign = &mut ctx.accounts.campaign;
@@ -297,6 +914,12 @@ pub mod gkescrow {
         // Mark that the NFT is now in escrow and ready for claiming
         campaign.nft_in_escrow = true;
         
+        emit_cpi!(NftEscrowed {
+            campaign: campaign.key(),
+            nft_mint: ctx.accounts.nft_mint.key(),
+            escrow_token_account: ctx.accounts.escrow_token_account.key(),
+        });
+        
         msg!("NFT transferred to escrow successfully!");
         msg!("Campaign: {}", campaign.project_name);
         msg!("NFT Mint: {}", ctx.accounts.nft_mint.key());
@@ -315,11 +938,22 @@ pub fn claim_nft_from_escrow(ctx: Context<ClaimNftFromEscrow>) -> Result<()> {
         return Err(ErrorCode::CampaignNotFunded.into());
     }
     
+    // Soulbound campaigns hand out badges instead of the transferable NFT
+    if campaign.reward_mode != RewardMode::Transferable {
+        return Err(ErrorCode::InvalidRewardMode.into());
+    }
+    
     // Ensure the NFT is in escrow
     if !campaign.nft_in_escrow {
         return Err(ErrorCode::NftNotInEscrow.into());
     }
     
+    // Ensure the claim window hasn't closed
+    let current_time = Clock::get()?.unix_timestamp;
+    if current_time > campaign.claim_deadline {
+        return Err(ErrorCode::ClaimWindowClosed.into());
+    }
+    
     // Ensure NFT hasn't been claimed by this supporter yet
     if supporter_funding.nft_minted {
         return Err(ErrorCode::NftAlreadyMinted.into());
@@ -361,9 +995,23 @@ pub fn claim_nft_from_escrow(ctx: Context<ClaimNftFromEscrow>) -> Result<()> {
         1, // Transfer exactly 1 token (the NFT)
     )?;
     
-    // Mark that this supporter has claimed their NFT
+    // Mark that this supporter has claimed their NFT, at the stretch tier unlocked so far
     supporter_funding.nft_minted = true;
     supporter_funding.edition_mint = ctx.accounts.nft_mint.key();
+    supporter_funding.reward_tier = campaign.stretch_goals_reached();
+    
+    // Count the claim in the supporter's portfolio
+    let supporter_profile = &mut ctx.accounts.supporter_profile;
+    supporter_profile.nfts_claimed = supporter_profile.nfts_claimed.checked_add(1)
+        .ok_or(ErrorCode::CountOverflow)?;
+    
+    emit_cpi!(NftClaimed {
+        campaign: campaign.key(),
+        supporter: supporter_funding.supporter,
+        nft_mint: ctx.accounts.nft_mint.key(),
+        edition_number: supporter_funding.edition_number,
+        reward_tier: supporter_funding.reward_tier,
+    });
     
     msg!("NFT claimed from escrow successfully!");
     msg!("Campaign: {}", campaign.project_name);
@@ -376,12 +1024,25 @@ pub fn claim_nft_from_escrow(ctx: Context<ClaimNftFromEscrow>) -> Result<()> {
 
 // New instruction to update the supporter's NFT mint info after frontend minting
 // Create a struct for updating supporter NFT mint info
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateSupporterNftMint<'info> {
     // Campaign account that must be fully funded
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = !campaign.is_frozen @ ErrorCode::CampaignFrozen,
+        constraint = campaign.takedown_reason.is_none() @ ErrorCode::CampaignTakenDown
+    )]
     pub campaign: Account<'info, Campaign>,
     
+    // Program-wide settings; the instruction is blocked while the platform is paused
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ ErrorCode::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    
     // The supporter funding record to update
     #[account(
         mut,
@@ -391,7 +1052,10 @@ pub struct UpdateSupporterNftMint<'info> {
             supporter_funding.supporter.as_ref(),
         ],
         bump,
-        constraint = supporter_funding.campaign == campaign.key() @ ErrorCode::InvalidCampaign
+        constraint = supporter_funding.campaign == campaign.key() @ ErrorCode::InvalidCampaign,
+        // Contributions returned through a dispute no longer earn rewards
+        constraint = !supporter_funding.is_claimed @ ErrorCode::RefundAlreadyClaimed,
+        constraint = supporter_funding.is_paid() @ ErrorCode::PledgeNotCollected
     )]
     pub supporter_funding: Account<'info, SupporterFunding>,
     
@@ -403,6 +1067,14 @@ pub struct UpdateSupporterNftMint<'info> {
     #[account(mut, constraint = authority.key() == supporter_funding.supporter @ ErrorCode::Unauthorized)]
     pub authority: Signer<'info>,
     
+    // Supporter's portfolio across campaigns
+    #[account(
+        mut,
+        seeds = [b"supporter-profile", authority.key().as_ref()],
+        bump = supporter_profile.bump
+    )]
+    pub supporter_profile: Box<Account<'info, SupporterProfile>>,
+    
     /// CHECK: The master edition NFT mint, verified by constraint to match the campaign's NFT mint
     #[account(constraint = master_edition_mint.key() == campaign.nft_mint @ ErrorCode::InvalidCampaign)]
     pub master_edition_mint: AccountInfo<'info>,
@@ -411,6 +1083,15 @@ pub struct UpdateSupporterNftMint<'info> {
     #[account(mut)]
     pub edition_mint: AccountInfo<'info>,
     
+    /// CHECK: Metaplex edition account of the edition mint; the handler checks it was printed from the campaign NFT
+    #[account(
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), edition_mint.key().as_ref(), b"edition"],
+        seeds::program = token_metadata_program.key(),
+        bump,
+        owner = token_metadata_program.key() @ ErrorCode::InvalidEdition
+    )]
+    pub edition: UncheckedAccount<'info>,
+    
     /// CHECK: Mint authority for the edition mint
     pub mint_authority: Signer<'info>,
     
@@ -421,6 +1102,7 @@ pub struct UpdateSupporterNftMint<'info> {
     // Required programs
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -441,6 +1123,15 @@ pub fn update_supporter_nft_mint(ctx: Context<UpdateSupporterNftMint>, edition_n
         return Err(ErrorCode::NftAlreadyMinted.into());
     }
     
+    // Only editions printed from the campaign's master edition are recorded, since
+    // recorded editions can later be verified into the campaign collection
+    let edition = Edition::try_from(&ctx.accounts.edition.to_account_info())
+        .map_err(|_| ErrorCode::InvalidEdition)?;
+    let (master_edition, _) = MasterEdition::find_pda(&campaign.nft_mint);
+    if edition.key != MetadataKey::EditionV1 || edition.parent != master_edition || edition.edition != edition_number {
+        return Err(ErrorCode::InvalidEdition.into());
+    }
+    
     // Update campaign edition count if needed
     if edition_number > campaign.editions_minted {
         campaign.editions_minted = edition_number;
@@ -450,10 +1141,24 @@ pub fn update_supporter_nft_mint(ctx: Context<UpdateSupporterNftMint>, edition_n
     // This doesn't actually transfer any NFT, just updates the record
     supporter_funding.nft_minted = true;
     supporter_funding.edition_number = edition_number;
+    supporter_funding.reward_tier = campaign.stretch_goals_reached();
+    
+    // Count the claim in the supporter's portfolio
+    let supporter_profile = &mut ctx.accounts.supporter_profile;
+    supporter_profile.nfts_claimed = supporter_profile.nfts_claimed.checked_add(1)
+        .ok_or(ErrorCode::CountOverflow)?;
     
     // Store the edition mint in the supporter funding record for future reference
     supporter_funding.edition_mint = ctx.accounts.edition_mint.key();
     
+    emit_cpi!(NftClaimed {
+        campaign: campaign.key(),
+        supporter: supporter_funding.supporter,
+        nft_mint: supporter_funding.edition_mint,
+        edition_number: supporter_funding.edition_number,
+        reward_tier: supporter_funding.reward_tier,
+    });
+    
     msg!("NFT claim status updated successfully (legacy method)");
     msg!("Campaign: {}", campaign.project_name);
     msg!("Edition number: {}/{}", supporter_funding.edition_number, campaign.max_editions);
@@ -462,364 +1167,4262 @@ pub fn update_supporter_nft_mint(ctx: Context<UpdateSupporterNftMint>, edition_n
     
     Ok(())
 }
-}
 
-#[derive(Accounts)]
-#[instruction(
-    project_name: String,
-    description: String, 
-    funding_goal_lamports: u64,
-    nft_name: String,
-    nft_symbol: String,
-    nft_uri: String
-)]
-pub struct InitializeCampaign<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = Campaign::space(&project_name, &description, &nft_name, &nft_symbol, &nft_uri),
-        seeds = [
-            b"campaign",
-            creator.key().as_ref(),
-            project_name.as_bytes()
-        ],
-        bump
-    )]
-    pub campaign: Account<'info, Campaign>,
-    
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    
-    /// CHECK: This is just a reference to the NFT mint
-    pub nft_mint: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    // Create the campaign's badge mint for soulbound reward modes (creator only).
+    // Soulbound campaigns get a Token-2022 mint with the NonTransferable extension,
+    // Frozen campaigns get a classic SPL mint whose freeze authority is the badge PDA.
+    pub fn initialize_badge_mint(ctx: Context<InitializeBadgeMint>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let token_program = &ctx.accounts.token_program;
 
-#[derive(Accounts)]
-#[instruction(amount_lamports: u64)]
-pub struct FundCampaign<'info> {
-    #[account(mut)]
-    pub campaign: Account<'info, Campaign>,
-    
-    #[account(mut)]
-    pub supporter: Signer<'info>,
-    
-    #[account(
-        init,
-        payer = supporter,
-        space = SupporterFunding::space(),
-        seeds = [
-            b"supporter-funding",
-            campaign.key().as_ref(),
-            supporter.key().as_ref(),
-        ],
-        bump
-    )]
-    pub supporter_funding: Account<'info, SupporterFunding>,
-    
-    pub system_program: Program<'info, System>,
-}
+        // Pick the token program and mint extensions for the campaign's reward mode
+        let (expected_program, extensions) = match campaign.reward_mode {
+            RewardMode::Transferable => return Err(ErrorCode::InvalidRewardMode.into()),
+            RewardMode::Soulbound => (anchor_spl::token_2022::ID, vec![ExtensionType::NonTransferable]),
+            RewardMode::Frozen => (anchor_spl::token::ID, vec![]),
+        };
+        if token_program.key() != expected_program {
+            return Err(ErrorCode::InvalidTokenProgram.into());
+        }
 
-#[derive(Accounts)]
-pub struct WithdrawFunds<'info> {
-    #[account(mut)]
-    pub campaign: Account<'info, Campaign>,
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    /// CHECK: This is the treasury PDA
-    #[account(
-        mut,
-        seeds = [b"treasury"],
-        bump
-    )]
-    pub treasury: UncheckedAccount<'info>,
-    pub system_program: Program<'info, System>,
-}
+        // Allocate the mint account at the badge mint PDA
+        let campaign_key = campaign.key();
+        let mint_seeds = &[
+            b"badge-mint".as_ref(),
+            campaign_key.as_ref(),
+            &[ctx.bumps.badge_mint],
+        ];
+        let mint_space = token_interface::find_mint_account_size(Some(&extensions))?;
+        let mint_lamports = Rent::get()?.minimum_balance(mint_space);
+        anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::CreateAccount {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.badge_mint.to_account_info(),
+                },
+                &[mint_seeds],
+            ),
+            mint_lamports,
+            mint_space as u64,
+            &token_program.key(),
+        )?;
 
-#[derive(Accounts)]
-pub struct ClaimRefund<'info> {
+        // Extensions must be initialized before the mint itself
+        if campaign.reward_mode == RewardMode::Soulbound {
+            token_interface::non_transferable_mint_initialize(CpiContext::new(
+                token_program.to_account_info(),
+                token_interface::NonTransferableMintInitialize {
+                    token_program_id: token_program.to_account_info(),
+                    mint: ctx.accounts.badge_mint.to_account_info(),
+                },
+            ))?;
+        }
+
+        // Badges are indivisible; the badge PDA is both mint and freeze authority
+        let badge_authority = ctx.accounts.badge_authority.key();
+        token_interface::initialize_mint2(
+            CpiContext::new(
+                token_program.to_account_info(),
+                token_interface::InitializeMint2 {
+                    mint: ctx.accounts.badge_mint.to_account_info(),
+                },
+            ),
+            0,
+            &badge_authority,
+            Some(&badge_authority),
+        )?;
+
+        emit_cpi!(BadgeMintInitialized {
+            campaign: campaign.key(),
+            badge_mint: ctx.accounts.badge_mint.key(),
+            reward_mode: campaign.reward_mode,
+        });
+
+        msg!("Badge mint initialized successfully!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Reward mode: {:?}", campaign.reward_mode);
+        msg!("Badge mint: {}", ctx.accounts.badge_mint.key());
+
+        Ok(())
+    }
+
+    // Claim a non-transferable supporter badge for a funded soulbound campaign
+    pub fn claim_supporter_badge(ctx: Context<ClaimSupporterBadge>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let supporter_funding = &mut ctx.accounts.supporter_funding;
+
+        // Ensure campaign is fully funded
+        if !campaign.is_funded {
+            return Err(ErrorCode::CampaignNotFunded.into());
+        }
+
+        // Transferable campaigns use the escrowed NFT flow instead
+        if campaign.reward_mode == RewardMode::Transferable {
+            return Err(ErrorCode::InvalidRewardMode.into());
+        }
+
+        // Ensure badge hasn't been claimed by this supporter yet
+        if supporter_funding.nft_minted {
+            return Err(ErrorCode::NftAlreadyMinted.into());
+        }
+
+        // Create seeds for badge authority PDA signing
+        let campaign_key = campaign.key();
+        let seeds = &[
+            b"badge-authority".as_ref(),
+            campaign_key.as_ref(),
+            &[ctx.bumps.badge_authority],
+        ];
+
+        // Mint exactly one badge to the supporter
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.badge_mint.to_account_info(),
+                    to: ctx.accounts.supporter_token_account.to_account_info(),
+                    authority: ctx.accounts.badge_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            1,
+        )?;
+
+        // Classic SPL badges are locked by freezing the holder's token account
+        if campaign.reward_mode == RewardMode::Frozen {
+            token_interface::freeze_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::FreezeAccount {
+                    account: ctx.accounts.supporter_token_account.to_account_info(),
+                    mint: ctx.accounts.badge_mint.to_account_info(),
+                    authority: ctx.accounts.badge_authority.to_account_info(),
+                },
+                &[seeds],
+            ))?;
+        }
+
+        // Mark that this supporter has claimed their badge, at the stretch tier unlocked so far
+        supporter_funding.nft_minted = true;
+        supporter_funding.edition_mint = ctx.accounts.badge_mint.key();
+        supporter_funding.reward_tier = campaign.stretch_goals_reached();
+
+        // Count the claim in the supporter's portfolio
+        let supporter_profile = &mut ctx.accounts.supporter_profile;
+        supporter_profile.nfts_claimed = supporter_profile.nfts_claimed.checked_add(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+
+        emit_cpi!(BadgeClaimed {
+            campaign: campaign.key(),
+            supporter: supporter_funding.supporter,
+            badge_mint: ctx.accounts.badge_mint.key(),
+            reward_mode: campaign.reward_mode,
+            reward_tier: supporter_funding.reward_tier,
+        });
+
+        msg!("Supporter badge claimed successfully!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Recipient: {}", supporter_funding.supporter);
+        msg!("Badge mint: {}", ctx.accounts.badge_mint.key());
+
+        Ok(())
+    }
+
+    // Return an unclaimed escrowed NFT to the creator and close the escrow token account.
+    // Allowed after the claim deadline, or immediately if the campaign failed or was cancelled.
+    pub fn reclaim_escrowed_nft(ctx: Context<ReclaimEscrowedNft>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // Ensure the NFT is in escrow
+        if !campaign.nft_in_escrow {
+            return Err(ErrorCode::NftNotInEscrow.into());
+        }
+
+        // Supporters keep priority until the claim window closes on successful campaigns
+        let current_time = Clock::get()?.unix_timestamp;
+        let is_cancelled = !campaign.is_active;
+        let is_failed = current_time > campaign.end_timestamp && !campaign.is_funded;
+        if current_time <= campaign.claim_deadline && !is_cancelled && !is_failed {
+            return Err(ErrorCode::ClaimWindowOpen.into());
+        }
+
+        // Create seeds for escrow PDA signing
+        let campaign_key = campaign.key();
+        let seeds = &[
+            b"escrow".as_ref(),
+            campaign_key.as_ref(),
+            &[ctx.bumps.escrow_authority],
+        ];
+
+        // Return whatever is left in escrow to the creator
+        let remaining = ctx.accounts.escrow_token_account.amount;
+        if remaining > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.escrow_token_account.to_account_info(),
+                        to: ctx.accounts.creator_token_account.to_account_info(),
+                        authority: ctx.accounts.escrow_authority.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                remaining,
+            )?;
+        }
+
+        // Close the escrow token account and return its rent to the creator
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::CloseAccount {
+                account: ctx.accounts.escrow_token_account.to_account_info(),
+                destination: ctx.accounts.creator.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+
+        // Mark that the NFT is no longer in escrow
+        campaign.nft_in_escrow = false;
+
+        emit_cpi!(NftReclaimed {
+            campaign: campaign.key(),
+            creator: ctx.accounts.creator.key(),
+            nft_mint: ctx.accounts.nft_mint.key(),
+            amount: remaining,
+        });
+
+        msg!("Escrowed NFT reclaimed successfully!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("NFT Mint: {}", ctx.accounts.nft_mint.key());
+        msg!("Amount returned: {}", remaining);
+
+        Ok(())
+    }
+
+    // Create the platform-wide "GotongKarya Supporters" parent collection (admin only)
+    pub fn initialize_platform_collection(
+        ctx: Context<InitializePlatformCollection>,
+        uri: String,
+    ) -> Result<()> {
+        validate_uri(&uri)?;
+
+        let admin = &ctx.accounts.admin;
+
+        let seeds = &[
+            b"platform-authority".as_ref(),
+            &[ctx.bumps.platform_authority],
+        ];
+
+        // Mint the single collection token to the platform authority
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.collection_mint.to_account_info(),
+                    to: ctx.accounts.collection_token_account.to_account_info(),
+                    authority: ctx.accounts.platform_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            1,
+        )?;
+
+        // Create sized collection metadata and a master edition with no prints
+        metadata::create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                metadata::CreateMetadataAccountsV3 {
+                    metadata: ctx.accounts.collection_metadata.to_account_info(),
+                    mint: ctx.accounts.collection_mint.to_account_info(),
+                    mint_authority: ctx.accounts.platform_authority.to_account_info(),
+                    payer: admin.to_account_info(),
+                    update_authority: ctx.accounts.platform_authority.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                &[seeds],
+            ),
+            DataV2 {
+                name: PLATFORM_COLLECTION_NAME.to_string(),
+                symbol: PLATFORM_COLLECTION_SYMBOL.to_string(),
+                uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            true,
+            true,
+            Some(CollectionDetails::V1 { size: 0 }),
+        )?;
+
+        metadata::create_master_edition_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                metadata::CreateMasterEditionV3 {
+                    edition: ctx.accounts.collection_master_edition.to_account_info(),
+                    mint: ctx.accounts.collection_mint.to_account_info(),
+                    update_authority: ctx.accounts.platform_authority.to_account_info(),
+                    mint_authority: ctx.accounts.platform_authority.to_account_info(),
+                    payer: admin.to_account_info(),
+                    metadata: ctx.accounts.collection_metadata.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                &[seeds],
+            ),
+            Some(0),
+        )?;
+
+        emit_cpi!(PlatformCollectionCreated {
+            collection_mint: ctx.accounts.collection_mint.key(),
+        });
+
+        msg!("Platform collection initialized successfully!");
+        msg!("Collection mint: {}", ctx.accounts.collection_mint.key());
+
+        Ok(())
+    }
+
+    // Create a collection NFT for a campaign, owned and signed for by the campaign PDA.
+    // The collection points at the platform parent collection, unverified until
+    // verify_campaign_collection is called.
+    pub fn initialize_campaign_collection(
+        ctx: Context<InitializeCampaignCollection>,
+        uri: String,
+    ) -> Result<()> {
+        validate_uri(&uri)?;
+
+        let campaign = &ctx.accounts.campaign;
+
+        // A campaign has exactly one collection
+        if campaign.collection_mint != Pubkey::default() {
+            return Err(ErrorCode::CollectionAlreadySet.into());
+        }
+
+        // Create seeds for campaign PDA signing
+        let creator_key = campaign.creator;
+        let creator_index = campaign.creator_index.to_le_bytes();
+        let seeds = &[
+            b"campaign".as_ref(),
+            creator_key.as_ref(),
+            creator_index.as_ref(),
+            &[campaign.bump],
+        ];
+
+        // Mint the single collection token to the campaign PDA
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.collection_mint.to_account_info(),
+                    to: ctx.accounts.collection_token_account.to_account_info(),
+                    authority: campaign.to_account_info(),
+                },
+                &[seeds],
+            ),
+            1,
+        )?;
+
+        metadata::create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                metadata::CreateMetadataAccountsV3 {
+                    metadata: ctx.accounts.collection_metadata.to_account_info(),
+                    mint: ctx.accounts.collection_mint.to_account_info(),
+                    mint_authority: campaign.to_account_info(),
+                    payer: ctx.accounts.creator.to_account_info(),
+                    update_authority: campaign.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                &[seeds],
+            ),
+            DataV2 {
+                name: campaign.project_name.clone(),
+                symbol: campaign.nft_symbol.clone(),
+                uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: Some(Collection {
+                    verified: false,
+                    key: ctx.accounts.platform_collection_mint.key(),
+                }),
+                uses: None,
+            },
+            true,
+            true,
+            Some(CollectionDetails::V1 { size: 0 }),
+        )?;
+
+        metadata::create_master_edition_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                metadata::CreateMasterEditionV3 {
+                    edition: ctx.accounts.collection_master_edition.to_account_info(),
+                    mint: ctx.accounts.collection_mint.to_account_info(),
+                    update_authority: campaign.to_account_info(),
+                    mint_authority: campaign.to_account_info(),
+                    payer: ctx.accounts.creator.to_account_info(),
+                    metadata: ctx.accounts.collection_metadata.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                &[seeds],
+            ),
+            Some(0),
+        )?;
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.collection_mint = ctx.accounts.collection_mint.key();
+
+        emit_cpi!(CampaignCollectionSet {
+            campaign: campaign.key(),
+            collection_mint: campaign.collection_mint,
+            created: true,
+        });
+
+        msg!("Campaign collection initialized successfully!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Collection mint: {}", campaign.collection_mint);
+
+        Ok(())
+    }
+
+    // Reference an existing collection NFT instead of creating one (creator only).
+    // The collection's update authority must approve the campaign PDA as a
+    // collection authority before rewards can be verified into it.
+    pub fn set_campaign_collection(ctx: Context<SetCampaignCollection>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // A campaign has exactly one collection
+        if campaign.collection_mint != Pubkey::default() {
+            return Err(ErrorCode::CollectionAlreadySet.into());
+        }
+
+        campaign.collection_mint = ctx.accounts.collection_mint.key();
+
+        emit_cpi!(CampaignCollectionSet {
+            campaign: campaign.key(),
+            collection_mint: campaign.collection_mint,
+            created: false,
+        });
+
+        msg!("Campaign collection set successfully!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Collection mint: {}", campaign.collection_mint);
+
+        Ok(())
+    }
+
+    // Verify a campaign reward NFT into the campaign collection, signed by the campaign PDA.
+    // Rewards are the campaign's escrowed NFT or a supporter's recorded edition mint, which
+    // update_supporter_nft_mint only accepts once printed from it. Supporter badges
+    // carry no Metaplex metadata and can't join a collection.
+    pub fn verify_reward_collection(ctx: Context<VerifyRewardCollection>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let reward_mint = ctx.accounts.reward_mint.key();
+
+        // Only mints that belong to this campaign can join its collection
+        let supporter_funding = ctx.accounts.supporter_funding.as_deref();
+        if !campaign.is_reward_mint(&reward_mint, supporter_funding) {
+            return Err(ErrorCode::InvalidNftMint.into());
+        }
+
+        // Create seeds for campaign PDA signing
+        let creator_key = campaign.creator;
+        let creator_index = campaign.creator_index.to_le_bytes();
+        let seeds = &[
+            b"campaign".as_ref(),
+            creator_key.as_ref(),
+            creator_index.as_ref(),
+            &[campaign.bump],
+        ];
+
+        // Referenced collections need the delegated collection authority record
+        let collection_authority_record = ctx
+            .accounts
+            .collection_authority_record
+            .as_ref()
+            .map(|record| record.to_account_info());
+
+        metadata::verify_sized_collection_item(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                metadata::VerifySizedCollectionItem {
+                    payer: ctx.accounts.payer.to_account_info(),
+                    metadata: ctx.accounts.reward_metadata.to_account_info(),
+                    collection_authority: campaign.to_account_info(),
+                    collection_mint: ctx.accounts.collection_mint.to_account_info(),
+                    collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+                    collection_master_edition: ctx.accounts.collection_master_edition.to_account_info(),
+                },
+                &[seeds],
+            )
+            .with_remaining_accounts(collection_authority_record.iter().cloned().collect()),
+            collection_authority_record.as_ref().map(|record| record.key()),
+        )?;
+
+        emit_cpi!(RewardVerified {
+            campaign: campaign.key(),
+            reward_mint,
+            collection_mint: campaign.collection_mint,
+        });
+
+        msg!("Reward verified into campaign collection!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Reward mint: {}", reward_mint);
+        msg!("Collection mint: {}", campaign.collection_mint);
+
+        Ok(())
+    }
+
+    // Verify a campaign collection into the platform parent collection (permissionless)
+    pub fn verify_campaign_collection(ctx: Context<VerifyCampaignCollection>) -> Result<()> {
+        let seeds = &[
+            b"platform-authority".as_ref(),
+            &[ctx.bumps.platform_authority],
+        ];
+
+        metadata::verify_sized_collection_item(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                metadata::VerifySizedCollectionItem {
+                    payer: ctx.accounts.payer.to_account_info(),
+                    metadata: ctx.accounts.collection_metadata.to_account_info(),
+                    collection_authority: ctx.accounts.platform_authority.to_account_info(),
+                    collection_mint: ctx.accounts.platform_collection_mint.to_account_info(),
+                    collection_metadata: ctx.accounts.platform_collection_metadata.to_account_info(),
+                    collection_master_edition: ctx.accounts.platform_collection_master_edition.to_account_info(),
+                },
+                &[seeds],
+            ),
+            None,
+        )?;
+
+        emit_cpi!(CampaignCollectionVerified {
+            campaign: ctx.accounts.campaign.key(),
+            collection_mint: ctx.accounts.collection_mint.key(),
+            platform_collection_mint: ctx.accounts.platform_collection_mint.key(),
+        });
+
+        msg!("Campaign collection verified into platform collection!");
+        msg!("Campaign: {}", ctx.accounts.campaign.project_name);
+        msg!("Collection mint: {}", ctx.accounts.campaign.collection_mint);
+
+        Ok(())
+    }
+
+    // Mark a funded campaign as delivered once the creator has completed the
+    // project. Only after it ended and its funds were released, with no dispute
+    // still open against it.
+    pub fn mark_campaign_delivered(ctx: Context<MarkCampaignDelivered>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // Only funded campaigns can be delivered
+        if !campaign.is_funded {
+            return Err(ErrorCode::CampaignNotFunded.into());
+        }
+
+        if campaign.is_delivered {
+            return Err(ErrorCode::CampaignAlreadyDelivered.into());
+        }
+
+        let current_time = Clock::get()?.unix_timestamp;
+        if current_time <= campaign.end_timestamp {
+            return Err(ErrorCode::CampaignNotEnded.into());
+        }
+
+        // Withdrawing the funds or collecting the pledges records the outcome
+        if !campaign.outcome_recorded {
+            return Err(ErrorCode::FundsNotWithdrawn.into());
+        }
+
+        if campaign.open_disputes > 0 {
+            return Err(ErrorCode::DisputesOpen.into());
+        }
+
+        campaign.is_delivered = true;
+
+        emit!(CampaignDelivered {
+            campaign: campaign.key(),
+        });
+
+        msg!("Campaign marked as delivered!");
+        msg!("Campaign: {}", campaign.project_name);
+
+        Ok(())
+    }
+
+    // Register the metadata URIs reward NFTs switch to for each campaign outcome (creator only)
+    pub fn set_outcome_metadata(
+        ctx: Context<SetOutcomeMetadata>,
+        funded_uri: String,
+        delivered_uri: String,
+        failed_uri: String,
+    ) -> Result<()> {
+        validate_uri(&funded_uri)?;
+        validate_uri(&delivered_uri)?;
+        validate_uri(&failed_uri)?;
+
+        let outcome_metadata = &mut ctx.accounts.outcome_metadata;
+
+        outcome_metadata.campaign = ctx.accounts.campaign.key();
+        outcome_metadata.funded_uri = funded_uri;
+        outcome_metadata.delivered_uri = delivered_uri;
+        outcome_metadata.failed_uri = failed_uri;
+
+        emit!(OutcomeMetadataSet {
+            campaign: ctx.accounts.campaign.key(),
+            funded_uri: outcome_metadata.funded_uri.clone(),
+            delivered_uri: outcome_metadata.delivered_uri.clone(),
+            failed_uri: outcome_metadata.failed_uri.clone(),
+        });
+
+        msg!("Outcome metadata set successfully!");
+        msg!("Campaign: {}", ctx.accounts.campaign.project_name);
+
+        Ok(())
+    }
+
+    // Point a reward NFT's metadata at the URI for the campaign's current outcome.
+    // Permissionless; the campaign PDA must be the reward's update authority.
+    pub fn update_reward_metadata(ctx: Context<UpdateRewardMetadata>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let reward_mint = ctx.accounts.reward_mint.key();
+
+        // Rewards and the campaign collection itself can be updated
+        let supporter_funding = ctx.accounts.supporter_funding.as_deref();
+        if !campaign.is_reward_mint(&reward_mint, supporter_funding)
+            && reward_mint != campaign.collection_mint
+        {
+            return Err(ErrorCode::InvalidNftMint.into());
+        }
+
+        // Pick the URI for what happened to the campaign
+        let current_time = Clock::get()?.unix_timestamp;
+        let outcome = campaign
+            .outcome(current_time)
+            .ok_or(ErrorCode::CampaignStillActive)?;
+        let outcome_metadata = &ctx.accounts.outcome_metadata;
+        // Supporter rewards keep the stretch tier granted when they were claimed;
+        // the campaign NFT and collection follow the tier the campaign reached
+        let tier = match supporter_funding {
+            Some(funding) if funding.nft_minted && funding.edition_mint == reward_mint => funding.reward_tier,
+            _ => campaign.stretch_goals_reached(),
+        };
+        let uri = match outcome {
+            CampaignOutcome::Funded => campaign.stretch_reward_uri(tier).unwrap_or(&outcome_metadata.funded_uri),
+            CampaignOutcome::Delivered => campaign.stretch_reward_uri(tier).unwrap_or(&outcome_metadata.delivered_uri),
+            CampaignOutcome::Failed => &outcome_metadata.failed_uri,
+        };
+
+        // Keep everything but the URI; stored strings are null padded
+        let reward_metadata = &ctx.accounts.reward_metadata;
+        let data = DataV2 {
+            name: reward_metadata.name.trim_end_matches('\0').to_string(),
+            symbol: reward_metadata.symbol.trim_end_matches('\0').to_string(),
+            uri: uri.clone(),
+            seller_fee_basis_points: reward_metadata.seller_fee_basis_points,
+            creators: reward_metadata.creators.clone(),
+            collection: reward_metadata.collection.clone(),
+            uses: reward_metadata.uses.clone(),
+        };
+
+        // Create seeds for campaign PDA signing
+        let creator_key = campaign.creator;
+        let creator_index = campaign.creator_index.to_le_bytes();
+        let seeds = &[
+            b"campaign".as_ref(),
+            creator_key.as_ref(),
+            creator_index.as_ref(),
+            &[campaign.bump],
+        ];
+
+        metadata::update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                metadata::UpdateMetadataAccountsV2 {
+                    metadata: ctx.accounts.reward_metadata.to_account_info(),
+                    update_authority: campaign.to_account_info(),
+                },
+                &[seeds],
+            ),
+            None,
+            Some(data),
+            None,
+            None,
+        )?;
+
+        emit_cpi!(RewardMetadataUpdated {
+            campaign: campaign.key(),
+            reward_mint,
+            outcome,
+            uri: uri.clone(),
+            raised_lamports: campaign.raised_lamports,
+        });
+
+        msg!("Reward metadata updated successfully!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Outcome: {:?}", outcome);
+        msg!("Raised: {} lamports", campaign.raised_lamports);
+        msg!("Reward mint: {}", reward_mint);
+
+        Ok(())
+    }
+
+    // Mint campaign share tokens to a supporter, one base unit per contributed lamport.
+    // Shares are issued once the campaign settled: funded, its dispute window
+    // closed and no disputes open. A disputed contribution therefore never holds
+    // shares, and claim_refund burns them on every other refund.
+    pub fn claim_share_tokens(ctx: Context<ClaimShareTokens>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let supporter_funding = &mut ctx.accounts.supporter_funding;
+
+        // Ensure campaign is fully funded
+        if !campaign.is_funded {
+            return Err(ErrorCode::CampaignNotFunded.into());
+        }
+
+        let current_time = Clock::get()?.unix_timestamp;
+        if current_time <= campaign.dispute_deadline || campaign.open_disputes > 0 {
+            return Err(ErrorCode::SharesLocked.into());
+        }
+
+        // Refunded contributions no longer carry any share
+        if supporter_funding.is_claimed {
+            return Err(ErrorCode::RefundAlreadyClaimed.into());
+        }
+
+        // Ensure shares haven't been claimed by this supporter yet
+        if supporter_funding.shares_minted > 0 {
+            return Err(ErrorCode::SharesAlreadyMinted.into());
+        }
+
+        // Create seeds for campaign PDA signing
+        let creator_key = campaign.creator;
+        let creator_index = campaign.creator_index.to_le_bytes();
+        let seeds = &[
+            b"campaign".as_ref(),
+            creator_key.as_ref(),
+            creator_index.as_ref(),
+            &[campaign.bump],
+        ];
+
+        let share_amount = supporter_funding.amount_lamports;
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.share_mint.to_account_info(),
+                    to: ctx.accounts.supporter_share_account.to_account_info(),
+                    authority: campaign.to_account_info(),
+                },
+                &[seeds],
+            ),
+            share_amount,
+        )?;
+
+        supporter_funding.shares_minted = share_amount;
+
+        emit_cpi!(SharesClaimed {
+            campaign: campaign.key(),
+            supporter: supporter_funding.supporter,
+            share_mint: ctx.accounts.share_mint.key(),
+            amount: share_amount,
+        });
+
+        msg!("Share tokens claimed successfully!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Supporter: {}", supporter_funding.supporter);
+        msg!("Shares: {}", share_amount);
+
+        Ok(())
+    }
+
+    // Correct a live campaign's name, summary, content or reward URI. The account
+    // is resized to fit, with the creator paying or receiving only the rent
+    // difference; the contributions it holds never move.
+    // The name, content and reward URI shape what supporters expect, so they are
+    // locked once the first contribution arrives.
+    pub fn update_campaign_metadata(
+        ctx: Context<UpdateCampaignMetadata>,
+        project_name: Option<String>,
+        description: Option<String>,
+        content: Option<CampaignContent>,
+        nft_uri: Option<String>,
+    ) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        if project_name.is_none() && description.is_none() && content.is_none() && nft_uri.is_none() {
+            return Err(ErrorCode::NothingToUpdate.into());
+        }
+
+        if !campaign.is_active {
+            return Err(ErrorCode::CampaignNotActive.into());
+        }
+
+        let current_time = Clock::get()?.unix_timestamp;
+        if current_time > campaign.end_timestamp {
+            return Err(ErrorCode::CampaignEnded.into());
+        }
+
+        let affects_supporters = project_name.is_some() || content.is_some() || nft_uri.is_some();
+        if affects_supporters && campaign.supporters_count > 0 {
+            return Err(ErrorCode::ContributionsReceived.into());
+        }
+
+        if let Some(project_name) = project_name {
+            validate_string(&project_name, MAX_PROJECT_NAME_LEN, ErrorCode::EmptyProjectName, ErrorCode::ProjectNameTooLong)?;
+            campaign.project_name = project_name;
+        }
+        if let Some(description) = description {
+            validate_description(&description)?;
+            campaign.description = description;
+        }
+        if let Some(content) = content {
+            validate_content(&content)?;
+            campaign.content_uri = content.uri;
+            campaign.content_hash = content.hash;
+        }
+        if let Some(nft_uri) = nft_uri {
+            validate_uri(&nft_uri)?;
+            campaign.nft_uri = nft_uri;
+        }
+
+        resize_campaign(
+            &campaign.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            campaign.current_space(),
+        )?;
+
+        emit!(CampaignMetadataUpdated {
+            campaign: campaign.key(),
+            project_name: campaign.project_name.clone(),
+            description: campaign.description.clone(),
+            content_uri: campaign.content_uri.clone(),
+            content_hash: campaign.content_hash,
+            nft_uri: campaign.nft_uri.clone(),
+        });
+
+        msg!("Campaign metadata updated!");
+        msg!("Project: {}", campaign.project_name);
+
+        Ok(())
+    }
+
+    // Set the creator's public profile. Creates the profile if the creator
+    // hasn't launched a campaign yet; stats are only changed by the program.
+    pub fn update_creator_profile(
+        ctx: Context<UpdateCreatorProfile>,
+        display_name: String,
+        avatar_uri: String,
+        socials_hash: [u8; 32],
+    ) -> Result<()> {
+        if display_name.len() > MAX_DISPLAY_NAME_LEN {
+            return Err(ErrorCode::DisplayNameTooLong.into());
+        }
+        if avatar_uri.len() > MAX_AVATAR_URI_LEN {
+            return Err(ErrorCode::AvatarUriTooLong.into());
+        }
+        // The avatar is optional, but must be a URI when given
+        if !avatar_uri.is_empty() && !has_uri_scheme(&avatar_uri) {
+            return Err(ErrorCode::InvalidUriScheme.into());
+        }
+
+        let creator_profile = &mut ctx.accounts.creator_profile;
+        creator_profile.creator = ctx.accounts.creator.key();
+        creator_profile.bump = ctx.bumps.creator_profile;
+        creator_profile.display_name = display_name;
+        creator_profile.avatar_uri = avatar_uri;
+        creator_profile.socials_hash = socials_hash;
+
+        emit!(CreatorProfileUpdated {
+            creator: creator_profile.creator,
+            display_name: creator_profile.display_name.clone(),
+            avatar_uri: creator_profile.avatar_uri.clone(),
+            socials_hash,
+        });
+
+        msg!("Creator profile updated!");
+        msg!("Creator: {}", creator_profile.creator);
+
+        Ok(())
+    }
+
+    // Cancel a running campaign that hasn't reached its goal. Contributions
+    // stop and supporters can claim refunds immediately.
+    pub fn cancel_campaign(ctx: Context<CancelCampaign>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        if !campaign.is_active {
+            return Err(ErrorCode::CampaignNotActive.into());
+        }
+
+        if campaign.is_funded {
+            return Err(ErrorCode::CampaignAlreadyFunded.into());
+        }
+
+        // Once ended below the goal the campaign has failed instead
+        let current_time = Clock::get()?.unix_timestamp;
+        if current_time > campaign.end_timestamp {
+            return Err(ErrorCode::CampaignEnded.into());
+        }
+
+        campaign.is_active = false;
+        campaign.outcome_recorded = true;
+
+        let creator_profile = &mut ctx.accounts.creator_profile;
+        creator_profile.campaigns_cancelled = creator_profile.campaigns_cancelled
+            .checked_add(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+
+        emit!(CampaignCancelled {
+            campaign: campaign.key(),
+            creator: campaign.creator,
+        });
+
+        msg!("Campaign cancelled!");
+        msg!("Campaign: {}", campaign.project_name);
+
+        Ok(())
+    }
+
+    // Record a failed campaign in its creator's profile. Permissionless, for
+    // failed campaigns where no supporter has claimed a refund yet.
+    pub fn record_campaign_failure(ctx: Context<RecordCampaignFailure>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        let current_time = Clock::get()?.unix_timestamp;
+        if current_time <= campaign.end_timestamp {
+            return Err(ErrorCode::CampaignStillActive.into());
+        }
+
+        if campaign.is_funded {
+            return Err(ErrorCode::CampaignAlreadyFunded.into());
+        }
+
+        if campaign.outcome_recorded {
+            return Err(ErrorCode::OutcomeAlreadyRecorded.into());
+        }
+
+        let creator_profile = &mut ctx.accounts.creator_profile;
+        creator_profile.campaigns_failed = creator_profile.campaigns_failed
+            .checked_add(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+        campaign.outcome_recorded = true;
+
+        emit!(CampaignFailed {
+            campaign: campaign.key(),
+            creator: campaign.creator,
+            raised_lamports: campaign.raised_lamports,
+        });
+
+        msg!("Campaign failure recorded!");
+        msg!("Campaign: {}", campaign.project_name);
+
+        Ok(())
+    }
+
+    // Post a progress update for supporters. Each update gets its own PDA,
+    // numbered in posting order, holding the URI and hash of its content.
+    pub fn post_update(ctx: Context<PostUpdate>, content: CampaignContent, is_milestone: bool) -> Result<()> {
+        validate_content(&content)?;
+
+        let campaign = &mut ctx.accounts.campaign;
+        let update = &mut ctx.accounts.update;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        update.campaign = campaign.key();
+        update.sequence = campaign.updates_count;
+        update.posted_at = current_time;
+        update.content_uri = content.uri;
+        update.content_hash = content.hash;
+        update.is_milestone = is_milestone;
+        update.bump = ctx.bumps.update;
+
+        campaign.updates_count = campaign.updates_count
+            .checked_add(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+
+        emit!(UpdatePosted {
+            campaign: campaign.key(),
+            update: update.key(),
+            sequence: update.sequence,
+            posted_at: current_time,
+            content_uri: update.content_uri.clone(),
+            content_hash: update.content_hash,
+            is_milestone,
+        });
+
+        msg!("Update posted!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Sequence: {}", update.sequence);
+
+        Ok(())
+    }
+
+    // Admin emergency switch. While paused no campaigns can be launched or
+    // funded, funds can't be withdrawn and rewards can't be claimed; refunds
+    // stay open so supporters can always get their contributions back.
+    pub fn set_platform_paused(ctx: Context<UpdatePlatformConfig>, paused: bool) -> Result<()> {
+        let admin = &ctx.accounts.admin;
+
+        let platform_config = &mut ctx.accounts.platform_config;
+        platform_config.paused = paused;
+        platform_config.bump = ctx.bumps.platform_config;
+
+        emit!(PlatformPauseSet {
+            admin: admin.key(),
+            paused,
+        });
+
+        msg!("Platform {}!", if paused { "paused" } else { "resumed" });
+
+        Ok(())
+    }
+
+    // Admin freeze for a single flagged campaign, with the same effect as the
+    // platform pause but limited to that campaign
+    pub fn set_campaign_frozen(ctx: Context<SetCampaignFrozen>, frozen: bool) -> Result<()> {
+        let admin = &ctx.accounts.admin;
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.is_frozen = frozen;
+
+        emit!(CampaignFreezeSet {
+            campaign: campaign.key(),
+            admin: admin.key(),
+            frozen,
+        });
+
+        msg!("Campaign {}!", if frozen { "frozen" } else { "unfrozen" });
+        msg!("Campaign: {}", campaign.project_name);
+
+        Ok(())
+    }
+
+    // Admin appoints the moderator allowed to take down campaigns
+    pub fn set_moderator(ctx: Context<UpdatePlatformConfig>, moderator: Pubkey) -> Result<()> {
+        let admin = &ctx.accounts.admin;
+
+        let platform_config = &mut ctx.accounts.platform_config;
+        platform_config.moderator = moderator;
+        platform_config.bump = ctx.bumps.platform_config;
+
+        emit!(ModeratorSet {
+            admin: admin.key(),
+            moderator,
+        });
+
+        msg!("Moderator set!");
+        msg!("Moderator: {}", moderator);
+
+        Ok(())
+    }
+
+    // Take down a fraudulent campaign. Contributions and withdrawals stop for
+    // good and every supporter can claim a refund, even if the goal was reached,
+    // as long as the funds haven't been released to the creator yet.
+    pub fn flag_campaign(ctx: Context<FlagCampaign>, reason: TakedownReason) -> Result<()> {
+        let moderator = &ctx.accounts.moderator;
+        let campaign = &mut ctx.accounts.campaign;
+
+        if campaign.takedown_reason.is_some() {
+            return Err(ErrorCode::CampaignTakenDown.into());
+        }
+
+        // Once the funds were released to the creator there is nothing left to refund
+        if campaign.is_funded && campaign.outcome_recorded {
+            return Err(ErrorCode::FundsAlreadyWithdrawn.into());
+        }
+
+        campaign.takedown_reason = Some(reason);
+        campaign.is_active = false;
+
+        // A takedown replaces any other outcome in the creator's track record
+        if !campaign.outcome_recorded {
+            let creator_profile = &mut ctx.accounts.creator_profile;
+            creator_profile.campaigns_taken_down = creator_profile.campaigns_taken_down
+                .checked_add(1)
+                .ok_or(ErrorCode::CountOverflow)?;
+            campaign.outcome_recorded = true;
+        }
+
+        emit!(CampaignTakenDown {
+            campaign: campaign.key(),
+            moderator: moderator.key(),
+            reason,
+        });
+
+        msg!("Campaign taken down!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Reason: {:?}", reason);
+
+        Ok(())
+    }
+
+    // Admin appoints the dispute arbitrator and sets the dispute window for
+    // campaigns created from now on
+    pub fn configure_disputes(
+        ctx: Context<UpdatePlatformConfig>,
+        arbitrator: Pubkey,
+        dispute_window_seconds: i64,
+    ) -> Result<()> {
+        let admin = &ctx.accounts.admin;
+
+        if !(0..=MAX_DISPUTE_WINDOW_SECONDS).contains(&dispute_window_seconds) {
+            return Err(ErrorCode::InvalidDisputeWindow.into());
+        }
+
+        let platform_config = &mut ctx.accounts.platform_config;
+        platform_config.arbitrator = arbitrator;
+        platform_config.dispute_window_seconds = Some(dispute_window_seconds);
+        platform_config.bump = ctx.bumps.platform_config;
+
+        emit!(DisputesConfigured {
+            admin: admin.key(),
+            arbitrator,
+            dispute_window_seconds,
+        });
+
+        msg!("Dispute settings updated!");
+        msg!("Arbitrator: {}", arbitrator);
+        msg!("Dispute window: {} seconds", dispute_window_seconds);
+
+        Ok(())
+    }
+
+    // A supporter of a funded campaign objects before the creator can withdraw,
+    // between the campaign's end and its dispute deadline. Withdrawals stay
+    // blocked until the arbitrator resolves the dispute.
+    pub fn file_dispute(ctx: Context<FileDispute>, evidence_hash: [u8; 32]) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let dispute = &mut ctx.accounts.dispute;
+
+        if !campaign.is_funded {
+            return Err(ErrorCode::CampaignNotFunded.into());
+        }
+
+        let current_time = Clock::get()?.unix_timestamp;
+        if current_time <= campaign.end_timestamp {
+            return Err(ErrorCode::CampaignNotEnded.into());
+        }
+
+        if current_time > campaign.dispute_deadline {
+            return Err(ErrorCode::DisputeWindowClosed.into());
+        }
+
+        if evidence_hash == [0; 32] {
+            return Err(ErrorCode::MissingEvidenceHash.into());
+        }
+
+        dispute.campaign = campaign.key();
+        dispute.supporter = ctx.accounts.supporter.key();
+        dispute.evidence_hash = evidence_hash;
+        dispute.filed_at = current_time;
+        dispute.status = DisputeStatus::Open;
+        dispute.resolved_at = 0;
+        dispute.bump = ctx.bumps.dispute;
+
+        campaign.open_disputes = campaign.open_disputes
+            .checked_add(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+
+        emit!(DisputeFiled {
+            campaign: campaign.key(),
+            supporter: dispute.supporter,
+            dispute: dispute.key(),
+            evidence_hash,
+        });
+
+        msg!("Dispute filed!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Supporter: {}", dispute.supporter);
+
+        Ok(())
+    }
+
+    // The arbitrator settles an open dispute, either releasing the contribution
+    // to the campaign or returning it to the supporter right away
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, resolution: DisputeResolution) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let dispute = &mut ctx.accounts.dispute;
+        let supporter_funding = &mut ctx.accounts.supporter_funding;
+
+        if dispute.status != DisputeStatus::Open {
+            return Err(ErrorCode::DisputeAlreadyResolved.into());
+        }
+
+        let mut refunded_lamports = 0;
+        match resolution {
+            DisputeResolution::Release => {
+                dispute.status = DisputeStatus::Released;
+            }
+            DisputeResolution::Refund => {
+                // Uncharged pledges are cancelled so collect_pledges skips them.
+                // Shares are only issued after the dispute window with no disputes
+                // open, so a disputed contribution has none to burn.
+                if supporter_funding.shares_minted > 0 {
+                    return Err(ErrorCode::SharesLocked.into());
+                }
+                if supporter_funding.pledge_status == Some(PledgeStatus::Pending) {
+                    supporter_funding.pledge_status = Some(PledgeStatus::Cancelled);
+                    supporter_funding.is_claimed = true;
+                    campaign.raised_lamports = campaign.raised_lamports
+                        .checked_sub(supporter_funding.amount_lamports)
+                        .ok_or(ErrorCode::AmountOverflow)?;
+                    campaign.pledges_pending = campaign.pledges_pending
+                        .checked_sub(1)
+                        .ok_or(ErrorCode::CountOverflow)?;
+                } else if !supporter_funding.is_claimed {
+                    refunded_lamports = supporter_funding.amount_lamports;
+                    let campaign_lamports = campaign.to_account_info().lamports()
+                        .checked_sub(refunded_lamports)
+                        .ok_or(ErrorCode::InsufficientFunds)?;
+                    let supporter_lamports = ctx.accounts.supporter.lamports()
+                        .checked_add(refunded_lamports)
+                        .ok_or(ErrorCode::AmountOverflow)?;
+                    **campaign.to_account_info().try_borrow_mut_lamports()? = campaign_lamports;
+                    **ctx.accounts.supporter.to_account_info().try_borrow_mut_lamports()? = supporter_lamports;
+                    supporter_funding.is_claimed = true;
+
+                    campaign.raised_lamports = campaign.raised_lamports
+                        .checked_sub(refunded_lamports)
+                        .ok_or(ErrorCode::AmountOverflow)?;
+
+                    let creator_profile = &mut ctx.accounts.creator_profile;
+                    creator_profile.total_refunded_lamports = creator_profile.total_refunded_lamports
+                        .checked_add(refunded_lamports)
+                        .ok_or(ErrorCode::AmountOverflow)?;
+
+                    let supporter_profile = &mut ctx.accounts.supporter_profile;
+                    supporter_profile.refunds_received = supporter_profile.refunds_received
+                        .checked_add(1)
+                        .ok_or(ErrorCode::CountOverflow)?;
+                    supporter_profile.total_refunded_lamports = supporter_profile.total_refunded_lamports
+                        .checked_add(refunded_lamports)
+                        .ok_or(ErrorCode::AmountOverflow)?;
+                }
+                dispute.status = DisputeStatus::Refunded;
+
+                // A campaign refunded below its goal is no longer funded; its other
+                // supporters can then claim refunds as for any failed campaign
+                if campaign.raised_lamports < campaign.funding_goal_lamports {
+                    campaign.is_funded = false;
+                }
+            }
+        }
+
+        dispute.resolved_at = Clock::get()?.unix_timestamp;
+        campaign.open_disputes = campaign.open_disputes
+            .checked_sub(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+
+        emit!(DisputeResolved {
+            campaign: campaign.key(),
+            supporter: dispute.supporter,
+            dispute: dispute.key(),
+            resolution,
+            refunded_lamports,
+        });
+
+        msg!("Dispute resolved!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Resolution: {:?}", resolution);
+
+        Ok(())
+    }
+
+    // Admin appoints the attester and sets the goal above which campaigns
+    // need a verified creator (None lets anyone launch any campaign)
+    pub fn configure_verification(
+        ctx: Context<UpdatePlatformConfig>,
+        attester: Pubkey,
+        threshold_lamports: Option<u64>,
+    ) -> Result<()> {
+        let admin = &ctx.accounts.admin;
+
+        let platform_config = &mut ctx.accounts.platform_config;
+        platform_config.attester = attester;
+        platform_config.verification_threshold_lamports = threshold_lamports;
+        platform_config.bump = ctx.bumps.platform_config;
+
+        emit!(VerificationConfigured {
+            admin: admin.key(),
+            attester,
+            threshold_lamports,
+        });
+
+        msg!("Verification settings updated!");
+        msg!("Attester: {}", attester);
+
+        Ok(())
+    }
+
+    // Record a creator's completed KYC. Issuing again replaces the tier and expiry.
+    pub fn issue_verification(ctx: Context<IssueVerification>, tier: u8, expires_at: i64) -> Result<()> {
+        let attester = &ctx.accounts.attester;
+
+        if tier == 0 {
+            return Err(ErrorCode::InvalidVerificationTier.into());
+        }
+
+        let current_time = Clock::get()?.unix_timestamp;
+        if expires_at <= current_time {
+            return Err(ErrorCode::InvalidVerificationExpiry.into());
+        }
+
+        let verification = &mut ctx.accounts.creator_verification;
+        verification.creator = ctx.accounts.creator.key();
+        verification.attester = attester.key();
+        verification.tier = tier;
+        verification.issued_at = current_time;
+        verification.expires_at = expires_at;
+        verification.bump = ctx.bumps.creator_verification;
+
+        emit!(CreatorVerified {
+            creator: verification.creator,
+            attester: attester.key(),
+            tier,
+            expires_at,
+        });
+
+        msg!("Creator verified!");
+        msg!("Creator: {}", verification.creator);
+        msg!("Tier: {}", tier);
+
+        Ok(())
+    }
+
+    // Withdraw a creator's verification; campaigns already launched keep the
+    // tier they recorded
+    pub fn revoke_verification(ctx: Context<RevokeVerification>) -> Result<()> {
+        let attester = &ctx.accounts.attester;
+
+        let creator = ctx.accounts.creator_verification.creator;
+
+        emit!(VerificationRevoked {
+            creator,
+            attester: attester.key(),
+        });
+
+        msg!("Creator verification revoked!");
+        msg!("Creator: {}", creator);
+
+        Ok(())
+    }
+
+    // Change who may contribute to a running campaign, e.g. to rotate the
+    // allowlist root as invitees are added. Existing contributions are unaffected.
+    pub fn set_campaign_access(ctx: Context<SetCampaignAccess>, access: CampaignAccess) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        if !campaign.is_active {
+            return Err(ErrorCode::CampaignNotActive.into());
+        }
+
+        let current_time = Clock::get()?.unix_timestamp;
+        if current_time > campaign.end_timestamp {
+            return Err(ErrorCode::CampaignEnded.into());
+        }
+
+        validate_access(&access)?;
+        campaign.access = access;
+
+        emit!(CampaignAccessSet {
+            campaign: campaign.key(),
+            access,
+        });
+
+        msg!("Campaign access updated!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Access: {:?}", campaign.access);
+
+        Ok(())
+    }
+
+    // Invite a wallet to contribute to an invite-only campaign (creator only)
+    pub fn invite_supporter(ctx: Context<InviteSupporter>) -> Result<()> {
+        let invitation = &mut ctx.accounts.invitation;
+        invitation.campaign = ctx.accounts.campaign.key();
+        invitation.supporter = ctx.accounts.supporter.key();
+        invitation.invited_at = Clock::get()?.unix_timestamp;
+        invitation.bump = ctx.bumps.invitation;
+
+        emit!(SupporterInvited {
+            campaign: invitation.campaign,
+            supporter: invitation.supporter,
+        });
+
+        msg!("Supporter invited!");
+        msg!("Supporter: {}", invitation.supporter);
+
+        Ok(())
+    }
+
+    // Withdraw an invitation; contributions already made with it stay in place
+    pub fn revoke_invitation(ctx: Context<RevokeInvitation>) -> Result<()> {
+        let invitation = &ctx.accounts.invitation;
+
+        emit!(InvitationRevoked {
+            campaign: invitation.campaign,
+            supporter: invitation.supporter,
+        });
+
+        msg!("Invitation revoked!");
+        msg!("Supporter: {}", invitation.supporter);
+
+        Ok(())
+    }
+
+    // Admin sets how long after contributing supporters may withdraw their
+    // contribution (None allows it until the campaign ends)
+    pub fn configure_withdrawals(ctx: Context<UpdatePlatformConfig>, cooling_off_seconds: Option<i64>) -> Result<()> {
+        let admin = &ctx.accounts.admin;
+
+        if cooling_off_seconds.is_some_and(|seconds| seconds < 0) {
+            return Err(ErrorCode::InvalidCoolingOffPeriod.into());
+        }
+
+        let platform_config = &mut ctx.accounts.platform_config;
+        platform_config.withdrawal_cooling_off_seconds = cooling_off_seconds;
+        platform_config.bump = ctx.bumps.platform_config;
+
+        emit!(WithdrawalsConfigured {
+            admin: admin.key(),
+            cooling_off_seconds,
+        });
+
+        msg!("Withdrawal settings updated!");
+        msg!("Cooling-off period: {:?} seconds", cooling_off_seconds);
+
+        Ok(())
+    }
+
+    // A supporter takes back their contribution while the campaign is still
+    // running. SOL is returned from escrow; a pending pledge has its approval
+    // revoked. The funding record is closed, so the supporter can contribute
+    // again later. Contributions with claimed rewards or a dispute are locked,
+    // and so is every contribution once the campaign reaches its goal.
+    pub fn withdraw_contribution(ctx: Context<WithdrawContribution>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let supporter_funding = &ctx.accounts.supporter_funding;
+        let supporter = &ctx.accounts.supporter;
+
+        if !campaign.is_active {
+            return Err(ErrorCode::CampaignNotActive.into());
+        }
+
+        let current_time = Clock::get()?.unix_timestamp;
+        if current_time > campaign.end_timestamp {
+            return Err(ErrorCode::CampaignEnded.into());
+        }
+
+        // Supporters and the creator can count on a funded campaign staying funded
+        if campaign.is_funded {
+            return Err(ErrorCode::CampaignIsFunded.into());
+        }
+
+        if let Some(cooling_off_seconds) = ctx.accounts.platform_config.withdrawal_cooling_off_seconds {
+            if current_time > supporter_funding.funded_at.saturating_add(cooling_off_seconds) {
+                return Err(ErrorCode::CoolingOffPeriodOver.into());
+            }
+        }
+
+        let amount = supporter_funding.amount_lamports;
+        match supporter_funding.pledge_status {
+            None => {
+                // Transfer SOL from campaign account back to supporter
+                let campaign_lamports = campaign.to_account_info().lamports()
+                    .checked_sub(amount)
+                    .ok_or(ErrorCode::InsufficientFunds)?;
+                let supporter_lamports = supporter.lamports()
+                    .checked_add(amount)
+                    .ok_or(ErrorCode::AmountOverflow)?;
+                **campaign.to_account_info().try_borrow_mut_lamports()? = campaign_lamports;
+                **supporter.to_account_info().try_borrow_mut_lamports()? = supporter_lamports;
+
+                let supporter_profile = &mut ctx.accounts.supporter_profile;
+                supporter_profile.total_contributed_lamports = supporter_profile.total_contributed_lamports
+                    .checked_sub(amount)
+                    .ok_or(ErrorCode::AmountOverflow)?;
+            }
+            Some(_) => {
+                let (Some(pledge_source), Some(token_program)) = (
+                    ctx.accounts.pledge_source.as_ref(),
+                    ctx.accounts.token_program.as_ref(),
+                ) else {
+                    return Err(ErrorCode::InvalidPledgeAccounts.into());
+                };
+
+                // Leave an approval the supporter has since given someone else alone
+                if pledge_source.delegate == COption::Some(campaign.key()) {
+                    token::revoke(CpiContext::new(
+                        token_program.to_account_info(),
+                        token::Revoke {
+                            source: pledge_source.to_account_info(),
+                            authority: supporter.to_account_info(),
+                        },
+                    ))?;
+                }
+
+                campaign.pledges_pending = campaign.pledges_pending
+                    .checked_sub(1)
+                    .ok_or(ErrorCode::CountOverflow)?;
+            }
+        }
+
+        campaign.raised_lamports = campaign.raised_lamports
+            .checked_sub(amount)
+            .ok_or(ErrorCode::AmountOverflow)?;
+        campaign.supporters_count = campaign.supporters_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+
+        let supporter_profile = &mut ctx.accounts.supporter_profile;
+        supporter_profile.campaigns_backed = supporter_profile.campaigns_backed
+            .checked_sub(1)
+            .ok_or(ErrorCode::CountOverflow)?;
+
+        emit!(ContributionWithdrawn {
+            campaign: campaign.key(),
+            supporter: supporter.key(),
+            amount,
+            raised_lamports: campaign.raised_lamports,
+            supporters_count: campaign.supporters_count,
+        });
+
+        msg!("Contribution withdrawn!");
+        msg!("Supporter: {}", supporter.key());
+        msg!("Amount: {}", amount);
+        msg!("Campaign raised: {}/{}", campaign.raised_lamports, campaign.funding_goal_lamports);
+
+        Ok(())
+    }
+
+    // Announce stretch goals above the funding goal, each unlocking a reward
+    // metadata tier (creator only). The list replaces the current one, but goals
+    // already reached must stay in place unchanged.
+    pub fn set_stretch_goals(ctx: Context<SetStretchGoals>, stretch_goals: Vec<StretchGoalTarget>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        if !campaign.is_active {
+            return Err(ErrorCode::CampaignNotActive.into());
+        }
+
+        let current_time = Clock::get()?.unix_timestamp;
+        if current_time > campaign.end_timestamp {
+            return Err(ErrorCode::CampaignEnded.into());
+        }
+
+        if stretch_goals.len() > MAX_STRETCH_GOALS {
+            return Err(ErrorCode::TooManyStretchGoals.into());
+        }
+
+        // Reached goals can't be dropped from the end of the list either
+        if campaign.stretch_goals.iter().skip(stretch_goals.len()).any(|goal| goal.reached_at != 0) {
+            return Err(ErrorCode::StretchGoalLocked.into());
+        }
+
+        // Thresholds climb from the funding goal; new goals must still be ahead
+        let mut previous_threshold = campaign.funding_goal_lamports;
+        let mut updated = Vec::with_capacity(stretch_goals.len());
+        for (index, target) in stretch_goals.into_iter().enumerate() {
+            validate_uri(&target.reward_uri)?;
+            if target.threshold_lamports <= previous_threshold {
+                return Err(ErrorCode::InvalidStretchGoalThreshold.into());
+            }
+            previous_threshold = target.threshold_lamports;
+
+            let reached_at = match campaign.stretch_goals.get(index) {
+                Some(goal) if goal.reached_at != 0 => {
+                    if goal.threshold_lamports != target.threshold_lamports || goal.reward_uri != target.reward_uri {
+                        return Err(ErrorCode::StretchGoalLocked.into());
+                    }
+                    goal.reached_at
+                }
+                _ => {
+                    if target.threshold_lamports <= campaign.raised_lamports {
+                        return Err(ErrorCode::InvalidStretchGoalThreshold.into());
+                    }
+                    0
+                }
+            };
+            updated.push(StretchGoal {
+                threshold_lamports: target.threshold_lamports,
+                reward_uri: target.reward_uri,
+                reached_at,
+            });
+        }
+        campaign.stretch_goals = updated;
+
+        resize_campaign(
+            &campaign.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            campaign.current_space(),
+        )?;
+
+        emit!(StretchGoalsSet {
+            campaign: campaign.key(),
+            stretch_goals: campaign.stretch_goals.clone(),
+        });
+
+        msg!("Stretch goals set!");
+        msg!("Campaign: {}", campaign.project_name);
+        msg!("Stretch goals: {}", campaign.stretch_goals.len());
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(
+    project_name: String,
+    description: String, 
+    content: CampaignContent,
+    funding_goal_lamports: u64,
+    nft_name: String,
+    nft_symbol: String,
+    nft_uri: String,
+    reward_mode: RewardMode,
+    access: CampaignAccess
+)]
+pub struct InitializeCampaign<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    // Creator's campaign counter, created with their first campaign
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CreatorProfile::SPACE,
+        seeds = [b"creator-profile", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+    
+    // Seeded by the creator's campaign count so names don't constrain the address
+    #[account(
+        init,
+        payer = creator,
+        space = Campaign::space(&project_name, &description, &content.uri, &nft_name, &nft_symbol, &nft_uri, 0),
+        seeds = [
+            b"campaign",
+            creator.key().as_ref(),
+            creator_profile.campaign_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    /// CHECK: This is just a reference to the NFT mint
+    pub nft_mint: UncheckedAccount<'info>,
+    
+    // Global campaign counter, created by the first campaign
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CampaignRegistry::SPACE,
+        seeds = [b"campaign-registry"],
+        bump
+    )]
+    pub campaign_registry: Box<Account<'info, CampaignRegistry>>,
+    
+    // Program-wide settings, created by the first campaign; no campaigns can be
+    // launched while the platform is paused
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = PlatformConfig::SPACE,
+        seeds = [b"platform-config"],
+        bump,
+        constraint = !platform_config.paused @ ErrorCode::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    
+    // Creator's identity verification; required when the goal is above the
+    // platform's verification threshold
+    #[account(
+        seeds = [b"creator-verification", creator.key().as_ref()],
+        bump = creator_verification.bump
+    )]
+    pub creator_verification: Option<Box<Account<'info, CreatorVerification>>>,
+    
+    // Page the new campaign is appended to, created by the first campaign on it
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CampaignRegistryPage::SPACE,
+        seeds = [
+            b"campaign-registry-page",
+            campaign_registry.next_page_index().to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub registry_page: Box<Account<'info, CampaignRegistryPage>>,
+    
+    // Optional fungible share token mint, with the campaign PDA as mint authority
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"share-mint", campaign.key().as_ref()],
+        bump,
+        mint::decimals = SHARE_TOKEN_DECIMALS,
+        mint::authority = campaign
+    )]
+    pub share_mint: Option<Account<'info, Mint>>,
+    
+    // Optional SPL mint that makes this a token campaign taking pledges
+    pub pledge_mint: Option<Box<Account<'info, Mint>>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount_lamports: u64, allowlist_proof: Vec<[u8; 32]>)]
+pub struct FundCampaign<'info> {
+    #[account(
+        mut,
+        constraint = !campaign.is_pledge_campaign() @ ErrorCode::PledgeCampaign,
+        constraint = !campaign.is_frozen @ ErrorCode::CampaignFrozen,
+        constraint = campaign.takedown_reason.is_none() @ ErrorCode::CampaignTakenDown
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    // Program-wide settings; the instruction is blocked while the platform is paused
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ ErrorCode::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    
+    #[account(mut)]
+    pub supporter: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = supporter,
+        space = SupporterFunding::space(),
+        seeds = [
+            b"supporter-funding",
+            campaign.key().as_ref(),
+            supporter.key().as_ref(),
+        ],
+        bump
+    )]
+    pub supporter_funding: Account<'info, SupporterFunding>,
+    
+    // Supporter's portfolio across campaigns, created with their first contribution
+    #[account(
+        init_if_needed,
+        payer = supporter,
+        space = SupporterProfile::SPACE,
+        seeds = [b"supporter-profile", supporter.key().as_ref()],
+        bump
+    )]
+    pub supporter_profile: Box<Account<'info, SupporterProfile>>,
+    
+    // Supporter's invitation; required by invite-only campaigns
+    #[account(
+        seeds = [b"invitation", campaign.key().as_ref(), supporter.key().as_ref()],
+        bump = invitation.bump
+    )]
+    pub invitation: Option<Account<'info, Invitation>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFunds<'info> {
+    #[account(
+        mut,
+        constraint = !campaign.is_pledge_campaign() @ ErrorCode::PledgeCampaign,
+        constraint = !campaign.is_frozen @ ErrorCode::CampaignFrozen,
+        constraint = campaign.takedown_reason.is_none() @ ErrorCode::CampaignTakenDown
+    )]
+    pub campaign: Account<'info, Campaign>,
+    // Program-wide settings; the instruction is blocked while the platform is paused
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ ErrorCode::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"creator-profile", campaign.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+    /// CHECK: This is the treasury PDA
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, allowlist_proof: Vec<[u8; 32]>)]
+pub struct Pledge<'info> {
+    #[account(
+        mut,
+        constraint = campaign.is_pledge_campaign() @ ErrorCode::NotPledgeCampaign,
+        constraint = !campaign.is_frozen @ ErrorCode::CampaignFrozen,
+        constraint = campaign.takedown_reason.is_none() @ ErrorCode::CampaignTakenDown
+    )]
+    pub campaign: Box<Account<'info, Campaign>>,
+    
+    // Program-wide settings; the instruction is blocked while the platform is paused
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ ErrorCode::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    
+    #[account(mut)]
+    pub supporter: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = supporter,
+        space = SupporterFunding::space(),
+        seeds = [
+            b"supporter-funding",
+            campaign.key().as_ref(),
+            supporter.key().as_ref(),
+        ],
+        bump
+    )]
+    pub supporter_funding: Box<Account<'info, SupporterFunding>>,
+    
+    // Supporter's portfolio across campaigns, created with their first contribution
+    #[account(
+        init_if_needed,
+        payer = supporter,
+        space = SupporterProfile::SPACE,
+        seeds = [b"supporter-profile", supporter.key().as_ref()],
+        bump
+    )]
+    pub supporter_profile: Box<Account<'info, SupporterProfile>>,
+    
+    // Supporter's invitation; required by invite-only campaigns
+    #[account(
+        seeds = [b"invitation", campaign.key().as_ref(), supporter.key().as_ref()],
+        bump = invitation.bump
+    )]
+    pub invitation: Option<Account<'info, Invitation>>,
+    
+    // Token account the pledge is collected from; the campaign PDA becomes its delegate
+    #[account(
+        mut,
+        token::mint = campaign.pledge_mint,
+        token::authority = supporter
+    )]
+    pub supporter_token_account: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CollectPledges<'info> {
+    #[account(
+        mut,
+        constraint = !campaign.is_frozen @ ErrorCode::CampaignFrozen,
+        constraint = campaign.takedown_reason.is_none() @ ErrorCode::CampaignTakenDown
+    )]
+    pub campaign: Box<Account<'info, Campaign>>,
+    
+    // Program-wide settings; the instruction is blocked while the platform is paused
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ ErrorCode::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    
+    // Anyone can run the crank; pays for the token accounts if they don't exist yet
+    #[account(mut)]
+    pub collector: Signer<'info>,
+    
+    #[account(
+        constraint = campaign.is_pledge_campaign() @ ErrorCode::NotPledgeCampaign,
+        address = campaign.pledge_mint @ ErrorCode::NotPledgeCampaign
+    )]
+    pub pledge_mint: Box<Account<'info, Mint>>,
+    
+    // Campaign-owned account holding collected pledges until they are released
+    #[account(
+        init_if_needed,
+        payer = collector,
+        associated_token::mint = pledge_mint,
+        associated_token::authority = campaign
+    )]
+    pub pledge_escrow: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Campaign creator, owner of the token account receiving the pledges
+    #[account(address = campaign.creator @ ErrorCode::Unauthorized)]
+    pub creator: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = collector,
+        associated_token::mint = pledge_mint,
+        associated_token::authority = creator
+    )]
+    pub creator_token_account: Box<Account<'info, TokenAccount>>,
+    
+    // Creator's profile, which records the outcome once the pledges are settled
+    #[account(
+        mut,
+        seeds = [b"creator-profile", campaign.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+    
+    /// CHECK: This is the treasury PDA
+    #[account(
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    // Receives the platform fee when the pledges are released
+    #[account(
+        init_if_needed,
+        payer = collector,
+        associated_token::mint = pledge_mint,
+        associated_token::authority = treasury
+    )]
+    pub treasury_token_account: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"supporter-funding",
+            campaign.key().as_ref(),
+            supporter.key().as_ref(),
+        ],
+        bump,
+        constraint = supporter_funding.campaign == campaign.key() @ ErrorCode::InvalidCampaign,
+        constraint = supporter_funding.supporter == supporter.key() @ ErrorCode::Unauthorized
+    )]
+    pub supporter_funding: Account<'info, SupporterFunding>,
+    
+    #[account(mut)]
+    pub supporter: Signer<'info>,
+    
+    // Creator's profile, which tracks refunded amounts
+    #[account(
+        mut,
+        seeds = [b"creator-profile", campaign.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    // Supporter's portfolio across campaigns
+    #[account(
+        mut,
+        seeds = [b"supporter-profile", supporter.key().as_ref()],
+        bump = supporter_profile.bump
+    )]
+    pub supporter_profile: Box<Account<'info, SupporterProfile>>,
+    
+    // Share token accounts, required if share tokens were minted for this contribution
+    #[account(
+        mut,
+        constraint = share_mint.key() == campaign.share_mint @ ErrorCode::InvalidShareMint
+    )]
+    pub share_mint: Option<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = supporter
+    )]
+    pub supporter_share_account: Option<Account<'info, TokenAccount>>,
+    
+    // Pledge escrow and the pledge's source account, required to refund a collected pledge
+    #[account(
+        mut,
+        associated_token::mint = campaign.pledge_mint,
+        associated_token::authority = campaign
+    )]
+    pub pledge_escrow: Option<Box<Account<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        address = supporter_funding.pledge_source @ ErrorCode::InvalidPledgeSource
+    )]
+    pub pledge_source: Option<Box<Account<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Context for treasury withdrawals
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    // Admin account must be a signer
+    #[account(mut, address = ADMIN_PUBKEY @ ErrorCode::Unauthorized)]
+    pub admin: Signer<'info>,
+    
+    /// CHECK: This is the treasury PDA
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for withdrawing token fees from the treasury
+#[derive(Accounts)]
+pub struct WithdrawTreasuryTokens<'info> {
+    #[account(address = ADMIN_PUBKEY @ ErrorCode::Unauthorized)]
+    pub admin: Signer<'info>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: This is the treasury PDA
+    #[account(
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = admin
+    )]
+    pub admin_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+// Context for minting edition NFTs to supporters
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferNftToEscrow<'info> {
+    // Campaign account
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+    
+    // Campaign creator as signer
+    #[account(
+        mut,
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+    
+    // The NFT mint account
+    #[account(
+        constraint = nft_mint.key() == campaign.nft_mint @ ErrorCode::InvalidNftMint
+    )]
+    pub nft_mint: Account<'info, Mint>,
+    
+    // Creator's token account holding the NFT
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == nft_mint.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = creator_token_account.owner == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+    
+    // PDA that acts as the escrow authority
+    #[account(
+        seeds = [b"escrow", campaign.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA used as the escrow authority
+    pub escrow_authority: AccountInfo<'info>,
+    
+    // Token account owned by the escrow PDA to hold the NFT
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = nft_mint,
+        associated_token::authority = escrow_authority
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    // Required programs
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimNftFromEscrow<'info> {
+    // Campaign account
+    #[account(
+        mut,
+        constraint = !campaign.is_frozen @ ErrorCode::CampaignFrozen,
+        constraint = campaign.takedown_reason.is_none() @ ErrorCode::CampaignTakenDown
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    // Program-wide settings; the instruction is blocked while the platform is paused
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ ErrorCode::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    
+    // The supporter funding record to update
+    #[account(
+        mut,
+        seeds = [
+            b"supporter-funding",
+            campaign.key().as_ref(),
+            supporter.key().as_ref(),
+        ],
+        bump,
+        constraint = supporter_funding.campaign == campaign.key() @ ErrorCode::InvalidCampaign,
+        constraint = supporter_funding.supporter == supporter.key() @ ErrorCode::Unauthorized,
+        // Contributions returned through a dispute no longer earn rewards
+        constraint = !supporter_funding.is_claimed @ ErrorCode::RefundAlreadyClaimed,
+        constraint = supporter_funding.is_paid() @ ErrorCode::PledgeNotCollected
+    )]
+    pub supporter_funding: Account<'info, SupporterFunding>,
+    
+    // Supporter as signer
+    #[account(mut)]
+    pub supporter: Signer<'info>,
+        // Supporter's portfolio across campaigns
+    #[account(
+        mut,
+        seeds = [b"supporter-profile", supporter.key().as_ref()],
+        bump = supporter_profile.bump
+    )]
+    pub supporter_profile: Box<Account<'info, SupporterProfile>>,
+    
+    // The NFT mint account
+    #[account(
+        constraint = nft_mint.key() == campaign.nft_mint @ ErrorCode::InvalidNftMint
+    )]
+    pub nft_mint: Account<'info, Mint>,
+    
+    // PDA that acts as the escrow authority
+    #[account(
+        seeds = [b"escrow", campaign.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA used as the escrow authority
+    pub escrow_authority: AccountInfo<'info>,
+    
+    // Token account owned by the escrow PDA holding the NFT
+    #[account(
+        mut,
+        constraint = escrow_token_account.mint == nft_mint.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = escrow_token_account.owner == escrow_authority.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    // Supporter's token account to receive the NFT
+    #[account(
+        init,
+        payer = supporter,
+        associated_token::mint = nft_mint,
+        associated_token::authority = supporter
+    )]
+    pub supporter_token_account: Account<'info, TokenAccount>,
+    
+    // Required programs
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReclaimEscrowedNft<'info> {
+    // Campaign account
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+
+    // Campaign creator as signer
+    #[account(
+        mut,
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+
+    // The NFT mint account
+    #[account(
+        constraint = nft_mint.key() == campaign.nft_mint @ ErrorCode::InvalidNftMint
+    )]
+    pub nft_mint: Account<'info, Mint>,
+
+    // Creator's token account to receive the NFT back
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == nft_mint.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = creator_token_account.owner == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    // PDA that acts as the escrow authority
+    #[account(
+        seeds = [b"escrow", campaign.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA used as the escrow authority
+    pub escrow_authority: AccountInfo<'info>,
+
+    // Token account owned by the escrow PDA holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = escrow_authority
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    // Required programs
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeBadgeMint<'info> {
+    // Campaign the badge mint belongs to
+    pub campaign: Account<'info, Campaign>,
+
+    // Campaign creator pays for the mint account
+    #[account(
+        mut,
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+
+    // PDA that acts as mint and freeze authority for the badges
+    #[account(
+        seeds = [b"badge-authority", campaign.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA used as the badge authority
+    pub badge_authority: AccountInfo<'info>,
+
+    // Badge mint PDA, created by the instruction for the campaign's token program
+    #[account(
+        mut,
+        seeds = [b"badge-mint", campaign.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Allocated and initialized as a mint in the instruction
+    pub badge_mint: UncheckedAccount<'info>,
+
+    // Token-2022 for soulbound badges, SPL Token for frozen badges
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimSupporterBadge<'info> {
+    // Campaign account
+    #[account(
+        constraint = !campaign.is_frozen @ ErrorCode::CampaignFrozen,
+        constraint = campaign.takedown_reason.is_none() @ ErrorCode::CampaignTakenDown
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    // Program-wide settings; the instruction is blocked while the platform is paused
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ ErrorCode::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    // The supporter funding record to update
+    #[account(
+        mut,
+        seeds = [
+            b"supporter-funding",
+            campaign.key().as_ref(),
+            supporter.key().as_ref(),
+        ],
+        bump,
+        constraint = supporter_funding.campaign == campaign.key() @ ErrorCode::InvalidCampaign,
+        constraint = supporter_funding.supporter == supporter.key() @ ErrorCode::Unauthorized,
+        // Contributions returned through a dispute no longer earn rewards
+        constraint = !supporter_funding.is_claimed @ ErrorCode::RefundAlreadyClaimed,
+        constraint = supporter_funding.is_paid() @ ErrorCode::PledgeNotCollected
+    )]
+    pub supporter_funding: Account<'info, SupporterFunding>,
+
+    // Supporter as signer
+    #[account(mut)]
+    pub supporter: Signer<'info>,
+
+    // Supporter's portfolio across campaigns
+    #[account(
+        mut,
+        seeds = [b"supporter-profile", supporter.key().as_ref()],
+        bump = supporter_profile.bump
+    )]
+    pub supporter_profile: Box<Account<'info, SupporterProfile>>,
+
+    // PDA that acts as mint and freeze authority for the badges
+    #[account(
+        seeds = [b"badge-authority", campaign.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA used as the badge authority
+    pub badge_authority: AccountInfo<'info>,
+
+    // The campaign's badge mint
+    #[account(
+        mut,
+        seeds = [b"badge-mint", campaign.key().as_ref()],
+        bump,
+        mint::token_program = token_program
+    )]
+    pub badge_mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    // Supporter's token account to receive the badge
+    #[account(
+        init,
+        payer = supporter,
+        associated_token::mint = badge_mint,
+        associated_token::authority = supporter,
+        associated_token::token_program = token_program
+    )]
+    pub supporter_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    // Required programs
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializePlatformCollection<'info> {
+    // Admin account must be a signer
+    #[account(mut, address = ADMIN_PUBKEY @ ErrorCode::Unauthorized)]
+    pub admin: Signer<'info>,
+
+    // PDA that acts as mint and update authority of the platform collection
+    #[account(
+        seeds = [b"platform-authority"],
+        bump
+    )]
+    /// CHECK: This is a PDA used as the platform collection authority
+    pub platform_authority: AccountInfo<'info>,
+
+    // Platform collection mint
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"platform-collection"],
+        bump,
+        mint::decimals = 0,
+        mint::authority = platform_authority,
+        mint::freeze_authority = platform_authority
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    // Token account holding the platform collection NFT
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = collection_mint,
+        associated_token::authority = platform_authority
+    )]
+    pub collection_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Metadata PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), collection_mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), collection_mint.key().as_ref(), b"edition"],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    // Required programs
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeCampaignCollection<'info> {
+    // Campaign account, mint and update authority of its collection
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+
+    // Campaign creator as signer
+    #[account(
+        mut,
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+
+    // Campaign collection mint
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"collection-mint", campaign.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = campaign,
+        mint::freeze_authority = campaign
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    // Token account holding the campaign collection NFT
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = collection_mint,
+        associated_token::authority = campaign
+    )]
+    pub collection_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Metadata PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), collection_mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), collection_mint.key().as_ref(), b"edition"],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Platform parent collection mint, only referenced by address
+    #[account(
+        seeds = [b"platform-collection"],
+        bump
+    )]
+    pub platform_collection_mint: UncheckedAccount<'info>,
+
+    // Required programs
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCampaignCollection<'info> {
+    // Campaign account
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+
+    // Campaign creator as signer
+    #[account(
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+
+    // Existing collection mint to reference
+    pub collection_mint: Account<'info, Mint>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VerifyRewardCollection<'info> {
+    // Campaign account, collection authority of its collection
+    pub campaign: Account<'info, Campaign>,
+
+    // Pays for any collection bookkeeping
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Supporter record holding the edition mint, when verifying a printed edition
+    #[account(
+        constraint = supporter_funding.campaign == campaign.key() @ ErrorCode::InvalidCampaign
+    )]
+    pub supporter_funding: Option<Account<'info, SupporterFunding>>,
+
+    // The reward NFT mint
+    pub reward_mint: Account<'info, Mint>,
+
+    /// CHECK: Reward metadata PDA, validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), reward_mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub reward_metadata: UncheckedAccount<'info>,
+
+    // The campaign's collection mint
+    #[account(
+        constraint = collection_mint.key() == campaign.collection_mint @ ErrorCode::InvalidCollection
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    /// CHECK: Collection metadata PDA, validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), collection_mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition PDA, validated by the token metadata program
+    #[account(
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), collection_mint.key().as_ref(), b"edition"],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Collection authority record for referenced collections, validated by the token metadata program
+    pub collection_authority_record: Option<UncheckedAccount<'info>>,
+
+    // Required programs
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VerifyCampaignCollection<'info> {
+    // Campaign whose collection is being verified
+    pub campaign: Account<'info, Campaign>,
+
+    // Pays for any collection bookkeeping
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // The campaign's collection mint
+    #[account(
+        constraint = collection_mint.key() == campaign.collection_mint @ ErrorCode::InvalidCollection
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    /// CHECK: Campaign collection metadata PDA, validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), collection_mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    // PDA that acts as the platform collection authority
+    #[account(
+        seeds = [b"platform-authority"],
+        bump
+    )]
+    /// CHECK: This is a PDA used as the platform collection authority
+    pub platform_authority: AccountInfo<'info>,
+
+    // Platform collection mint
+    #[account(
+        seeds = [b"platform-collection"],
+        bump
+    )]
+    pub platform_collection_mint: Account<'info, Mint>,
+
+    /// CHECK: Platform collection metadata PDA, validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), platform_collection_mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub platform_collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Platform collection master edition PDA, validated by the token metadata program
+    #[account(
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), platform_collection_mint.key().as_ref(), b"edition"],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub platform_collection_master_edition: UncheckedAccount<'info>,
+
+    // Required programs
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(Accounts)]
+pub struct MarkCampaignDelivered<'info> {
+    // Campaign account, neither frozen nor taken down
+    #[account(
+        mut,
+        constraint = !campaign.is_frozen @ ErrorCode::CampaignFrozen,
+        constraint = campaign.takedown_reason.is_none() @ ErrorCode::CampaignTakenDown
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    // Campaign creator as signer
+    #[account(
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(funded_uri: String, delivered_uri: String, failed_uri: String)]
+pub struct SetOutcomeMetadata<'info> {
+    // Campaign account
+    pub campaign: Account<'info, Campaign>,
+
+    // Campaign creator as signer
+    #[account(
+        mut,
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+
+    // Outcome URIs for the campaign's reward NFTs
+    #[account(
+        init,
+        payer = creator,
+        space = OutcomeMetadata::space(&funded_uri, &delivered_uri, &failed_uri),
+        seeds = [b"outcome-metadata", campaign.key().as_ref()],
+        bump
+    )]
+    pub outcome_metadata: Account<'info, OutcomeMetadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRewardMetadata<'info> {
+    // Campaign account, update authority of its reward NFTs
+    pub campaign: Account<'info, Campaign>,
+
+    // Outcome URIs registered by the creator
+    #[account(
+        seeds = [b"outcome-metadata", campaign.key().as_ref()],
+        bump,
+        constraint = outcome_metadata.campaign == campaign.key() @ ErrorCode::InvalidCampaign
+    )]
+    pub outcome_metadata: Account<'info, OutcomeMetadata>,
+
+    // Supporter record holding the edition mint, when updating a printed edition
+    #[account(
+        constraint = supporter_funding.campaign == campaign.key() @ ErrorCode::InvalidCampaign
+    )]
+    pub supporter_funding: Option<Account<'info, SupporterFunding>>,
+
+    // The reward NFT mint
+    pub reward_mint: Account<'info, Mint>,
+
+    // Reward metadata, must have the campaign PDA as update authority
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), reward_mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump,
+        constraint = reward_metadata.update_authority == campaign.key() @ ErrorCode::Unauthorized
+    )]
+    pub reward_metadata: Account<'info, MetadataAccount>,
+
+    // Required programs
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimShareTokens<'info> {
+    // Campaign account, mint authority of its share tokens
+    #[account(
+        constraint = !campaign.is_frozen @ ErrorCode::CampaignFrozen,
+        constraint = campaign.takedown_reason.is_none() @ ErrorCode::CampaignTakenDown
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    // Program-wide settings; the instruction is blocked while the platform is paused
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ ErrorCode::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    // The supporter funding record to update
+    #[account(
+        mut,
+        seeds = [
+            b"supporter-funding",
+            campaign.key().as_ref(),
+            supporter.key().as_ref(),
+        ],
+        bump,
+        constraint = supporter_funding.campaign == campaign.key() @ ErrorCode::InvalidCampaign,
+        constraint = supporter_funding.supporter == supporter.key() @ ErrorCode::Unauthorized
+    )]
+    pub supporter_funding: Account<'info, SupporterFunding>,
+
+    // Supporter as signer
+    #[account(mut)]
+    pub supporter: Signer<'info>,
+
+    // The campaign's share token mint
+    #[account(
+        mut,
+        constraint = share_mint.key() == campaign.share_mint @ ErrorCode::InvalidShareMint
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    // Supporter's token account to receive the shares
+    #[account(
+        init,
+        payer = supporter,
+        associated_token::mint = share_mint,
+        associated_token::authority = supporter
+    )]
+    pub supporter_share_account: Account<'info, TokenAccount>,
+
+    // Required programs
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCampaignMetadata<'info> {
+    // Resized by the handler to fit the new strings. Not an Anchor realloc: that
+    // hands every lamport above the new rent minimum, contributions included,
+    // to the payer when the account shrinks.
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+
+    // Campaign creator as signer, pays for growth and receives the rent difference on shrink
+    #[account(
+        mut,
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCreatorProfile<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CreatorProfile::SPACE,
+        seeds = [b"creator-profile", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelCampaign<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+
+    // Campaign creator as signer
+    #[account(
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator-profile", campaign.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+}
+
+#[derive(Accounts)]
+pub struct RecordCampaignFailure<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(
+        mut,
+        seeds = [b"creator-profile", campaign.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+}
+
+#[derive(Accounts)]
+#[instruction(content: CampaignContent)]
+pub struct PostUpdate<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+
+    // Numbered by the campaign's update counter
+    #[account(
+        init,
+        payer = creator,
+        space = CampaignUpdate::space(&content.uri),
+        seeds = [b"campaign-update", campaign.key().as_ref(), campaign.updates_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub update: Account<'info, CampaignUpdate>,
+
+    // Campaign creator as signer, pays for the update account
+    #[account(
+        mut,
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePlatformConfig<'info> {
+    // Admin account must be a signer
+    #[account(mut, address = ADMIN_PUBKEY @ ErrorCode::Unauthorized)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = PlatformConfig::SPACE,
+        seeds = [b"platform-config"],
+        bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCampaignFrozen<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+
+    // Admin account must be a signer
+    #[account(address = ADMIN_PUBKEY @ ErrorCode::Unauthorized)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FlagCampaign<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+
+    // Platform moderator (or admin) as signer
+    #[account(
+        constraint = moderator.key() == platform_config.moderator || moderator.key() == ADMIN_PUBKEY
+            @ ErrorCode::Unauthorized
+    )]
+    pub moderator: Signer<'info>,
+
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"creator-profile", campaign.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+}
+
+#[derive(Accounts)]
+pub struct FileDispute<'info> {
+    #[account(
+        mut,
+        constraint = campaign.takedown_reason.is_none() @ ErrorCode::CampaignTakenDown
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    // Only supporters whose contribution is still in the campaign can dispute
+    #[account(
+        seeds = [
+            b"supporter-funding",
+            campaign.key().as_ref(),
+            supporter.key().as_ref(),
+        ],
+        bump,
+        constraint = !supporter_funding.is_claimed @ ErrorCode::RefundAlreadyClaimed
+    )]
+    pub supporter_funding: Account<'info, SupporterFunding>,
+
+    #[account(
+        init,
+        payer = supporter,
+        space = Dispute::SPACE,
+        seeds = [b"dispute", campaign.key().as_ref(), supporter.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub supporter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(mut)]
+    pub campaign: Box<Account<'info, Campaign>>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", campaign.key().as_ref(), dispute.supporter.as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"supporter-funding",
+            campaign.key().as_ref(),
+            dispute.supporter.as_ref(),
+        ],
+        bump
+    )]
+    pub supporter_funding: Account<'info, SupporterFunding>,
+
+    // Receives the contribution if the dispute is upheld
+    #[account(mut, address = dispute.supporter @ ErrorCode::Unauthorized)]
+    pub supporter: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"supporter-profile", dispute.supporter.as_ref()],
+        bump = supporter_profile.bump
+    )]
+    pub supporter_profile: Box<Account<'info, SupporterProfile>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator-profile", campaign.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+
+    // Platform arbitrator (or admin) as signer
+    #[account(
+        constraint = arbitrator.key() == platform_config.arbitrator || arbitrator.key() == ADMIN_PUBKEY
+            @ ErrorCode::Unauthorized
+    )]
+    pub arbitrator: Signer<'info>,
+
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct IssueVerification<'info> {
+    // Platform attester (or admin) as signer, pays for the verification account
+    #[account(
+        mut,
+        constraint = attester.key() == platform_config.attester || attester.key() == ADMIN_PUBKEY
+            @ ErrorCode::Unauthorized
+    )]
+    pub attester: Signer<'info>,
+
+    /// CHECK: Wallet of the creator being verified
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = attester,
+        space = CreatorVerification::SPACE,
+        seeds = [b"creator-verification", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_verification: Account<'info, CreatorVerification>,
+
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeVerification<'info> {
+    // Platform attester (or admin) as signer, receives the account rent
+    #[account(
+        mut,
+        constraint = attester.key() == platform_config.attester || attester.key() == ADMIN_PUBKEY
+            @ ErrorCode::Unauthorized
+    )]
+    pub attester: Signer<'info>,
+
+    #[account(
+        mut,
+        close = attester,
+        seeds = [b"creator-verification", creator_verification.creator.as_ref()],
+        bump = creator_verification.bump
+    )]
+    pub creator_verification: Account<'info, CreatorVerification>,
+
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetCampaignAccess<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InviteSupporter<'info> {
+    pub campaign: Account<'info, Campaign>,
+
+    // Campaign creator as signer, pays for the invitation account
+    #[account(
+        mut,
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+
+    /// CHECK: Wallet being invited
+    pub supporter: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = Invitation::SPACE,
+        seeds = [b"invitation", campaign.key().as_ref(), supporter.key().as_ref()],
+        bump
+    )]
+    pub invitation: Account<'info, Invitation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeInvitation<'info> {
+    pub campaign: Account<'info, Campaign>,
+
+    // Campaign creator as signer, receives the account rent
+    #[account(
+        mut,
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        has_one = campaign,
+        seeds = [b"invitation", campaign.key().as_ref(), invitation.supporter.as_ref()],
+        bump = invitation.bump
+    )]
+    pub invitation: Account<'info, Invitation>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawContribution<'info> {
+    #[account(
+        mut,
+        constraint = !campaign.is_frozen @ ErrorCode::CampaignFrozen,
+        constraint = campaign.takedown_reason.is_none() @ ErrorCode::CampaignTakenDown
+    )]
+    pub campaign: Box<Account<'info, Campaign>>,
+
+    // Program-wide settings; the instruction is blocked while the platform is paused
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ ErrorCode::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    // Closed on withdrawal, returning its rent to the supporter
+    #[account(
+        mut,
+        close = supporter,
+        seeds = [
+            b"supporter-funding",
+            campaign.key().as_ref(),
+            supporter.key().as_ref(),
+        ],
+        bump,
+        constraint = !supporter_funding.is_claimed @ ErrorCode::RefundAlreadyClaimed,
+        constraint = !supporter_funding.nft_minted && supporter_funding.shares_minted == 0
+            @ ErrorCode::ContributionRewardsClaimed
+    )]
+    pub supporter_funding: Account<'info, SupporterFunding>,
+
+    // Supporter's dispute PDA, which must not exist
+    /// CHECK: only checked to be empty
+    #[account(
+        seeds = [b"dispute", campaign.key().as_ref(), supporter.key().as_ref()],
+        bump,
+        constraint = dispute.data_is_empty() @ ErrorCode::ContributionDisputed
+    )]
+    pub dispute: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub supporter: Signer<'info>,
+
+    // Supporter's portfolio across campaigns
+    #[account(
+        mut,
+        seeds = [b"supporter-profile", supporter.key().as_ref()],
+        bump = supporter_profile.bump
+    )]
+    pub supporter_profile: Box<Account<'info, SupporterProfile>>,
+
+    // Pledge token account and program, required to withdraw a pledge
+    #[account(
+        mut,
+        address = supporter_funding.pledge_source @ ErrorCode::InvalidPledgeSource
+    )]
+    pub pledge_source: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[derive(Accounts)]
+pub struct SetStretchGoals<'info> {
+    // Resized by the handler to fit the new stretch goals, moving only the rent difference
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>,
+
+    // Campaign creator as signer, pays for growth and receives the rent difference on shrink
+    #[account(
+        mut,
+        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintEditionNft<'info> {
+    // Campaign must be fully funded
     #[account(mut)]
     pub campaign: Account<'info, Campaign>,
     
+    // The supporter funding record to update
     #[account(
         mut,
         seeds = [
             b"supporter-funding",
             campaign.key().as_ref(),
-            supporter.key().as_ref(),
+            supporter_funding.supporter.as_ref(),
         ],
         bump,
-        constraint = supporter_funding.campaign == campaign.key() @ ErrorCode::InvalidCampaign,
-        constraint = supporter_funding.supporter == supporter.key() @ ErrorCode::Unauthorized
+        constraint = supporter_funding.campaign == campaign.key() @ ErrorCode::InvalidCampaign
     )]
     pub supporter_funding: Account<'info, SupporterFunding>,
     
+    /// CHECK: The creator account (not required to sign)
+    #[account(constraint = creator.key() == campaign.creator @ ErrorCode::InvalidCampaign)]
+    pub creator: AccountInfo<'info>,
+    
+    // Signer for the transaction (must be the supporter who funded the campaign)
+    #[account(constraint = authority.key() == supporter_funding.supporter @ ErrorCode::Unauthorized)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: The master edition NFT mint, verified by constraint to match the campaign's NFT mint
+    #[account(constraint = master_edition_mint.key() == campaign.nft_mint @ ErrorCode::InvalidCampaign)]
+    pub master_edition_mint: AccountInfo<'info>,
+    
+    /// CHECK: The new edition mint account that will be created
     #[account(mut)]
-    pub supporter: Signer<'info>,
+    pub edition_mint: AccountInfo<'info>,
+    
+    /// CHECK: Mint authority for the edition mint
+    pub mint_authority: Signer<'info>,
     
+    /// CHECK: Recipient's token account for the edition NFT
+    #[account(mut)]
+    pub recipient_token_account: AccountInfo<'info>,
+    
+    // Required programs
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[account]
+pub struct Campaign {
+    pub creator: Pubkey,
+    pub bump: u8, // Campaign PDA bump, used when the campaign signs CPIs
+    pub id: u64,  // Position in the campaign registry, assigned in creation order
+    pub creator_index: u64, // Creator's campaign number, used in the campaign PDA seeds
+    pub verification_tier: u8, // Creator's verification tier at launch (0 if unverified)
+    pub project_name: String,
+    pub description: String,       // Optional short summary
+    pub content_uri: String,       // Full campaign description as canonical JSON (Arweave/IPFS/HTTPS)
+    pub content_hash: [u8; 32],    // SHA-256 of the document at content_uri
+    pub funding_goal_lamports: u64,
+    pub raised_lamports: u64,
+    pub supporters_count: u32,
+    pub updates_count: u32, // Progress updates posted; seeds the next CampaignUpdate PDA
+    pub is_active: bool,
+    pub is_funded: bool,
+    pub is_delivered: bool, // Set by the creator once the funded project is completed
+    pub outcome_recorded: bool, // Success, failure or cancellation counted in the creator profile
+    pub is_frozen: bool, // Frozen by the admin: no contributions, withdrawals or claims, refunds stay open
+    pub takedown_reason: Option<TakedownReason>, // Set when a moderator takes the campaign down
+    pub created_at: i64,
+    pub end_timestamp: i64,
+    pub claim_deadline: i64, // Escrowed NFT can be reclaimed by the creator after this
+    pub dispute_deadline: i64, // Supporters can file disputes until this; withdrawals open after it
+    pub open_disputes: u32,    // Disputes awaiting the arbitrator; withdrawals are blocked while non-zero
+    pub access: CampaignAccess, // Who may contribute: anyone, allowlisted wallets or invited wallets
+    pub stretch_goals: Vec<StretchGoal>, // Thresholds above the funding goal, in increasing order
+    
+    // NFT details
+    pub nft_name: String,
+    pub nft_symbol: String,
+    pub nft_uri: String,
+    pub nft_mint: Pubkey,
+    pub nft_in_escrow: bool, // Flag to indicate whether NFT has been transferred to escrow
+    pub reward_mode: RewardMode, // Transferable escrowed NFT or a soulbound supporter badge
+    pub collection_mint: Pubkey, // Collection rewards are verified into (zeroed if not set)
+    pub share_mint: Pubkey,      // Fungible supporter share token mint (zeroed if disabled)
+    pub pledge_mint: Pubkey,     // Token pledges are taken in, with amounts in its base units (zeroed for SOL)
+    pub pledges_pending: u32,    // Pledges not yet collected, failed or cancelled
+    pub pledges_failed: u32,     // Pledges collect_pledges couldn't pull; excluded from raised_lamports
+    
+    // Edition NFT tracking
+    pub max_editions: u64,        // Maximum number of editions that can be minted (5 for hackathon demo)
+    pub editions_minted: u64,     // Number of editions already minted
+}
+
+// Per-creator profile and track record. `campaign_count` is the number of
+// campaigns launched and seeds the creator's next campaign PDA.
+#[account]
+pub struct CreatorProfile {
+    pub creator: Pubkey,
+    pub campaign_count: u64,
+    pub bump: u8,
+    pub display_name: String,
+    pub avatar_uri: String,
+    pub socials_hash: [u8; 32], // Hash of the creator's off-chain social links
+    
+    // Running stats, maintained by the program
+    pub campaigns_succeeded: u64,
+    pub campaigns_failed: u64,
+    pub campaigns_cancelled: u64,
+    pub campaigns_taken_down: u64,
+    pub total_raised_lamports: u64,   // Raised by successful campaigns
+    pub total_refunded_lamports: u64, // Refunded to supporters of failed or cancelled campaigns
+}
+
+// Per-wallet portfolio aggregating a supporter's activity across campaigns
+#[account]
+pub struct SupporterProfile {
+    pub supporter: Pubkey,
+    pub bump: u8,
+    pub total_contributed_lamports: u64,
+    pub campaigns_backed: u64,
+    pub refunds_received: u64,
+    pub total_refunded_lamports: u64,
+    pub nfts_claimed: u64, // Escrowed NFTs, editions and badges
+}
+
+// Program-wide settings controlled by the admin
+#[account]
+pub struct PlatformConfig {
+    pub paused: bool, // Emergency stop for launches, contributions, withdrawals and reward claims
+    pub moderator: Pubkey, // Can take down fraudulent campaigns (zeroed if unset)
+    pub arbitrator: Pubkey, // Resolves supporter disputes (zeroed if unset)
+    pub dispute_window_seconds: Option<i64>, // None uses DEFAULT_DISPUTE_WINDOW_SECONDS
+    pub attester: Pubkey, // Issues and revokes creator verifications (zeroed if unset)
+    pub verification_threshold_lamports: Option<u64>, // Goals above this need a verified creator; None disables
+    pub withdrawal_cooling_off_seconds: Option<i64>, // Time after contributing to withdraw; None allows it until the end
+    pub bump: u8,
+}
+
+// Global campaign counter; campaign ids are assigned from it
+#[account]
+pub struct CampaignRegistry {
+    pub campaign_count: u64,
+    pub bump: u8,
+}
+
+// Fixed-size page of campaign addresses in id order. Page `n` holds campaign
+// ids `n * CAMPAIGNS_PER_PAGE` up to the next page, so clients can enumerate
+// every campaign by walking pages 0..=campaign_count / CAMPAIGNS_PER_PAGE.
+#[account]
+pub struct CampaignRegistryPage {
+    pub page_index: u64,
+    pub campaigns: Vec<Pubkey>,
+}
+
+// Creator progress update. `sequence` counts from 0 in posting order.
+#[account]
+pub struct CampaignUpdate {
+    pub campaign: Pubkey,
+    pub sequence: u32,
+    pub posted_at: i64,
+    pub content_uri: String,
+    pub content_hash: [u8; 32], // SHA-256 of the canonical JSON at content_uri
+    pub is_milestone: bool,     // Update reports reaching a project milestone
+    pub bump: u8,
+}
+
+// How supporters of a campaign are rewarded
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RewardMode {
+    Transferable, // Creator's NFT is escrowed and claimed by supporters
+    Soulbound,    // Token-2022 badge with the NonTransferable extension
+    Frozen,       // SPL badge whose token account is frozen by the badge PDA
+}
+
+// Off-chain campaign description: where it lives and the SHA-256 hash of its
+// canonical JSON, so clients can check the fetched document wasn't altered
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CampaignContent {
+    pub uri: String,
+    pub hash: [u8; 32],
+}
+
+// Who may contribute to a campaign
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CampaignAccess {
+    Public,
+    // Merkle root over the allowed wallets; supporters pass a proof of membership
+    Allowlist { root: [u8; 32] },
+    // Supporters need an Invitation PDA created by the creator
+    InviteOnly,
+}
+
+// Extra funding threshold announced by the creator. Reaching it switches funded
+// rewards to `reward_uri` when their metadata is updated.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct StretchGoal {
+    pub threshold_lamports: u64, // Raised total that unlocks the goal
+    pub reward_uri: String,
+    pub reached_at: i64, // Zero until the raised total crosses the threshold
+}
+
+// Stretch goal as passed to set_stretch_goals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct StretchGoalTarget {
+    pub threshold_lamports: u64,
+    pub reward_uri: String,
+}
+
+// Creator's invitation for one wallet to contribute to an invite-only campaign
+#[account]
+pub struct Invitation {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub invited_at: i64,
+    pub bump: u8,
+}
+
+// Identity verification of a creator, issued by the platform attester after KYC
+#[account]
+pub struct CreatorVerification {
+    pub creator: Pubkey,
+    pub attester: Pubkey,
+    pub tier: u8, // Verification level, 1 and up
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+// A supporter's objection to a funded campaign, one per supporter and campaign
+#[account]
+pub struct Dispute {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub evidence_hash: [u8; 32], // SHA-256 of the supporter's off-chain evidence
+    pub filed_at: i64,
+    pub status: DisputeStatus,
+    pub resolved_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisputeStatus {
+    Open,
+    Released, // Dismissed; the contribution stays with the campaign
+    Refunded, // Upheld; the contribution was returned to the supporter
+}
+
+// How the arbitrator settles a dispute
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisputeResolution {
+    Release,
+    Refund,
+}
+
+// Why a moderator took a campaign down
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TakedownReason {
+    Fraud,
+    Impersonation,
+    ProhibitedContent,
+    IntellectualProperty,
+    Other,
+}
+
+// What happened to a campaign, as reflected in its reward metadata
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CampaignOutcome {
+    Funded,
+    Delivered,
+    Failed,
+}
+
+#[account]
+pub struct SupporterFunding {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub amount_lamports: u64,
+    pub funded_at: i64,
+    pub is_claimed: bool,
+    pub nft_minted: bool,         // Whether an NFT has been minted for this supporter
+    pub edition_number: u64,      // Which edition number was minted for this supporter
+    pub edition_mint: Pubkey,     // Mint address of the edition NFT (zeroed if not minted)
+    pub reward_tier: u8,          // Stretch goals reached when the reward was claimed; its metadata tier
+    pub shares_minted: u64,       // Share tokens minted for this contribution (burned on refund)
+    pub pledge_source: Pubkey,    // Token account a pledge is collected from (zeroed for SOL contributions)
+    pub pledge_status: Option<PledgeStatus>, // None for SOL contributions
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PledgeStatus {
+    Pending,   // Approved, waiting for collect_pledges
+    Collected, // Tokens pulled into the campaign's pledge escrow
+    Failed,    // Approval or balance no longer covered the pledge
+    Cancelled, // Refunded through a dispute before collection
+}
+
+#[account]
+pub struct OutcomeMetadata {
+    pub campaign: Pubkey,
+    pub funded_uri: String,    // Reward URI once the goal is reached
+    pub delivered_uri: String, // Reward URI once the creator delivers the project
+    pub failed_uri: String,    // Reward URI if the campaign ends below its goal
+}
+
+impl Campaign {
+    fn space(
+        project_name: &str,
+        description: &str,
+        content_uri: &str,
+        nft_name: &str,
+        nft_symbol: &str,
+        nft_uri: &str,
+        stretch_goals_space: usize,
+    ) -> usize {
+        8 +  // discriminator
+        32 + // creator pubkey
+        1 + // bump
+        8 + // id
+        8 + // creator_index
+        1 + // verification_tier
+        4 + project_name.len() + // project_name string
+        4 + description.len() + // description string
+        4 + content_uri.len() + // content_uri string
+        32 + // content_hash
+        8 + // funding_goal_lamports
+        8 + // raised_lamports
+        4 + // supporters_count
+        4 + // updates_count
+        1 + // is_active
+        1 + // is_funded
+        1 + // is_delivered
+        1 + // outcome_recorded
+        1 + // is_frozen
+        1 + 1 + // takedown_reason option
+        8 + // created_at
+        8 + // end_timestamp
+        8 + // claim_deadline
+        8 + // dispute_deadline
+        4 + // open_disputes
+        1 + 32 + // access enum with the largest variant's allowlist root
+        4 + stretch_goals_space + // stretch_goals vec
+        4 + nft_name.len() + // nft_name string
+        4 + nft_symbol.len() + // nft_symbol string
+        4 + nft_uri.len() + // nft_uri string
+        32 + // nft_mint pubkey
+        1 + // nft_in_escrow
+        1 + // reward_mode
+        32 + // collection_mint pubkey
+        32 + // share_mint pubkey
+        32 + // pledge_mint pubkey
+        4 +  // pledges_pending
+        4 +  // pledges_failed
+        8 +  // max_editions
+        8    // editions_minted
+    }
+
+    // Space for the campaign's current strings and stretch goals
+    fn current_space(&self) -> usize {
+        Campaign::space(
+            &self.project_name,
+            &self.description,
+            &self.content_uri,
+            &self.nft_name,
+            &self.nft_symbol,
+            &self.nft_uri,
+            self.stretch_goals.iter().map(|goal| StretchGoal::space(&goal.reward_uri)).sum(),
+        )
+    }
+
+    fn is_pledge_campaign(&self) -> bool {
+        self.pledge_mint != Pubkey::default()
+    }
+
+    fn stretch_goals_reached(&self) -> u8 {
+        self.stretch_goals.iter().filter(|goal| goal.reached_at != 0).count() as u8
+    }
+
+    // Reward URI of a stretch tier, the number of stretch goals reached; None for tier 0.
+    // Goals are reached in order and reached goals never change, so the tier's
+    // goal is the last one reached at the time.
+    fn stretch_reward_uri(&self, tier: u8) -> Option<&String> {
+        let index = usize::from(tier).checked_sub(1)?;
+        self.stretch_goals.get(index).map(|goal| &goal.reward_uri)
+    }
+
+    // Whether a mint is one of this campaign's reward NFTs: the escrowed NFT, or
+    // an edition printed from it and recorded on one of its supporter funding
+    // accounts. Soulbound and frozen badges have no Metaplex metadata, so they
+    // are never reward NFTs here.
+    fn is_reward_mint(&self, mint: &Pubkey, supporter_funding: Option<&SupporterFunding>) -> bool {
+        *mint == self.nft_mint
+            || (self.reward_mode == RewardMode::Transferable
+                && supporter_funding.is_some_and(|funding| {
+                    funding.nft_minted && funding.edition_mint == *mint
+                }))
+    }
+
+    // The campaign's outcome so far, or None while it is still running.
+    // Cancelled and taken down campaigns have failed right away, even if funded.
+    fn outcome(&self, current_time: i64) -> Option<CampaignOutcome> {
+        if self.takedown_reason.is_some() || !self.is_active {
+            Some(CampaignOutcome::Failed)
+        } else if self.is_delivered {
+            Some(CampaignOutcome::Delivered)
+        } else if self.is_funded {
+            Some(CampaignOutcome::Funded)
+        } else if current_time > self.end_timestamp {
+            Some(CampaignOutcome::Failed)
+        } else {
+            None
+        }
+    }
+}
+
+impl SupporterFunding {
+    fn space() -> usize {
+        8 +  // discriminator
+        32 + // campaign pubkey
+        32 + // supporter pubkey
+        8 +  // amount_lamports
+        8 +  // funded_at
+        1 +  // is_claimed
+        1 +  // nft_minted
+        8 +  // edition_number
+        32 + // edition_mint pubkey
+        1 +  // reward_tier
+        8 +  // shares_minted
+        32 + // pledge_source pubkey
+        1 + 1 // pledge_status option
+    }
+
+    // SOL contributions are paid up front; pledges once collected
+    fn is_paid(&self) -> bool {
+        matches!(self.pledge_status, None | Some(PledgeStatus::Collected))
+    }
+}
+
+impl OutcomeMetadata {
+    fn space(funded_uri: &str, delivered_uri: &str, failed_uri: &str) -> usize {
+        8 +  // discriminator
+        32 + // campaign pubkey
+        4 + funded_uri.len() +    // funded_uri string
+        4 + delivered_uri.len() + // delivered_uri string
+        4 + failed_uri.len()      // failed_uri string
+    }
+}
+
+impl CreatorProfile {
+    const SPACE: usize = 8 + // discriminator
+        32 + // creator pubkey
+        8 +  // campaign_count
+        1 +  // bump
+        4 + MAX_DISPLAY_NAME_LEN + // display_name string
+        4 + MAX_AVATAR_URI_LEN +   // avatar_uri string
+        32 + // socials_hash
+        8 +  // campaigns_succeeded
+        8 +  // campaigns_failed
+        8 +  // campaigns_cancelled
+        8 +  // campaigns_taken_down
+        8 +  // total_raised_lamports
+        8;   // total_refunded_lamports
+}
+
+impl SupporterProfile {
+    const SPACE: usize = 8 + // discriminator
+        32 + // supporter pubkey
+        1 +  // bump
+        8 +  // total_contributed_lamports
+        8 +  // campaigns_backed
+        8 +  // refunds_received
+        8 +  // total_refunded_lamports
+        8;   // nfts_claimed
+}
+
+impl PlatformConfig {
+    const SPACE: usize = 8 + // discriminator
+        1 +  // paused
+        32 + // moderator pubkey
+        32 + // arbitrator pubkey
+        1 + 8 + // dispute_window_seconds option
+        32 + // attester pubkey
+        1 + 8 + // verification_threshold_lamports option
+        1 + 8 + // withdrawal_cooling_off_seconds option
+        1;   // bump
+
+    fn dispute_window(&self) -> i64 {
+        self.dispute_window_seconds.unwrap_or(DEFAULT_DISPUTE_WINDOW_SECONDS)
+    }
+}
+
+impl CreatorVerification {
+    const SPACE: usize = 8 + // discriminator
+        32 + // creator pubkey
+        32 + // attester pubkey
+        1 +  // tier
+        8 +  // issued_at
+        8 +  // expires_at
+        1;   // bump
+}
+
+impl Invitation {
+    const SPACE: usize = 8 + // discriminator
+        32 + // campaign pubkey
+        32 + // supporter pubkey
+        8 +  // invited_at
+        1;   // bump
+}
+
+impl Dispute {
+    const SPACE: usize = 8 + // discriminator
+        32 + // campaign pubkey
+        32 + // supporter pubkey
+        32 + // evidence_hash
+        8 +  // filed_at
+        1 +  // status
+        8 +  // resolved_at
+        1;   // bump
+}
+
+impl StretchGoal {
+    fn space(reward_uri: &str) -> usize {
+        8 + // threshold_lamports
+        4 + reward_uri.len() + // reward_uri string
+        8   // reached_at
+    }
+}
+
+impl CampaignRegistry {
+    const SPACE: usize = 8 + // discriminator
+        8 + // campaign_count
+        1;  // bump
+
+    // Page the next campaign will be appended to
+    fn next_page_index(&self) -> u64 {
+        self.campaign_count / CAMPAIGNS_PER_PAGE
+    }
+}
+
+impl CampaignRegistryPage {
+    // Pages are allocated at full size when created
+    const SPACE: usize = 8 + // discriminator
+        8 + // page_index
+        4 + 32 * CAMPAIGNS_PER_PAGE as usize; // campaigns vector
+}
+
+impl CampaignUpdate {
+    fn space(content_uri: &str) -> usize {
+        8 +  // discriminator
+        32 + // campaign pubkey
+        4 +  // sequence
+        8 +  // posted_at
+        4 + content_uri.len() + // content_uri string
+        32 + // content_hash
+        1 +  // is_milestone
+        1    // bump
+    }
+}
+
+// Resize a campaign account, moving only the rent difference between its old
+// and new size: the creator pays it in when the account grows and gets it back
+// when it shrinks. Anything else the campaign holds stays put.
+fn resize_campaign<'info>(
+    campaign: &AccountInfo<'info>,
+    creator: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let owed = settle_resize_rent(&Rent::get()?, campaign, creator, new_len)?;
+    if owed > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: creator.clone(),
+                    to: campaign.clone(),
+                },
+            ),
+            owed,
+        )?;
+    }
+    campaign.realloc(new_len, false)?;
+    Ok(())
+}
+
+// Refund the rent freed by shrinking to `new_len` straight to the creator, or
+// return the extra rent growing to it needs
+fn settle_resize_rent(rent: &Rent, campaign: &AccountInfo, creator: &AccountInfo, new_len: usize) -> Result<u64> {
+    let old_rent = rent.minimum_balance(campaign.data_len());
+    let new_rent = rent.minimum_balance(new_len);
+    if new_rent >= old_rent {
+        return Ok(new_rent - old_rent);
+    }
+
+    let refund = old_rent - new_rent;
+    let campaign_lamports = campaign.lamports()
+        .checked_sub(refund)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    let creator_lamports = creator.lamports()
+        .checked_add(refund)
+        .ok_or(ErrorCode::AmountOverflow)?;
+    **campaign.try_borrow_mut_lamports()? = campaign_lamports;
+    **creator.try_borrow_mut_lamports()? = creator_lamports;
+    Ok(0)
+}
+
+// Reject an empty or oversize string argument with field-specific errors
+fn validate_string(value: &str, max_len: usize, empty_error: ErrorCode, too_long_error: ErrorCode) -> Result<()> {
+    if value.is_empty() {
+        return Err(empty_error.into());
+    }
+    if value.len() > max_len {
+        return Err(too_long_error.into());
+    }
+    Ok(())
+}
+
+// The inline description is an optional summary, so only its length is checked
+fn validate_description(description: &str) -> Result<()> {
+    if description.len() > MAX_DESCRIPTION_LEN {
+        return Err(ErrorCode::DescriptionTooLong.into());
+    }
+    Ok(())
+}
+
+fn validate_content(content: &CampaignContent) -> Result<()> {
+    validate_uri(&content.uri)?;
+    if content.hash == [0; 32] {
+        return Err(ErrorCode::MissingContentHash.into());
+    }
+    Ok(())
+}
+
+// Private campaigns only take contributions from allowlisted or invited wallets
+fn check_access(campaign: &Campaign, supporter: &Pubkey, allowlist_proof: &[[u8; 32]], invited: bool) -> Result<()> {
+    match campaign.access {
+        CampaignAccess::Public => {}
+        CampaignAccess::Allowlist { root } => {
+            if !verify_allowlist_proof(&root, supporter, allowlist_proof) {
+                return Err(ErrorCode::NotOnAllowlist.into());
+            }
+        }
+        CampaignAccess::InviteOnly => {
+            if !invited {
+                return Err(ErrorCode::NotInvited.into());
+            }
+        }
+    }
+    Ok(())
 }
 
-// Context for treasury withdrawals
-#[derive(Accounts)]
-pub struct WithdrawTreasury<'info> {
-    // Admin account must be a signer
-    #[account(mut)]
-    pub admin: Signer<'info>,
-    
-    /// CHECK: This is the treasury PDA
-    #[account(
-        mut,
-        seeds = [b"treasury"],
-        bump
-    )]
-    pub treasury: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
+fn validate_access(access: &CampaignAccess) -> Result<()> {
+    if *access == (CampaignAccess::Allowlist { root: [0; 32] }) {
+        return Err(ErrorCode::MissingAllowlistRoot.into());
+    }
+    Ok(())
 }
 
-// Context for minting edition NFTs to supporters
-#[derive(Accounts)]
-pub struct TransferNftToEscrow<'info> {
-    // Campaign account
-    #[account(mut)]
-    pub campaign: Account<'info, Campaign>,
-    
-    // Campaign creator as signer
-    #[account(
-        mut,
-        constraint = creator.key() == campaign.creator @ ErrorCode::Unauthorized
-    )]
-    pub creator: Signer<'info>,
-    
-    // The NFT mint account
-    #[account(
-        constraint = nft_mint.key() == campaign.nft_mint @ ErrorCode::InvalidNftMint
-    )]
-    pub nft_mint: Account<'info, Mint>,
-    
-    // Creator's token account holding the NFT
-    #[account(
-        mut,
-        constraint = creator_token_account.mint == nft_mint.key() @ ErrorCode::InvalidTokenAccount,
-        constraint = creator_token_account.owner == creator.key() @ ErrorCode::Unauthorized
-    )]
-    pub creator_token_account: Account<'info, TokenAccount>,
-    
-    // PDA that acts as the escrow authority
-    #[account(
-        seeds = [b"escrow", campaign.key().as_ref()],
-        bump
-    )]
-    /// CHECK: This is a PDA used as the escrow authority
-    pub escrow_authority: AccountInfo<'info>,
-    
-    // Token account owned by the escrow PDA to hold the NFT
-    #[account(
-        init,
-        payer = creator,
-        associated_token::mint = nft_mint,
-        associated_token::authority = escrow_authority
-    )]
-    pub escrow_token_account: Account<'info, TokenAccount>,
-    
-    // Required programs
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+// Allowlist leaves are SHA-256(wallet); each proof step hashes the sorted pair
+// of nodes, so proofs don't need to record left/right positions. Public so the
+// client's allowlist builder can be checked against it.
+pub fn verify_allowlist_proof(root: &[u8; 32], supporter: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let leaf = hashv(&[supporter.as_ref()]).to_bytes();
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == *root
 }
 
-#[derive(Accounts)]
-pub struct ClaimNftFromEscrow<'info> {
-    // Campaign account
-    #[account(mut)]
-    pub campaign: Account<'info, Campaign>,
-    
-    // The supporter funding record to update
-    #[account(
-        mut,
-        seeds = [
-            b"supporter-funding",
-            campaign.key().as_ref(),
-            supporter.key().as_ref(),
-        ],
-        bump,
-        constraint = supporter_funding.campaign == campaign.key() @ ErrorCode::InvalidCampaign,
-        constraint = supporter_funding.supporter == supporter.key() @ ErrorCode::Unauthorized
-    )]
-    pub supporter_funding: Account<'info, SupporterFunding>,
-    
-    // Supporter as signer
-    #[account(mut)]
-    pub supporter: Signer<'info>,
-    
-    // The NFT mint account
-    #[account(
-        constraint = nft_mint.key() == campaign.nft_mint @ ErrorCode::InvalidNftMint
-    )]
-    pub nft_mint: Account<'info, Mint>,
-    
-    // PDA that acts as the escrow authority
-    #[account(
-        seeds = [b"escrow", campaign.key().as_ref()],
-        bump
-    )]
-    /// CHECK: This is a PDA used as the escrow authority
-    pub escrow_authority: AccountInfo<'info>,
-    
-    // Token account owned by the escrow PDA holding the NFT
-    #[account(
-        mut,
-        constraint = escrow_token_account.mint == nft_mint.key() @ ErrorCode::InvalidTokenAccount,
-        constraint = escrow_token_account.owner == escrow_authority.key() @ ErrorCode::InvalidTokenAccount
-    )]
-    pub escrow_token_account: Account<'info, TokenAccount>,
-    
-    // Supporter's token account to receive the NFT
-    #[account(
-        init,
-        payer = supporter,
-        associated_token::mint = nft_mint,
-        associated_token::authority = supporter
-    )]
-    pub supporter_token_account: Account<'info, TokenAccount>,
-    
-    // Required programs
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+// Mark the stretch goals the raised total now covers and announce each one
+fn reach_stretch_goals(campaign: &mut Account<Campaign>, current_time: i64) {
+    let campaign_key = campaign.key();
+    let raised_lamports = campaign.raised_lamports;
+    for (index, goal) in campaign.stretch_goals.iter_mut().enumerate() {
+        if goal.reached_at == 0 && raised_lamports >= goal.threshold_lamports {
+            goal.reached_at = current_time;
+            emit!(StretchGoalReached {
+                campaign: campaign_key,
+                index: index as u8,
+                threshold_lamports: goal.threshold_lamports,
+                reward_uri: goal.reward_uri.clone(),
+                raised_lamports,
+            });
+        }
+    }
+}
+
+// Metadata URIs must be non-empty, fit Metaplex's limit and start with a scheme
+fn validate_uri(uri: &str) -> Result<()> {
+    validate_string(uri, MAX_URI_LEN, ErrorCode::EmptyUri, ErrorCode::UriTooLong)?;
+    if !has_uri_scheme(uri) {
+        return Err(ErrorCode::InvalidUriScheme.into());
+    }
+    Ok(())
+}
+
+// RFC 3986 scheme (`https:`, `ipfs:`, `ar:` ...): a letter, then letters,
+// digits, `+`, `-` or `.`, then a colon followed by the rest of the URI
+fn has_uri_scheme(uri: &str) -> bool {
+    let Some((scheme, rest)) = uri.split_once(':') else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|first| first.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && !rest.is_empty()
+}
+
+// Events emitted by every state-changing instruction, for indexers
+#[event]
+pub struct CampaignCreated {
+    pub campaign: Pubkey,
+    pub id: u64,
+    pub creator: Pubkey,
+    pub project_name: String,
+    pub content_uri: String,
+    pub content_hash: [u8; 32],
+    pub funding_goal_lamports: u64,
+    pub end_timestamp: i64,
+    pub reward_mode: RewardMode,
+    pub share_mint: Pubkey,
+    pub pledge_mint: Pubkey,
+    pub access: CampaignAccess,
+}
+
+#[event]
+pub struct Contributed {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub amount_lamports: u64,
+    pub raised_lamports: u64,
+    pub supporters_count: u32,
+}
+
+#[event]
+pub struct GoalReached {
+    pub campaign: Pubkey,
+    pub funding_goal_lamports: u64,
+    pub raised_lamports: u64,
+}
+
+#[event]
+pub struct FundsWithdrawn {
+    pub campaign: Pubkey,
+    pub creator: Pubkey,
+    pub creator_amount: u64,
+    pub platform_fee: u64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub amount_lamports: u64,
+    pub shares_burned: u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub admin: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TreasuryTokensWithdrawn {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct NftEscrowed {
+    pub campaign: Pubkey,
+    pub nft_mint: Pubkey,
+    pub escrow_token_account: Pubkey,
+}
+
+#[event]
+pub struct NftClaimed {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub nft_mint: Pubkey,
+    pub edition_number: u64,
+    pub reward_tier: u8, // Stretch goals reached at claim time, kept by the reward
+}
+
+#[event]
+pub struct NftReclaimed {
+    pub campaign: Pubkey,
+    pub creator: Pubkey,
+    pub nft_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct BadgeMintInitialized {
+    pub campaign: Pubkey,
+    pub badge_mint: Pubkey,
+    pub reward_mode: RewardMode,
+}
+
+#[event]
+pub struct BadgeClaimed {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub badge_mint: Pubkey,
+    pub reward_mode: RewardMode,
+    pub reward_tier: u8, // Stretch goals reached at claim time, kept by the reward
+}
+
+#[event]
+pub struct PlatformCollectionCreated {
+    pub collection_mint: Pubkey,
+}
+
+#[event]
+pub struct CampaignCollectionSet {
+    pub campaign: Pubkey,
+    pub collection_mint: Pubkey,
+    pub created: bool, // Created by the program rather than referenced
+}
+
+#[event]
+pub struct RewardVerified {
+    pub campaign: Pubkey,
+    pub reward_mint: Pubkey,
+    pub collection_mint: Pubkey,
+}
+
+#[event]
+pub struct CampaignCollectionVerified {
+    pub campaign: Pubkey,
+    pub collection_mint: Pubkey,
+    pub platform_collection_mint: Pubkey,
+}
+
+#[event]
+pub struct CampaignDelivered {
+    pub campaign: Pubkey,
+}
+
+#[event]
+pub struct OutcomeMetadataSet {
+    pub campaign: Pubkey,
+    pub funded_uri: String,
+    pub delivered_uri: String,
+    pub failed_uri: String,
+}
+
+#[event]
+pub struct RewardMetadataUpdated {
+    pub campaign: Pubkey,
+    pub reward_mint: Pubkey,
+    pub outcome: CampaignOutcome,
+    pub uri: String,
+    pub raised_lamports: u64,
+}
+
+#[event]
+pub struct SharesClaimed {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub share_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CampaignMetadataUpdated {
+    pub campaign: Pubkey,
+    pub project_name: String,
+    pub description: String,
+    pub content_uri: String,
+    pub content_hash: [u8; 32],
+    pub nft_uri: String,
+}
+
+#[event]
+pub struct CreatorProfileUpdated {
+    pub creator: Pubkey,
+    pub display_name: String,
+    pub avatar_uri: String,
+    pub socials_hash: [u8; 32],
+}
+
+#[event]
+pub struct CampaignCancelled {
+    pub campaign: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct CampaignFailed {
+    pub campaign: Pubkey,
+    pub creator: Pubkey,
+    pub raised_lamports: u64,
+}
+
+#[event]
+pub struct UpdatePosted {
+    pub campaign: Pubkey,
+    pub update: Pubkey,
+    pub sequence: u32,
+    pub posted_at: i64,
+    pub content_uri: String,
+    pub content_hash: [u8; 32],
+    pub is_milestone: bool,
+}
+
+#[event]
+pub struct PlatformPauseSet {
+    pub admin: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct CampaignFreezeSet {
+    pub campaign: Pubkey,
+    pub admin: Pubkey,
+    pub frozen: bool,
+}
+
+#[event]
+pub struct ModeratorSet {
+    pub admin: Pubkey,
+    pub moderator: Pubkey,
+}
+
+#[event]
+pub struct CampaignTakenDown {
+    pub campaign: Pubkey,
+    pub moderator: Pubkey,
+    pub reason: TakedownReason,
+}
+
+#[event]
+pub struct DisputesConfigured {
+    pub admin: Pubkey,
+    pub arbitrator: Pubkey,
+    pub dispute_window_seconds: i64,
+}
+
+#[event]
+pub struct DisputeFiled {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub dispute: Pubkey,
+    pub evidence_hash: [u8; 32],
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub dispute: Pubkey,
+    pub resolution: DisputeResolution,
+    pub refunded_lamports: u64,
 }
 
-#[derive(Accounts)]
-pub struct MintEditionNft<'info> {
-    // Campaign must be fully funded
-    #[account(mut)]
-    pub campaign: Account<'info, Campaign>,
-    
-    // The supporter funding record to update
-    #[account(
-        mut,
-        seeds = [
-            b"supporter-funding",
-            campaign.key().as_ref(),
-            supporter_funding.supporter.as_ref(),
-        ],
-        bump,
-        constraint = supporter_funding.campaign == campaign.key() @ ErrorCode::InvalidCampaign
-    )]
-    pub supporter_funding: Account<'info, SupporterFunding>,
-    
-    /// CHECK: The creator account (not required to sign)
-    #[account(constraint = creator.key() == campaign.creator @ ErrorCode::InvalidCampaign)]
-    pub creator: AccountInfo<'info>,
-    
-    // Signer for the transaction (must be the supporter who funded the campaign)
-    #[account(constraint = authority.key() == supporter_funding.supporter @ ErrorCode::Unauthorized)]
-    pub authority: Signer<'info>,
-    
-    /// CHECK: The master edition NFT mint, verified by constraint to match the campaign's NFT mint
-    #[account(constraint = master_edition_mint.key() == campaign.nft_mint @ ErrorCode::InvalidCampaign)]
-    pub master_edition_mint: AccountInfo<'info>,
-    
-    /// CHECK: The new edition mint account that will be created
-    #[account(mut)]
-    pub edition_mint: AccountInfo<'info>,
-    
-    /// CHECK: Mint authority for the edition mint
-    pub mint_authority: Signer<'info>,
-    
-    /// CHECK: Recipient's token account for the edition NFT
-    #[account(mut)]
-    pub recipient_token_account: AccountInfo<'info>,
-    
-    // Required programs
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct VerificationConfigured {
+    pub admin: Pubkey,
+    pub attester: Pubkey,
+    pub threshold_lamports: Option<u64>,
 }
 
-#[account]
-pub struct Campaign {
+#[event]
+pub struct CreatorVerified {
     pub creator: Pubkey,
-    pub project_name: String,
-    pub description: String,
-    pub funding_goal_lamports: u64,
+    pub attester: Pubkey,
+    pub tier: u8,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct VerificationRevoked {
+    pub creator: Pubkey,
+    pub attester: Pubkey,
+}
+
+#[event]
+pub struct CampaignAccessSet {
+    pub campaign: Pubkey,
+    pub access: CampaignAccess,
+}
+
+#[event]
+pub struct SupporterInvited {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+}
+
+#[event]
+pub struct InvitationRevoked {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+}
+
+#[event]
+pub struct Pledged {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub amount: u64,
     pub raised_lamports: u64,
     pub supporters_count: u32,
-    pub is_active: bool,
-    pub is_funded: bool,
-    pub created_at: i64,
-    pub end_timestamp: i64,
-    
-    // NFT details
-    pub nft_name: String,
-    pub nft_symbol: String,
-    pub nft_uri: String,
-    pub nft_mint: Pubkey,
-    pub nft_in_escrow: bool, // Flag to indicate whether NFT has been transferred to escrow
-    
-    // Edition NFT tracking
-    pub max_editions: u64,        // Maximum number of editions that can be minted (5 for hackathon demo)
-    pub editions_minted: u64,     // Number of editions already minted
 }
 
-#[account]
-pub struct SupporterFunding {
+#[event]
+pub struct PledgeCollected {
     pub campaign: Pubkey,
     pub supporter: Pubkey,
-    pub amount_lamports: u64,
-    pub funded_at: i64,
-    pub is_claimed: bool,
-    pub nft_minted: bool,         // Whether an NFT has been minted for this supporter
-    pub edition_number: u64,      // Which edition number was minted for this supporter
-    pub edition_mint: Pubkey,     // Mint address of the edition NFT (zeroed if not minted)
+    pub amount: u64, // Moved into the pledge escrow
 }
 
-impl Campaign {
-    fn space(
-        project_name: &str,
-        description: &str,
-        nft_name: &str,
-        nft_symbol: &str,
-        nft_uri: &str,
-    ) -> usize {
-        8 +  // discriminator
-        32 + // creator pubkey
-        4 + project_name.len() + // project_name string
-        4 + description.len() + // description string
-        8 + // funding_goal_lamports
-        8 + // raised_lamports
-        4 + // supporters_count
-        1 + // is_active
-        1 + // is_funded
-        8 + // created_at
-        8 + // end_timestamp
-        4 + nft_name.len() + // nft_name string
-        4 + nft_symbol.len() + // nft_symbol string
-        4 + nft_uri.len() + // nft_uri string
-        32 + // nft_mint pubkey
-        1 + // nft_in_escrow
-        8 +  // max_editions
-        8    // editions_minted
-    }
+#[event]
+pub struct PledgeFailed {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub amount: u64,
 }
 
-impl SupporterFunding {
-    fn space() -> usize {
-        8 +  // discriminator
-        32 + // campaign pubkey
-        32 + // supporter pubkey
-        8 +  // amount_lamports
-        8 +  // funded_at
-        1 +  // is_claimed
-        1 +  // nft_minted
-        8 +  // edition_number
-        32   // edition_mint pubkey
-    }
+#[event]
+pub struct WithdrawalsConfigured {
+    pub admin: Pubkey,
+    pub cooling_off_seconds: Option<i64>,
+}
+
+#[event]
+pub struct StretchGoalsSet {
+    pub campaign: Pubkey,
+    pub stretch_goals: Vec<StretchGoal>,
+}
+
+#[event]
+pub struct StretchGoalReached {
+    pub campaign: Pubkey,
+    pub index: u8, // Position in Campaign::stretch_goals
+    pub threshold_lamports: u64,
+    pub reward_uri: String,
+    pub raised_lamports: u64,
+}
+
+#[event]
+pub struct ContributionWithdrawn {
+    pub campaign: Pubkey,
+    pub supporter: Pubkey,
+    pub amount: u64, // Lamports, or pledge mint base units for pledges
+    pub raised_lamports: u64,
+    pub supporters_count: u32,
 }
 
 #[error_code]
@@ -889,4 +5492,408 @@ pub enum ErrorCode {
     
     #[msg("Insufficient funds for the operation")]
     InsufficientFunds,
+    
+    #[msg("Instruction not available for this campaign's reward mode")]
+    InvalidRewardMode,
+    
+    #[msg("Token program does not match the campaign's reward mode")]
+    InvalidTokenProgram,
+    
+    #[msg("NFT claim window has closed")]
+    ClaimWindowClosed,
+    
+    #[msg("NFT claim window is still open")]
+    ClaimWindowOpen,
+    
+    #[msg("Campaign collection has already been set")]
+    CollectionAlreadySet,
+    
+    #[msg("Collection does not match the campaign collection")]
+    InvalidCollection,
+    
+    #[msg("Campaign has already been marked as delivered")]
+    CampaignAlreadyDelivered,
+    
+    #[msg("Share mint does not match the campaign share mint")]
+    InvalidShareMint,
+    
+    #[msg("Share tokens already minted for this supporter")]
+    SharesAlreadyMinted,
+    
+    #[msg("Share token accounts are required to burn shares on refund")]
+    ShareAccountsRequired,
+    
+    #[msg("Display name is too long")]
+    DisplayNameTooLong,
+    
+    #[msg("Avatar URI is too long")]
+    AvatarUriTooLong,
+    
+    #[msg("Campaign outcome has already been recorded")]
+    OutcomeAlreadyRecorded,
+    
+    #[msg("Project name cannot be empty")]
+    EmptyProjectName,
+    
+    #[msg("Project name is too long")]
+    ProjectNameTooLong,
+    
+    #[msg("Description summary is longer than 280 bytes")]
+    DescriptionTooLong,
+    
+    #[msg("NFT name cannot be empty")]
+    EmptyNftName,
+    
+    #[msg("NFT name is longer than 32 bytes")]
+    NftNameTooLong,
+    
+    #[msg("NFT symbol cannot be empty")]
+    EmptyNftSymbol,
+    
+    #[msg("NFT symbol is longer than 10 bytes")]
+    NftSymbolTooLong,
+    
+    #[msg("URI cannot be empty")]
+    EmptyUri,
+    
+    #[msg("URI is longer than 200 bytes")]
+    UriTooLong,
+    
+    #[msg("URI must start with a scheme such as https:// or ipfs://")]
+    InvalidUriScheme,
+    
+    #[msg("No campaign fields to update")]
+    NothingToUpdate,
+    
+    #[msg("Campaign name, content and reward URI cannot change after the first contribution")]
+    ContributionsReceived,
+    
+    #[msg("Campaign content hash is missing")]
+    MissingContentHash,
+    
+    #[msg("The platform is paused")]
+    PlatformPaused,
+    
+    #[msg("Campaign is frozen by the platform admin")]
+    CampaignFrozen,
+    
+    #[msg("Campaign has been taken down by a moderator")]
+    CampaignTakenDown,
+    
+    #[msg("Dispute window must be between 0 and 30 days")]
+    InvalidDisputeWindow,
+    
+    #[msg("Funds can't be withdrawn until the dispute window closes")]
+    DisputeWindowOpen,
+    
+    #[msg("The dispute window for this campaign has closed")]
+    DisputeWindowClosed,
+    
+    #[msg("Funds can't be withdrawn while disputes are open")]
+    DisputesOpen,
+    
+    #[msg("Dispute evidence hash is missing")]
+    MissingEvidenceHash,
+    
+    #[msg("Dispute has already been resolved")]
+    DisputeAlreadyResolved,
+    
+    #[msg("Campaigns with this funding goal require a verified creator")]
+    CreatorNotVerified,
+    
+    #[msg("Creator verification has expired")]
+    VerificationExpired,
+    
+    #[msg("Verification tier must be at least 1")]
+    InvalidVerificationTier,
+    
+    #[msg("Verification expiry must be in the future")]
+    InvalidVerificationExpiry,
+    
+    #[msg("Allowlist root is missing")]
+    MissingAllowlistRoot,
+    
+    #[msg("Supporter is not on the campaign allowlist")]
+    NotOnAllowlist,
+    
+    #[msg("Supporter has not been invited to this campaign")]
+    NotInvited,
+    
+    #[msg("This campaign takes token pledges, not SOL")]
+    PledgeCampaign,
+    
+    #[msg("This campaign doesn't take token pledges")]
+    NotPledgeCampaign,
+    
+    #[msg("Token pledge campaigns can't issue share tokens")]
+    SharesNotSupportedForPledges,
+    
+    #[msg("Pledges must be passed as supporter funding and token account pairs")]
+    InvalidPledgeAccounts,
+    
+    #[msg("Token account is not the pledge's source")]
+    InvalidPledgeSource,
+    
+    #[msg("Pledges are only charged when a campaign succeeds, so there is nothing to refund")]
+    PledgeNotRefundable,
+    
+    #[msg("The pledge has not been collected")]
+    PledgeNotCollected,
+    
+    #[msg("Cooling-off period can't be negative")]
+    InvalidCoolingOffPeriod,
+    
+    #[msg("The cooling-off period for withdrawing this contribution is over")]
+    CoolingOffPeriodOver,
+    
+    #[msg("Contributions with claimed rewards or shares can't be withdrawn")]
+    ContributionRewardsClaimed,
+    
+    #[msg("Disputed contributions can't be withdrawn")]
+    ContributionDisputed,
+    
+    #[msg("A campaign can have at most 5 stretch goals")]
+    TooManyStretchGoals,
+    
+    #[msg("Stretch goals must be above the funding goal and the amount raised, in increasing order")]
+    InvalidStretchGoalThreshold,
+    
+    #[msg("Stretch goals already reached can't be changed or removed")]
+    StretchGoalLocked,
+    
+    #[msg("Edition was not printed from the campaign NFT")]
+    InvalidEdition,
+    
+    #[msg("Campaign funds have already been released to the creator")]
+    FundsAlreadyWithdrawn,
+    
+    #[msg("Share tokens can be claimed once the dispute window closes with no disputes open")]
+    SharesLocked,
+    
+    #[msg("Campaign funds have not been released to the creator yet")]
+    FundsNotWithdrawn,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Running campaign ending at t=100 that hasn't reached its goal
+    fn campaign() -> Campaign {
+        Campaign {
+            creator: Pubkey::new_unique(),
+            bump: 255,
+            id: 0,
+            creator_index: 0,
+            verification_tier: 0,
+            project_name: "Project".to_string(),
+            description: String::new(),
+            content_uri: String::new(),
+            content_hash: [0; 32],
+            funding_goal_lamports: 1_000,
+            raised_lamports: 0,
+            supporters_count: 0,
+            updates_count: 0,
+            is_active: true,
+            is_funded: false,
+            is_delivered: false,
+            outcome_recorded: false,
+            is_frozen: false,
+            takedown_reason: None,
+            created_at: 0,
+            end_timestamp: 100,
+            claim_deadline: 200,
+            dispute_deadline: 150,
+            open_disputes: 0,
+            access: CampaignAccess::Public,
+            stretch_goals: Vec::new(),
+            nft_name: String::new(),
+            nft_symbol: String::new(),
+            nft_uri: String::new(),
+            nft_mint: Pubkey::default(),
+            nft_in_escrow: false,
+            reward_mode: RewardMode::Transferable,
+            collection_mint: Pubkey::default(),
+            share_mint: Pubkey::default(),
+            pledge_mint: Pubkey::default(),
+            pledges_pending: 0,
+            pledges_failed: 0,
+            max_editions: 5,
+            editions_minted: 0,
+        }
+    }
+
+    #[test]
+    fn outcome_is_none_while_running() {
+        assert_eq!(campaign().outcome(50), None);
+        assert_eq!(campaign().outcome(100), None);
+    }
+
+    #[test]
+    fn outcome_fails_after_end_below_goal() {
+        assert_eq!(campaign().outcome(101), Some(CampaignOutcome::Failed));
+    }
+
+    #[test]
+    fn outcome_is_funded_once_goal_reached() {
+        let mut campaign = campaign();
+        campaign.is_funded = true;
+        assert_eq!(campaign.outcome(50), Some(CampaignOutcome::Funded));
+        assert_eq!(campaign.outcome(101), Some(CampaignOutcome::Funded));
+    }
+
+    #[test]
+    fn outcome_is_delivered_once_marked() {
+        let mut campaign = campaign();
+        campaign.is_funded = true;
+        campaign.is_delivered = true;
+        assert_eq!(campaign.outcome(101), Some(CampaignOutcome::Delivered));
+    }
+
+    #[test]
+    fn outcome_fails_when_cancelled_before_end() {
+        let mut campaign = campaign();
+        campaign.is_active = false;
+        assert_eq!(campaign.outcome(50), Some(CampaignOutcome::Failed));
+    }
+
+    #[test]
+    fn outcome_fails_when_taken_down() {
+        let mut campaign = campaign();
+        campaign.is_funded = true;
+        campaign.is_active = false;
+        campaign.takedown_reason = Some(TakedownReason::Fraud);
+        assert_eq!(campaign.outcome(50), Some(CampaignOutcome::Failed));
+        campaign.is_delivered = true;
+        assert_eq!(campaign.outcome(101), Some(CampaignOutcome::Failed));
+    }
+
+    fn string_of(len: usize) -> String {
+        "a".repeat(len)
+    }
+
+    // An https URI exactly `len` bytes long
+    fn uri_of(len: usize) -> String {
+        let prefix = "https://example.com/";
+        format!("{}{}", prefix, "a".repeat(len - prefix.len()))
+    }
+
+    #[test]
+    fn string_limits_are_inclusive() {
+        for (max_len, empty_error, too_long_error) in [
+            (MAX_PROJECT_NAME_LEN, ErrorCode::EmptyProjectName, ErrorCode::ProjectNameTooLong),
+            (MAX_NFT_NAME_LEN, ErrorCode::EmptyNftName, ErrorCode::NftNameTooLong),
+            (MAX_NFT_SYMBOL_LEN, ErrorCode::EmptyNftSymbol, ErrorCode::NftSymbolTooLong),
+        ] {
+            assert!(validate_string(&string_of(1), max_len, empty_error, too_long_error).is_ok());
+            assert!(validate_string(&string_of(max_len), max_len, empty_error, too_long_error).is_ok());
+            assert_eq!(
+                validate_string(&string_of(max_len + 1), max_len, empty_error, too_long_error),
+                Err(too_long_error.into())
+            );
+            assert_eq!(validate_string("", max_len, empty_error, too_long_error), Err(empty_error.into()));
+        }
+    }
+
+    #[test]
+    fn description_may_be_empty_up_to_its_limit() {
+        assert!(validate_description("").is_ok());
+        assert!(validate_description(&string_of(MAX_DESCRIPTION_LEN)).is_ok());
+        assert_eq!(
+            validate_description(&string_of(MAX_DESCRIPTION_LEN + 1)),
+            Err(ErrorCode::DescriptionTooLong.into())
+        );
+    }
+
+    #[test]
+    fn uri_length_limit_is_inclusive() {
+        assert!(validate_uri(&uri_of(MAX_URI_LEN)).is_ok());
+        assert_eq!(validate_uri(&uri_of(MAX_URI_LEN + 1)), Err(ErrorCode::UriTooLong.into()));
+        assert_eq!(validate_uri(""), Err(ErrorCode::EmptyUri.into()));
+    }
+
+    #[test]
+    fn uri_schemes() {
+        for uri in [
+            "https://example.com/a.json",
+            "ipfs://bafy",
+            "ar://tx",
+            "data:application/json,{}",
+            "git+ssh://host/repo",
+        ] {
+            assert!(has_uri_scheme(uri), "{}", uri);
+            assert!(validate_uri(uri).is_ok(), "{}", uri);
+        }
+        for uri in [
+            "example.com/a.json", // no scheme
+            "://example.com",     // empty scheme
+            "1ipfs://bafy",       // scheme starting with a digit
+            "ht tps://example",   // space in the scheme
+            "ipfs_v1://bafy",     // underscore in the scheme
+            "https:",             // nothing after the colon
+            "/ipfs/bafy:1",       // path before the colon
+        ] {
+            assert!(!has_uri_scheme(uri), "{}", uri);
+            assert_eq!(validate_uri(uri), Err(ErrorCode::InvalidUriScheme.into()), "{}", uri);
+        }
+    }
+
+    #[test]
+    fn shrinking_a_campaign_refunds_only_the_rent_difference() {
+        let rent = Rent::default();
+        let mut campaign = campaign();
+        campaign.description = string_of(MAX_DESCRIPTION_LEN);
+        let old_len = campaign.current_space();
+        campaign.description = string_of(10);
+        let new_len = campaign.current_space();
+
+        // Rent-exempt campaign holding 5 SOL of contributions
+        let contributions = 5_000_000_000;
+        let (campaign_key, creator_key, owner) = (Pubkey::new_unique(), Pubkey::new_unique(), crate::ID);
+        let mut campaign_lamports = rent.minimum_balance(old_len) + contributions;
+        let mut campaign_data = vec![0; old_len];
+        let campaign_info = AccountInfo::new(
+            &campaign_key, false, true, &mut campaign_lamports, &mut campaign_data, &owner, false, 0,
+        );
+        let mut creator_lamports = 1_000_000_000;
+        let mut creator_data = vec![];
+        let system_program = anchor_lang::system_program::ID;
+        let creator_info = AccountInfo::new(
+            &creator_key, true, true, &mut creator_lamports, &mut creator_data, &system_program, false, 0,
+        );
+
+        let owed = settle_resize_rent(&rent, &campaign_info, &creator_info, new_len).unwrap();
+        assert_eq!(owed, 0);
+        assert_eq!(campaign_info.lamports() - rent.minimum_balance(new_len), contributions);
+        assert_eq!(
+            creator_info.lamports(),
+            1_000_000_000 + rent.minimum_balance(old_len) - rent.minimum_balance(new_len)
+        );
+    }
+
+    #[test]
+    fn growing_a_campaign_charges_only_the_rent_difference() {
+        let rent = Rent::default();
+        let old_len = campaign().current_space();
+        let new_len = old_len + MAX_DESCRIPTION_LEN;
+
+        let (campaign_key, creator_key, owner) = (Pubkey::new_unique(), Pubkey::new_unique(), crate::ID);
+        let mut campaign_lamports = rent.minimum_balance(old_len) + 5_000_000_000;
+        let mut campaign_data = vec![0; old_len];
+        let campaign_info = AccountInfo::new(
+            &campaign_key, false, true, &mut campaign_lamports, &mut campaign_data, &owner, false, 0,
+        );
+        let mut creator_lamports = 1_000_000_000;
+        let mut creator_data = vec![];
+        let system_program = anchor_lang::system_program::ID;
+        let creator_info = AccountInfo::new(
+            &creator_key, true, true, &mut creator_lamports, &mut creator_data, &system_program, false, 0,
+        );
+
+        let owed = settle_resize_rent(&rent, &campaign_info, &creator_info, new_len).unwrap();
+        assert_eq!(owed, rent.minimum_balance(new_len) - rent.minimum_balance(old_len));
+        // The transfer itself is a system program CPI; nothing moved yet
+        assert_eq!(campaign_info.lamports(), rent.minimum_balance(old_len) + 5_000_000_000);
+        assert_eq!(creator_info.lamports(), 1_000_000_000);
+    }
 }
@@ -0,0 +1,1881 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Gkescrow } from "../target/types/gkescrow";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  revoke,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { createHash } from "crypto";
+import * as fs from "fs";
+
+// Admin public key - should match the one in lib.rs
+const ADMIN_PUBKEY = "ZaikXX6zRGseZdyGnpdBaTkBdetDNgZcGEqzeZgAXtM";
+
+// Metaplex Token Metadata program, cloned into the local validator (see Anchor.toml)
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// Platform fee constants - should match those in lib.rs
+const PLATFORM_FEE_NUMERATOR = 25;
+const PLATFORM_FEE_DENOMINATOR = 1000;
+
+// Dispute window for the test campaigns, configured by the admin before they are created
+const DISPUTE_WINDOW_SECONDS = 60;
+
+describe("gkescrow", () => {
+  // Force the test to connect to localnet
+  const url = "http://localhost:8899";
+  const connection = new anchor.web3.Connection(url, "confirmed");
+  
+  // Create a provider from connection
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: "confirmed",
+    preflightCommitment: "confirmed",
+  });
+  anchor.setProvider(provider);
+  
+  const program = anchor.workspace.gkescrow as Program<Gkescrow>;
+  
+  console.log("Test running on:", provider.connection.rpcEndpoint);
+  
+  // Test campaign data
+  const projectName = "Test Campaign";
+  const description = "This is a test campaign for the Gotong Karya platform";
+  const fundingGoalLamports = new anchor.BN(1 * LAMPORTS_PER_SOL); // 1 SOL
+  const nftName = "Test Supporter NFT";
+  const nftSymbol = "TEST";
+  const nftUri = "https://arweave.net/test-metadata-uri";
+  
+  // Create keypairs for testing with specific seeds for repeatability
+  const creator = Keypair.fromSeed(Uint8Array.from(Array(32).fill(1)));
+  const supporter = Keypair.fromSeed(Uint8Array.from(Array(32).fill(2)));
+  const nftMint = Keypair.generate();
+  
+  // Admin keypair, loaded from the file in ADMIN_KEYPAIR. Without it the default
+  // 3-day dispute window applies and the tests that withdraw funds are skipped.
+  const admin = process.env.ADMIN_KEYPAIR
+    ? Keypair.fromSecretKey(Uint8Array.from(JSON.parse(fs.readFileSync(process.env.ADMIN_KEYPAIR, "utf8"))))
+    : null;
+  
+  // Set by the first test; the following ones fund and withdraw from it
+  let campaignPda: PublicKey;
+  
+  // Campaign for the platform fee test, funded early and withdrawn from once its dispute window closes
+  const feeTestName = "Fee Test Campaign";
+  const feeCreator = Keypair.generate();
+  const feeSupporter = Keypair.generate();
+  const feeNftMint = Keypair.generate();
+  let feeCampaignPda: PublicKey;
+  
+  // Campaign for the dispute test; its supporter disputes it between its end and dispute deadline
+  const disputeCreator = Keypair.generate();
+  const disputeBacker = Keypair.generate(); // Funds the goal on their own
+  const disputeSupporter = Keypair.generate(); // Files the dispute
+  const disputeNftMint = Keypair.generate();
+  let disputeCampaignPda: PublicKey;
+  
+  // Token pledge campaigns. The first fails at collection because one of its
+  // pledgers revokes their approval; the second is collected in full.
+  const pledgeCreator = Keypair.generate();
+  const pledgeBacker = Keypair.generate(); // Pledges to the failing campaign and is refunded
+  const pledgeRevoker = Keypair.generate(); // Revokes their pledge to the failing campaign
+  const pledgeSupporter = Keypair.generate(); // Pledges the whole goal of the second campaign
+  const PLEDGE_TOKEN = 1_000_000; // One token in base units (6 decimals)
+  let pledgeMint: PublicKey;
+  let failingPledgeCampaignPda: PublicKey;
+  let pledgeCampaignPda: PublicKey;
+  
+  console.log("Creator pubkey:", creator.publicKey.toString());
+  console.log("Supporter pubkey:", supporter.publicKey.toString());
+  
+  // Helper function to transfer SOL
+  async function transferSol(from: anchor.Wallet, to: PublicKey, amount: number) {
+    console.log(`Transferring ${amount / LAMPORTS_PER_SOL} SOL to ${to.toString()}`);
+    
+    const tx = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.transfer({
+        fromPubkey: from.publicKey,
+        toPubkey: to,
+        lamports: amount,
+      })
+    );
+    
+    return await provider.sendAndConfirm(tx);
+  }
+  
+  // Campaign PDAs are seeded by the creator's campaign count, kept in their profile
+  function findCreatorProfilePda(creator: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("creator-profile"), creator.toBuffer()],
+      program.programId
+    )[0];
+  }
+  
+  function findCampaignPda(creator: PublicKey, creatorIndex: anchor.BN) {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("campaign"),
+        creator.toBuffer(),
+        creatorIndex.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+  }
+  
+  // Program-wide settings, including the emergency pause switch
+  const platformConfigPda = PublicKey.findProgramAddressSync(
+    [Buffer.from("platform-config")],
+    program.programId
+  )[0];
+  
+  // Off-chain campaign content, anchored by the SHA-256 hash of the document
+  function campaignContent(uri: string, document: string) {
+    return { uri, hash: Array.from(createHash("sha256").update(document).digest()) };
+  }
+  
+  function findSupporterFundingPda(campaign: PublicKey, supporter: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("supporter-funding"), campaign.toBuffer(), supporter.toBuffer()],
+      program.programId
+    )[0];
+  }
+  
+  // Supporter portfolio across campaigns, created with the first contribution
+  function findSupporterProfilePda(supporter: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("supporter-profile"), supporter.toBuffer()],
+      program.programId
+    )[0];
+  }
+  
+  // Platform fees collected on withdrawal
+  const [treasuryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury")],
+    program.programId
+  );
+  
+  function findDisputePda(campaign: PublicKey, supporter: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute"), campaign.toBuffer(), supporter.toBuffer()],
+      program.programId
+    )[0];
+  }
+  
+  // Campaigns are appended to fixed-size registry pages in id order
+  const CAMPAIGNS_PER_PAGE = 64;
+  
+  function findCampaignRegistryPda() {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("campaign-registry")],
+      program.programId
+    )[0];
+  }
+  
+  function findRegistryPagePda(pageIndex: anchor.BN) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("campaign-registry-page"), pageIndex.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  }
+  
+  // Helper function to launch a campaign and return its PDA
+  async function initializeCampaign(
+    campaignCreator: Keypair,
+    campaignNftMint: PublicKey,
+    args: {
+      projectName: string;
+      description: string;
+      content?: { uri: string; hash: number[] }; // Defaults to a document holding the description
+      fundingGoalLamports: anchor.BN;
+      nftName: string;
+      nftSymbol: string;
+      nftUri: string;
+      rewardMode?: object; // Defaults to escrowed transferable NFTs
+      access?: object; // Defaults to public
+      pledgeMint?: PublicKey; // Makes it a token pledge campaign; defaults to a SOL campaign
+    }
+  ) {
+    // The next campaign's index is the creator's current campaign count
+    const creatorProfilePda = findCreatorProfilePda(campaignCreator.publicKey);
+    const creatorProfile = await program.account.creatorProfile.fetchNullable(creatorProfilePda);
+    const campaignPda = findCampaignPda(
+      campaignCreator.publicKey,
+      creatorProfile ? creatorProfile.campaignCount : new anchor.BN(0)
+    );
+    
+    // The campaign lands on the page of the next registry id
+    const campaignRegistryPda = findCampaignRegistryPda();
+    const campaignRegistry = await program.account.campaignRegistry.fetchNullable(campaignRegistryPda);
+    const campaignCount = campaignRegistry ? campaignRegistry.campaignCount : new anchor.BN(0);
+    const registryPagePda = findRegistryPagePda(campaignCount.divn(CAMPAIGNS_PER_PAGE));
+    
+    const tx = await program.methods
+      .initializeCampaign(
+        args.projectName,
+        args.description,
+        args.content ?? campaignContent("https://arweave.net/test-content-uri", args.description),
+        args.fundingGoalLamports,
+        args.nftName,
+        args.nftSymbol,
+        args.nftUri,
+        args.rewardMode ?? { transferable: {} },
+        args.access ?? { public: {} }
+      )
+      .accountsPartial({
+        campaign: campaignPda,
+        creator: campaignCreator.publicKey,
+        creatorProfile: creatorProfilePda,
+        nftMint: campaignNftMint,
+        campaignRegistry: campaignRegistryPda,
+        registryPage: registryPagePda,
+        platformConfig: platformConfigPda,
+        // Unverified creator; the goals stay below any verification threshold
+        creatorVerification: null,
+        // No share tokens; the mint's PDA would otherwise be resolved and created
+        shareMint: null,
+        pledgeMint: args.pledgeMint ?? null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([campaignCreator])
+      .rpc();
+    
+    console.log("Campaign initialized transaction signature:", tx);
+    console.log("Campaign PDA:", campaignPda.toString());
+    
+    return campaignPda;
+  }
+  
+  // Helper function to contribute to a public campaign, returning the transaction signature
+  async function fundCampaign(campaign: PublicKey, campaignSupporter: Keypair, amount: anchor.BN) {
+    return await program.methods
+      .fundCampaign(amount, [])
+      .accountsPartial({
+        campaign,
+        supporter: campaignSupporter.publicKey,
+        supporterFunding: findSupporterFundingPda(campaign, campaignSupporter.publicKey),
+        supporterProfile: findSupporterProfilePda(campaignSupporter.publicKey),
+        platformConfig: platformConfigPda,
+        invitation: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([campaignSupporter])
+      .rpc();
+  }
+  
+  // Helper function for the creator to withdraw a funded campaign, returning the transaction signature
+  async function withdrawFunds(campaign: PublicKey, campaignCreator: Keypair) {
+    const campaignAccount = await program.account.campaign.fetch(campaign);
+    return await program.methods
+      .withdrawFunds()
+      .accountsPartial({
+        campaign,
+        platformConfig: platformConfigPda,
+        creator: campaignCreator.publicKey,
+        creatorProfile: findCreatorProfilePda(campaignAccount.creator),
+        treasury: treasuryPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([campaignCreator])
+      .rpc();
+  }
+  
+  // Helper function for a supporter to dispute a campaign, returning the transaction signature
+  async function fileDispute(campaign: PublicKey, campaignSupporter: Keypair, evidence: string) {
+    return await program.methods
+      .fileDispute(Array.from(createHash("sha256").update(evidence).digest()))
+      .accountsPartial({
+        campaign,
+        supporterFunding: findSupporterFundingPda(campaign, campaignSupporter.publicKey),
+        dispute: findDisputePda(campaign, campaignSupporter.publicKey),
+        supporter: campaignSupporter.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([campaignSupporter])
+      .rpc();
+  }
+  
+  // Helper function to pledge tokens from the supporter's associated token account
+  async function pledge(campaign: PublicKey, pledger: Keypair, amount: anchor.BN) {
+    return await program.methods
+      .pledge(amount, [])
+      .accountsPartial({
+        campaign,
+        platformConfig: platformConfigPda,
+        supporter: pledger.publicKey,
+        supporterFunding: findSupporterFundingPda(campaign, pledger.publicKey),
+        supporterProfile: findSupporterProfilePda(pledger.publicKey),
+        invitation: null,
+        supporterTokenAccount: getAssociatedTokenAddressSync(pledgeMint, pledger.publicKey),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([pledger])
+      .rpc();
+  }
+  
+  // Helper function to run the pledge collection crank over the given pledgers
+  async function collectPledges(campaign: PublicKey, pledgers: Keypair[]) {
+    const campaignAccount = await program.account.campaign.fetch(campaign);
+    
+    // Pairs of the pledge's funding record and its source token account
+    const pledgeAccounts: anchor.web3.AccountMeta[] = [];
+    for (const pledger of pledgers) {
+      pledgeAccounts.push(
+        { pubkey: findSupporterFundingPda(campaign, pledger.publicKey), isSigner: false, isWritable: true },
+        {
+          pubkey: getAssociatedTokenAddressSync(campaignAccount.pledgeMint, pledger.publicKey),
+          isSigner: false,
+          isWritable: true,
+        }
+      );
+    }
+    
+    return await program.methods
+      .collectPledges()
+      .accountsPartial({
+        campaign,
+        platformConfig: platformConfigPda,
+        collector: wallet.publicKey,
+        pledgeMint: campaignAccount.pledgeMint,
+        pledgeEscrow: getAssociatedTokenAddressSync(campaignAccount.pledgeMint, campaign, true),
+        creator: campaignAccount.creator,
+        creatorTokenAccount: getAssociatedTokenAddressSync(campaignAccount.pledgeMint, campaignAccount.creator),
+        creatorProfile: findCreatorProfilePda(campaignAccount.creator),
+        treasury: treasuryPda,
+        treasuryTokenAccount: getAssociatedTokenAddressSync(campaignAccount.pledgeMint, treasuryPda, true),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(pledgeAccounts)
+      .rpc();
+  }
+  
+  // Helper function to wait until the validator clock is past a unix timestamp
+  async function waitUntilPast(timestamp: anchor.BN) {
+    console.log(`Waiting until ${new Date(timestamp.toNumber() * 1000).toISOString()}...`);
+    
+    for (;;) {
+      const blockTime = await provider.connection.getBlockTime(await provider.connection.getSlot());
+      if (blockTime !== null && blockTime > timestamp.toNumber()) {
+        return;
+      }
+      await new Promise((resolve) => setTimeout(resolve, 5000));
+    }
+  }
+  
+  // Helper function for a supporter to claim a SOL refund, returning the transaction signature
+  async function claimRefund(campaign: PublicKey, campaignSupporter: Keypair) {
+    const campaignAccount = await program.account.campaign.fetch(campaign);
+    return await program.methods
+      .claimRefund()
+      .accountsPartial({
+        campaign,
+        supporterFunding: findSupporterFundingPda(campaign, campaignSupporter.publicKey),
+        supporter: campaignSupporter.publicKey,
+        creatorProfile: findCreatorProfilePda(campaignAccount.creator),
+        supporterProfile: findSupporterProfilePda(campaignSupporter.publicKey),
+        shareMint: null,
+        supporterShareAccount: null,
+        pledgeEscrow: null,
+        pledgeSource: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([campaignSupporter])
+      .rpc();
+  }
+  
+  // Fund accounts
+  before(async () => {
+    console.log("Payer wallet:", wallet.publicKey.toString());
+    
+    // Check wallet balance
+    const balance = await provider.connection.getBalance(wallet.publicKey);
+    console.log(`Payer wallet balance: ${balance / LAMPORTS_PER_SOL} SOL`);
+    
+    if (balance < 2 * LAMPORTS_PER_SOL) {
+      console.log("Trying to airdrop SOL to payer wallet...");
+      const signature = await provider.connection.requestAirdrop(wallet.publicKey, 2 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(signature);
+      console.log("Airdrop confirmed!");
+    }
+    
+    // Transfer SOL to test accounts
+    await transferSol(wallet, creator.publicKey, 1 * LAMPORTS_PER_SOL);
+    await transferSol(wallet, supporter.publicKey, 1 * LAMPORTS_PER_SOL);
+    
+    // Verify balances
+    const creatorBalance = await provider.connection.getBalance(creator.publicKey);
+    const supporterBalance = await provider.connection.getBalance(supporter.publicKey);
+    
+    console.log(`Creator balance: ${creatorBalance / LAMPORTS_PER_SOL} SOL`);
+    console.log(`Supporter balance: ${supporterBalance / LAMPORTS_PER_SOL} SOL`);
+    
+    // Shorten the dispute window so the test campaigns can be withdrawn from
+    if (admin) {
+      assert.equal(admin.publicKey.toString(), ADMIN_PUBKEY, "ADMIN_KEYPAIR should hold the program admin");
+      await transferSol(wallet, admin.publicKey, 1 * LAMPORTS_PER_SOL);
+      
+      await program.methods
+        .configureDisputes(admin.publicKey, new anchor.BN(DISPUTE_WINDOW_SECONDS))
+        .accountsPartial({
+          admin: admin.publicKey,
+          platformConfig: platformConfigPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+      console.log(`Dispute window set to ${DISPUTE_WINDOW_SECONDS} seconds`);
+    } else {
+      console.log("ADMIN_KEYPAIR not set, skipping the admin tests");
+    }
+    
+    // Wait for confirmation
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+  });
+  
+  it("Initializes a campaign", async () => {
+    try {
+      // Initialize campaign
+      campaignPda = await initializeCampaign(creator, nftMint.publicKey, {
+        projectName,
+        description,
+        fundingGoalLamports,
+        nftName,
+        nftSymbol,
+        nftUri,
+      });
+      
+      // Fetch campaign data
+      const campaignAccount = await program.account.campaign.fetch(campaignPda);
+      console.log("Campaign data:", campaignAccount);
+      
+      // Verify campaign data
+      assert.equal(campaignAccount.projectName, projectName);
+      assert.equal(campaignAccount.description, description);
+      assert.equal(campaignAccount.contentUri, "https://arweave.net/test-content-uri");
+      assert.deepEqual(
+        Array.from(campaignAccount.contentHash),
+        Array.from(createHash("sha256").update(description).digest())
+      );
+      assert.ok(campaignAccount.fundingGoalLamports.eq(fundingGoalLamports));
+      assert.equal(campaignAccount.nftName, nftName);
+      assert.equal(campaignAccount.nftSymbol, nftSymbol);
+      assert.equal(campaignAccount.nftUri, nftUri);
+      assert.equal(campaignAccount.nftMint.toString(), nftMint.publicKey.toString());
+      assert.deepEqual(campaignAccount.rewardMode, { transferable: {} });
+      assert.deepEqual(campaignAccount.access, { public: {} });
+      assert.equal(campaignAccount.creatorIndex.toNumber(), 0);
+      
+      // The campaign is listed in the registry under its id
+      const registryPage = await program.account.campaignRegistryPage.fetch(
+        findRegistryPagePda(campaignAccount.id.divn(CAMPAIGNS_PER_PAGE))
+      );
+      assert.ok(registryPage.campaigns.some((campaign) => campaign.equals(campaignPda)));
+      
+      // The creator's profile counts the campaign
+      const creatorProfile = await program.account.creatorProfile.fetch(findCreatorProfilePda(creator.publicKey));
+      assert.equal(creatorProfile.campaignCount.toNumber(), 1);
+    } catch (error) {
+      console.error("Error initializing campaign:", error);
+      throw error;
+    }
+  });
+  
+  it("Funds a campaign", async () => {
+    // Find supporter funding PDA
+    const supporterFundingPda = findSupporterFundingPda(campaignPda, supporter.publicKey);
+    
+    const fundingAmount = new anchor.BN(0.5 * LAMPORTS_PER_SOL); // 0.5 SOL
+    
+    try {
+      // Fund campaign
+      const tx = await fundCampaign(campaignPda, supporter, fundingAmount);
+        
+      console.log("Campaign funding transaction signature:", tx);
+      
+      // Fetch updated campaign data
+      const campaignAccount = await program.account.campaign.fetch(campaignPda);
+      console.log("Updated campaign data:", campaignAccount);
+      
+      // Fetch supporter funding data
+      const supporterFundingAccount = await program.account.supporterFunding.fetch(supporterFundingPda);
+      console.log("Supporter funding data:", supporterFundingAccount);
+      
+      // Verify funding data
+      assert.ok(campaignAccount.raisedLamports.gte(fundingAmount));
+      assert.equal(supporterFundingAccount.campaign.toString(), campaignPda.toString());
+      assert.equal(supporterFundingAccount.supporter.toString(), supporter.publicKey.toString());
+      assert.ok(supporterFundingAccount.amountLamports.eq(fundingAmount));
+      assert.equal(supporterFundingAccount.isClaimed, false);
+      
+      // The contribution is counted in the supporter's portfolio
+      const supporterProfile = await program.account.supporterProfile.fetch(findSupporterProfilePda(supporter.publicKey));
+      assert.ok(supporterProfile.totalContributedLamports.eq(fundingAmount));
+      assert.equal(supporterProfile.campaignsBacked.toNumber(), 1);
+    } catch (error) {
+      console.error("Error funding campaign:", error);
+      throw error;
+    }
+  });
+  
+  it("Fully funds a campaign and holds withdrawals during the dispute window", async () => {
+    try {
+      // Fetch current campaign data
+      let campaignAccount = await program.account.campaign.fetch(campaignPda);
+      
+      // Calculate remaining amount to fully fund the campaign
+      const remainingAmount = campaignAccount.fundingGoalLamports.sub(campaignAccount.raisedLamports);
+      console.log(`Need ${remainingAmount.toNumber() / LAMPORTS_PER_SOL} more SOL to fully fund campaign`);
+      
+      if (remainingAmount.toNumber() > 0) {
+        // Create a second supporter to fully fund the campaign
+        const additionalSupporter = Keypair.fromSeed(Uint8Array.from(Array(32).fill(3)));
+        console.log("Additional supporter pubkey:", additionalSupporter.publicKey.toString());
+        
+        // Fund the additional supporter
+        await transferSol(wallet, additionalSupporter.publicKey, 1 * LAMPORTS_PER_SOL);
+        
+        // Fund campaign with the remaining amount needed
+        const fundTx = await fundCampaign(campaignPda, additionalSupporter, remainingAmount);
+          
+        console.log("Additional funding transaction signature:", fundTx);
+      }
+      
+      // Fetch updated campaign data to verify it's fully funded
+      campaignAccount = await program.account.campaign.fetch(campaignPda);
+      console.log("Campaign after full funding:", campaignAccount);
+      
+      // Verify campaign is now fully funded
+      assert.ok(campaignAccount.raisedLamports.gte(campaignAccount.fundingGoalLamports));
+      assert.ok(campaignAccount.isFunded);
+      
+      // Supporters can dispute the campaign until its dispute deadline; the
+      // creator can't withdraw before then
+      try {
+        await withdrawFunds(campaignPda, creator);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to withdraw during the dispute window");
+      } catch (error) {
+        // Expected error - the dispute window is still open
+        console.log("Withdraw during the dispute window correctly failed");
+        assert.ok(error.toString().includes("DisputeWindowOpen"));
+      }
+      
+    } catch (error) {
+      console.error("Error in withdrawal test:", error);
+      throw error;
+    }
+  });
+  
+  it("Creates a campaign that fails to meet funding goal and tests refund", async () => {
+    // Create new campaign with different name to avoid conflicts
+    const failingProjectName = "Failing Test Campaign";
+    const failingCreator = Keypair.fromSeed(Uint8Array.from(Array(32).fill(4)));
+    const failingSupporter = Keypair.fromSeed(Uint8Array.from(Array(32).fill(5)));
+    const failingNftMint = Keypair.generate();
+    
+    console.log("Failing campaign creator:", failingCreator.publicKey.toString());
+    console.log("Failing campaign supporter:", failingSupporter.publicKey.toString());
+    
+    // Fund accounts
+    await transferSol(wallet, failingCreator.publicKey, 1 * LAMPORTS_PER_SOL);
+    await transferSol(wallet, failingSupporter.publicKey, 1 * LAMPORTS_PER_SOL);
+    
+    try {
+      // Initialize failing campaign
+      const failingCampaignPda = await initializeCampaign(failingCreator, failingNftMint.publicKey, {
+        projectName: failingProjectName,
+        description: "This campaign will not reach its goal",
+        fundingGoalLamports: new anchor.BN(2 * LAMPORTS_PER_SOL), // 2 SOL goal
+        nftName: "Failing NFT",
+        nftSymbol: "FAIL",
+        nftUri: "https://arweave.net/failing-nft-uri",
+      });
+      
+      console.log("Failing campaign initialized");
+      
+      // Find supporter funding PDA
+      const supporterFundingPda = findSupporterFundingPda(failingCampaignPda, failingSupporter.publicKey);
+      
+      // Fund campaign with insufficient amount
+      const fundAmount = new anchor.BN(0.5 * LAMPORTS_PER_SOL); // Only 0.5 SOL, not enough to meet goal
+      const fundTx = await fundCampaign(failingCampaignPda, failingSupporter, fundAmount);
+        
+      console.log("Partial funding transaction:", fundTx);
+      
+      // Simulate time passing - normally we'd wait for the campaign to end naturally
+      // For testing, we'll use a workaround by modifying the timestamp in the tests
+      console.log("Simulating campaign end... In a real scenario, we'd wait for 30 days");
+      console.log("For testing purposes, we'll directly test the refund path");
+      
+      // Check supporter balance before refund
+      const supporterBalanceBefore = await provider.connection.getBalance(failingSupporter.publicKey);
+      console.log(`Supporter balance before refund: ${supporterBalanceBefore / LAMPORTS_PER_SOL} SOL`);
+      
+      // Note: In a real test we would need to modify the clock to simulate the end timestamp
+      // For now we'll make comments about what would happen in a real scenario
+      
+      console.log("⚠️ In a real scenario, after the campaign end timestamp passes:");
+      console.log("1. Campaign would be verified as not fully funded");
+      console.log("2. Supporters would call claim_refund() to get their SOL back");
+      console.log("3. The contract would verify the campaign has ended and transfer SOL back");
+      
+      // For now we'll verify the campaign data is correct
+      const campaignAccount = await program.account.campaign.fetch(failingCampaignPda);
+      const supporterFundingAccount = await program.account.supporterFunding.fetch(supporterFundingPda);
+      
+      assert.equal(campaignAccount.isFunded, false);
+      assert.ok(campaignAccount.raisedLamports.lt(campaignAccount.fundingGoalLamports));
+      assert.equal(supporterFundingAccount.isClaimed, false);
+      assert.ok(supporterFundingAccount.amountLamports.eq(fundAmount));
+      
+    } catch (error) {
+      console.error("Error in failing campaign test:", error);
+      throw error;
+    }
+  });
+  
+  it("Tests edge cases and validation rules", async () => {
+    // Test validation rules like trying to withdraw from an unfunded campaign
+    // or trying to fund a campaign after it has ended
+    
+    // Create a new campaign for testing edge cases
+    const edgeCaseProjectName = "Edge Case Campaign";
+    const edgeCaseCreator = Keypair.fromSeed(Uint8Array.from(Array(32).fill(6)));
+    const edgeCaseNftMint = Keypair.generate();
+    
+    console.log("Edge case campaign creator:", edgeCaseCreator.publicKey.toString());
+    
+    // Fund the creator
+    await transferSol(wallet, edgeCaseCreator.publicKey, 1 * LAMPORTS_PER_SOL);
+    
+    try {
+      // Initialize campaign
+      const edgeCaseCampaignPda = await initializeCampaign(edgeCaseCreator, edgeCaseNftMint.publicKey, {
+        projectName: edgeCaseProjectName,
+        description: "Campaign for testing edge cases",
+        fundingGoalLamports: new anchor.BN(1 * LAMPORTS_PER_SOL),
+        nftName: "Edge NFT",
+        nftSymbol: "EDGE",
+        nftUri: "https://arweave.net/edge-nft-uri",
+      });
+      
+      console.log("Edge case campaign initialized");
+      
+      // Test 1: Try to withdraw from an unfunded campaign (should fail)
+      console.log("Test 1: Attempting to withdraw from unfunded campaign (should fail)");
+      try {
+        await withdrawFunds(edgeCaseCampaignPda, edgeCaseCreator);
+          
+        // If we reach here, the test failed
+        assert.fail("Should not be able to withdraw from unfunded campaign");
+      } catch (error) {
+        // Expected error - campaign is not funded
+        console.log("Withdraw from unfunded campaign correctly failed");
+        assert.ok(error.toString().includes("CampaignNotFunded") || 
+                 error.toString().includes("Error Code: CampaignNotFunded"));
+      }
+      
+      // Fund the campaign so the creator check is reached
+      const edgeCaseSupporter = Keypair.generate();
+      await transferSol(wallet, edgeCaseSupporter.publicKey, 2 * LAMPORTS_PER_SOL);
+      await fundCampaign(edgeCaseCampaignPda, edgeCaseSupporter, new anchor.BN(1 * LAMPORTS_PER_SOL));
+      
+      // Test 2: Try to withdraw as non-creator (should fail)
+      console.log("Test 2: Attempting to withdraw as non-creator (should fail)");
+      try {
+        await withdrawFunds(edgeCaseCampaignPda, supporter); // Using existing supporter as non-creator
+          
+        // If we reach here, the test failed
+        assert.fail("Should not be able to withdraw as non-creator");
+      } catch (error) {
+        // Expected error - unauthorized
+        console.log("Withdraw as non-creator correctly failed");
+        assert.ok(error.toString().includes("Unauthorized") || 
+                 error.toString().includes("Error Code: Unauthorized") ||
+                 error.toString().includes("0x177b"));
+      }
+    } catch (error) {
+      console.error("Error in edge case tests:", error);
+      throw error;
+    }
+  });
+
+  // Test the new Edition NFT minting functionality
+  it("Tests NFT edition minting for a fully funded campaign", async () => {
+    // Create a new campaign for testing NFT editions
+    const editionProjectName = "Edition Test Campaign";
+    const editionCreator = anchor.web3.Keypair.generate();
+    
+    console.log("NFT edition campaign creator:", editionCreator.publicKey.toString());
+    
+    // Fund the creator
+    await transferSol(wallet, editionCreator.publicKey, 2 * LAMPORTS_PER_SOL);
+    
+    // Fund the supporter
+    const editionSupporter = anchor.web3.Keypair.generate();
+    await transferSol(wallet, editionSupporter.publicKey, 2 * LAMPORTS_PER_SOL);
+    
+    try {
+      // Create NFT mint keypair
+      const editionNftMint = anchor.web3.Keypair.generate();
+      console.log("NFT master edition mint:", editionNftMint.publicKey.toString());
+      
+      // Initialize campaign with a small funding goal
+      const editionCampaignPda = await initializeCampaign(editionCreator, editionNftMint.publicKey, {
+        projectName: editionProjectName,
+        description: "Campaign for testing NFT editions",
+        fundingGoalLamports: new anchor.BN(Math.floor(0.5 * LAMPORTS_PER_SOL)), // Just 0.5 SOL goal
+        nftName: "Edition NFT",
+        nftSymbol: "ENFT",
+        nftUri: "https://arweave.net/edition-nft-uri",
+      });
+      
+      console.log("NFT edition campaign initialized");
+      
+      // Find supporter funding PDA
+      const supporterFundingPda = findSupporterFundingPda(editionCampaignPda, editionSupporter.publicKey);
+      
+      // Fund campaign to make it fully funded
+      const fundTx = await fundCampaign(editionCampaignPda, editionSupporter, new anchor.BN(Math.floor(0.5 * LAMPORTS_PER_SOL)));
+      
+      console.log("Campaign fully funded for NFT edition test");
+      
+      // Verify campaign is funded
+      const campaignAfterFunding = await program.account.campaign.fetch(editionCampaignPda);
+      assert.ok(campaignAfterFunding.isFunded);
+      console.log("Campaign confirmed as fully funded");
+      console.log("Campaign max editions:", campaignAfterFunding.maxEditions.toString());
+      console.log("Campaign editions minted:", campaignAfterFunding.editionsMinted.toString());
+      
+      // For testing purposes, we'll just verify that the campaign has the correct edition settings
+      // and that the supporter funding record is properly initialized
+      const supporterFundingData = await program.account.supporterFunding.fetch(supporterFundingPda);
+      assert.equal(supporterFundingData.nftMinted, false);
+      assert.equal(supporterFundingData.editionNumber, 0);
+      
+      console.log("✅ NFT edition test successful - campaign and supporter funding properly initialized");
+      console.log("For a full test of edition minting, we would need to setup token accounts and metadata accounts");
+    } catch (error) {
+      console.error("Error in NFT edition test:", error);
+      throw error;
+    }
+  });
+
+  it("Tests updateSupporterNftMint rejects editions not printed from the campaign NFT", async () => {
+    // Create new keypairs for this specific test
+    const nftCreator = Keypair.fromSeed(Uint8Array.from(Array(32).fill(5)));
+    const nftSupporter = Keypair.fromSeed(Uint8Array.from(Array(32).fill(6)));
+    const masterNftMint = Keypair.generate(); // Master NFT mint
+    const editionNftMint = Keypair.generate(); // A mint the frontend claims is an edition, never printed from the master
+    
+    // Fund accounts
+    await transferSol(wallet, nftCreator.publicKey, 1 * LAMPORTS_PER_SOL);
+    await transferSol(wallet, nftSupporter.publicKey, 1 * LAMPORTS_PER_SOL);
+    
+    const nftProjectName = "NFT Update Test Project";
+    
+    console.log("\n🔍 Testing updateSupporterNftMint instruction...");
+    
+    try {
+      // Initialize campaign
+      console.log("Creating campaign for NFT update test...");
+      const nftCampaignPda = await initializeCampaign(nftCreator, masterNftMint.publicKey, {
+        projectName: nftProjectName,
+        description: "Test campaign for NFT update functionality",
+        fundingGoalLamports: new anchor.BN(1 * LAMPORTS_PER_SOL), // 1 SOL goal
+        nftName: "Test NFT",
+        nftSymbol: "TNFT",
+        nftUri: "https://arweave.net/test-uri",
+      });
+      
+      console.log("Campaign created for NFT update test");
+      
+      // Find supporter funding PDA
+      const supporterFundingPda = findSupporterFundingPda(nftCampaignPda, nftSupporter.publicKey);
+      console.log("Supporter Funding PDA:", supporterFundingPda.toString());
+      
+      // Fund the campaign to make it fully funded
+      console.log("Funding campaign...");
+      await fundCampaign(nftCampaignPda, nftSupporter, new anchor.BN(1 * LAMPORTS_PER_SOL)); // Fund with exactly the goal amount
+      
+      console.log("Campaign fully funded");
+      
+      // Verify campaign is now fully funded
+      const campaignAccount = await program.account.campaign.fetch(nftCampaignPda);
+      assert.equal(campaignAccount.isFunded, true, "Campaign should be marked as funded");
+      
+      // Before calling updateSupporterNftMint, confirm supporter funding exists and NFT not yet minted
+      const initialSupporterData = await program.account.supporterFunding.fetch(supporterFundingPda);
+      assert.equal(initialSupporterData.nftMinted, false, "NFT should not be minted initially");
+      
+      // Call updateSupporterNftMint with a mint that was never printed from the
+      // campaign's master edition; only real editions can be recorded
+      console.log("Calling updateSupporterNftMint with an unprinted edition mint (should fail)...");
+      const editionNumber = 1; // First edition
+      const [editionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("metadata"),
+          TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+          editionNftMint.publicKey.toBuffer(),
+          Buffer.from("edition"),
+        ],
+        TOKEN_METADATA_PROGRAM_ID
+      );
+      
+      try {
+        await program.methods
+          .updateSupporterNftMint(new anchor.BN(editionNumber))
+          .accountsPartial({
+            campaign: nftCampaignPda,
+            platformConfig: platformConfigPda,
+            supporterFunding: supporterFundingPda,
+            creator: nftCreator.publicKey,
+            authority: nftSupporter.publicKey, // The supporter is claiming their own NFT
+            supporterProfile: findSupporterProfilePda(nftSupporter.publicKey),
+            masterEditionMint: masterNftMint.publicKey,
+            editionMint: editionNftMint.publicKey,
+            edition: editionPda,
+            mintAuthority: nftSupporter.publicKey,
+            recipientTokenAccount: nftSupporter.publicKey,
+            tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([nftSupporter])
+          .rpc();
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to record an edition that wasn't printed from the campaign NFT");
+      } catch (error) {
+        // Expected error - the edition account doesn't belong to the metadata program
+        console.log("Unprinted edition mint correctly rejected");
+        assert.ok(error.toString().includes("InvalidEdition"));
+      }
+      
+      // Nothing was recorded for the supporter
+      const supporterFundingData = await program.account.supporterFunding.fetch(supporterFundingPda);
+      assert.equal(supporterFundingData.nftMinted, false, "NFT should still not be minted");
+      assert.equal(supporterFundingData.editionNumber.toNumber(), 0, "No edition number should be recorded");
+      
+      const updatedCampaign = await program.account.campaign.fetch(nftCampaignPda);
+      assert.equal(updatedCampaign.editionsMinted.toNumber(), 0, "Campaign editions_minted should be unchanged");
+      
+      console.log("✅ updateSupporterNftMint only accepts editions printed from the campaign NFT");
+    } catch (error) {
+      console.error("Error in NFT claiming test:", error);
+      throw error;
+    }
+  });
+
+  it("Funds a campaign for the platform fee test", async () => {
+    try {
+      // Fund accounts
+      await transferSol(wallet, feeCreator.publicKey, 2 * LAMPORTS_PER_SOL);
+      await transferSol(wallet, feeSupporter.publicKey, 5 * LAMPORTS_PER_SOL);
+      
+      console.log("Treasury PDA:", treasuryPda.toString());
+      
+      // Initialize campaign
+      feeCampaignPda = await initializeCampaign(feeCreator, feeNftMint.publicKey, {
+        projectName: feeTestName,
+        description,
+        fundingGoalLamports: new anchor.BN(1 * LAMPORTS_PER_SOL), // 1 SOL funding goal
+        nftName,
+        nftSymbol,
+        nftUri,
+      });
+        
+      console.log("Campaign initialized for fee test");
+      
+      // Fund campaign with 2 SOL (200% of goal - ensures campaign is funded)
+      await fundCampaign(feeCampaignPda, feeSupporter, new anchor.BN(2 * LAMPORTS_PER_SOL));
+        
+      console.log("Campaign fully funded with 2 SOL");
+      
+      // Check campaign account to verify funding
+      const campaignAccount = await program.account.campaign.fetch(feeCampaignPda);
+      assert.equal(campaignAccount.isFunded, true, "Campaign should be marked as funded");
+      assert.equal(
+        campaignAccount.raisedLamports.toString(),
+        (2 * LAMPORTS_PER_SOL).toString(),
+        "Campaign should have 2 SOL raised"
+      );
+    } catch (error) {
+      console.error("Error in treasury fee test:", error);
+      throw error;
+    }
+  });
+  
+  it("Pauses the platform and blocks funding until it resumes", async function () {
+    if (!admin) {
+      this.skip();
+    }
+    
+    const pauseCreator = Keypair.generate();
+    const pauseSupporter = Keypair.generate();
+    const pauseNftMint = Keypair.generate();
+    
+    // Fund accounts
+    await transferSol(wallet, pauseCreator.publicKey, 1 * LAMPORTS_PER_SOL);
+    await transferSol(wallet, pauseSupporter.publicKey, 1 * LAMPORTS_PER_SOL);
+    
+    // Helper function to flip the platform pause switch
+    const setPlatformPaused = (signer: Keypair, paused: boolean) =>
+      program.methods
+        .setPlatformPaused(paused)
+        .accountsPartial({
+          admin: signer.publicKey,
+          platformConfig: platformConfigPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+    
+    try {
+      // Campaigns can't be launched while paused, so create it first
+      const pauseCampaignPda = await initializeCampaign(pauseCreator, pauseNftMint.publicKey, {
+        projectName: "Pause Test Campaign",
+        description: "Campaign for testing the platform pause",
+        fundingGoalLamports: new anchor.BN(1 * LAMPORTS_PER_SOL),
+        nftName: "Pause NFT",
+        nftSymbol: "PAUSE",
+        nftUri: "https://arweave.net/pause-nft-uri",
+      });
+      
+      // Test 1: Only the admin can pause the platform
+      console.log("Test 1: Attempting to pause the platform as non-admin (should fail)");
+      try {
+        await setPlatformPaused(pauseSupporter, true);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to pause the platform as non-admin");
+      } catch (error) {
+        // Expected error - unauthorized
+        console.log("Pause as non-admin correctly failed");
+        assert.ok(error.toString().includes("Unauthorized"));
+      }
+      
+      // Admin pauses the platform
+      await setPlatformPaused(admin, true);
+      let platformConfig = await program.account.platformConfig.fetch(platformConfigPda);
+      assert.equal(platformConfig.paused, true);
+      console.log("Platform paused");
+      
+      // Test 2: Funding is blocked while paused
+      console.log("Test 2: Attempting to fund while the platform is paused (should fail)");
+      try {
+        await fundCampaign(pauseCampaignPda, pauseSupporter, new anchor.BN(0.1 * LAMPORTS_PER_SOL));
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to fund while the platform is paused");
+      } catch (error) {
+        // Expected error - platform paused
+        console.log("Funding while paused correctly failed");
+        assert.ok(error.toString().includes("PlatformPaused"));
+      }
+      
+      // Admin resumes the platform and funding works again
+      await setPlatformPaused(admin, false);
+      platformConfig = await program.account.platformConfig.fetch(platformConfigPda);
+      assert.equal(platformConfig.paused, false);
+      console.log("Platform resumed");
+      
+      await fundCampaign(pauseCampaignPda, pauseSupporter, new anchor.BN(0.1 * LAMPORTS_PER_SOL));
+      const campaignAccount = await program.account.campaign.fetch(pauseCampaignPda);
+      assert.ok(campaignAccount.raisedLamports.eq(new anchor.BN(0.1 * LAMPORTS_PER_SOL)));
+      
+      console.log("✅ Platform pause test successful");
+    } catch (error) {
+      console.error("Error in platform pause test:", error);
+      throw error;
+    }
+  });
+  
+  it("Freezes a single campaign and blocks its funding until unfrozen", async function () {
+    if (!admin) {
+      this.skip();
+    }
+    
+    const freezeCreator = Keypair.generate();
+    const freezeSupporter = Keypair.generate();
+    const freezeNftMint = Keypair.generate();
+    
+    // Fund accounts
+    await transferSol(wallet, freezeCreator.publicKey, 1 * LAMPORTS_PER_SOL);
+    await transferSol(wallet, freezeSupporter.publicKey, 1 * LAMPORTS_PER_SOL);
+    
+    try {
+      const freezeCampaignPda = await initializeCampaign(freezeCreator, freezeNftMint.publicKey, {
+        projectName: "Freeze Test Campaign",
+        description: "Campaign for testing the campaign freeze",
+        fundingGoalLamports: new anchor.BN(1 * LAMPORTS_PER_SOL),
+        nftName: "Freeze NFT",
+        nftSymbol: "FRZ",
+        nftUri: "https://arweave.net/freeze-nft-uri",
+      });
+      
+      // Helper function to freeze or unfreeze the campaign
+      const setCampaignFrozen = (signer: Keypair, frozen: boolean) =>
+        program.methods
+          .setCampaignFrozen(frozen)
+          .accountsPartial({
+            campaign: freezeCampaignPda,
+            admin: signer.publicKey,
+          })
+          .signers([signer])
+          .rpc();
+      
+      // Test 1: Only the admin can freeze a campaign
+      console.log("Test 1: Attempting to freeze the campaign as its creator (should fail)");
+      try {
+        await setCampaignFrozen(freezeCreator, true);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to freeze a campaign as non-admin");
+      } catch (error) {
+        // Expected error - unauthorized
+        console.log("Freeze as non-admin correctly failed");
+        assert.ok(error.toString().includes("Unauthorized"));
+      }
+      
+      // Admin freezes the campaign
+      await setCampaignFrozen(admin, true);
+      let campaignAccount = await program.account.campaign.fetch(freezeCampaignPda);
+      assert.equal(campaignAccount.isFrozen, true);
+      console.log("Campaign frozen");
+      
+      // Test 2: Funding the frozen campaign is blocked
+      console.log("Test 2: Attempting to fund the frozen campaign (should fail)");
+      try {
+        await fundCampaign(freezeCampaignPda, freezeSupporter, new anchor.BN(0.1 * LAMPORTS_PER_SOL));
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to fund a frozen campaign");
+      } catch (error) {
+        // Expected error - campaign frozen
+        console.log("Funding the frozen campaign correctly failed");
+        assert.ok(error.toString().includes("CampaignFrozen"));
+      }
+      
+      // Admin unfreezes the campaign and funding works again
+      await setCampaignFrozen(admin, false);
+      await fundCampaign(freezeCampaignPda, freezeSupporter, new anchor.BN(0.1 * LAMPORTS_PER_SOL));
+      
+      campaignAccount = await program.account.campaign.fetch(freezeCampaignPda);
+      assert.equal(campaignAccount.isFrozen, false);
+      assert.ok(campaignAccount.raisedLamports.eq(new anchor.BN(0.1 * LAMPORTS_PER_SOL)));
+      
+      console.log("✅ Campaign freeze test successful");
+    } catch (error) {
+      console.error("Error in campaign freeze test:", error);
+      throw error;
+    }
+  });
+  
+  it("Takes down a funded campaign and refunds its supporters right away", async function () {
+    if (!admin) {
+      this.skip();
+    }
+    
+    const takedownCreator = Keypair.generate();
+    const takedownSupporter = Keypair.generate();
+    const takedownNftMint = Keypair.generate();
+    
+    // Fund accounts
+    await transferSol(wallet, takedownCreator.publicKey, 1 * LAMPORTS_PER_SOL);
+    await transferSol(wallet, takedownSupporter.publicKey, 2 * LAMPORTS_PER_SOL);
+    
+    try {
+      const takedownCampaignPda = await initializeCampaign(takedownCreator, takedownNftMint.publicKey, {
+        projectName: "Takedown Test Campaign",
+        description: "Campaign for testing moderator takedowns",
+        fundingGoalLamports: new anchor.BN(1 * LAMPORTS_PER_SOL),
+        nftName: "Takedown NFT",
+        nftSymbol: "TKDN",
+        nftUri: "https://arweave.net/takedown-nft-uri",
+      });
+      
+      // Fund the campaign to its goal
+      const fundAmount = new anchor.BN(1 * LAMPORTS_PER_SOL);
+      await fundCampaign(takedownCampaignPda, takedownSupporter, fundAmount);
+      let campaignAccount = await program.account.campaign.fetch(takedownCampaignPda);
+      assert.equal(campaignAccount.isFunded, true, "Campaign should be marked as funded");
+      
+      // Helper function to flag the campaign
+      const flagCampaign = (moderator: Keypair) =>
+        program.methods
+          .flagCampaign({ fraud: {} })
+          .accountsPartial({
+            campaign: takedownCampaignPda,
+            moderator: moderator.publicKey,
+            platformConfig: platformConfigPda,
+            creatorProfile: findCreatorProfilePda(takedownCreator.publicKey),
+          })
+          .signers([moderator])
+          .rpc();
+      
+      // Test 1: Only the moderator or admin can take a campaign down
+      console.log("Test 1: Attempting to flag the campaign as a supporter (should fail)");
+      try {
+        await flagCampaign(takedownSupporter);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to flag a campaign as non-moderator");
+      } catch (error) {
+        // Expected error - unauthorized
+        console.log("Flag as non-moderator correctly failed");
+        assert.ok(error.toString().includes("Unauthorized"));
+      }
+      
+      // Admin takes the campaign down
+      await flagCampaign(admin);
+      campaignAccount = await program.account.campaign.fetch(takedownCampaignPda);
+      assert.deepEqual(campaignAccount.takedownReason, { fraud: {} });
+      assert.equal(campaignAccount.isActive, false);
+      
+      const creatorProfile = await program.account.creatorProfile.fetch(findCreatorProfilePda(takedownCreator.publicKey));
+      assert.equal(creatorProfile.campaignsTakenDown.toNumber(), 1);
+      console.log("Campaign taken down");
+      
+      // Test 2: A campaign can only be taken down once
+      console.log("Test 2: Attempting to flag the campaign again (should fail)");
+      try {
+        await flagCampaign(admin);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to flag a campaign twice");
+      } catch (error) {
+        // Expected error - already taken down
+        console.log("Second flag correctly failed");
+        assert.ok(error.toString().includes("CampaignTakenDown"));
+      }
+      
+      // Test 3: The campaign no longer accepts contributions
+      console.log("Test 3: Attempting to fund the taken down campaign (should fail)");
+      try {
+        await fundCampaign(takedownCampaignPda, takedownSupporter, new anchor.BN(0.1 * LAMPORTS_PER_SOL));
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to fund a taken down campaign");
+      } catch (error) {
+        // Expected error - campaign taken down
+        console.log("Funding the taken down campaign correctly failed");
+        assert.ok(error.toString().includes("CampaignTakenDown"));
+      }
+      
+      // Test 4: The creator can't withdraw the funds
+      console.log("Test 4: Attempting to withdraw from the taken down campaign (should fail)");
+      try {
+        await withdrawFunds(takedownCampaignPda, takedownCreator);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to withdraw from a taken down campaign");
+      } catch (error) {
+        // Expected error - campaign taken down
+        console.log("Withdraw from the taken down campaign correctly failed");
+        assert.ok(error.toString().includes("CampaignTakenDown"));
+      }
+      
+      // The supporter is refunded before the campaign ends, even though it reached its goal
+      const supporterBalanceBefore = await provider.connection.getBalance(takedownSupporter.publicKey);
+      await claimRefund(takedownCampaignPda, takedownSupporter);
+      const supporterBalanceAfter = await provider.connection.getBalance(takedownSupporter.publicKey);
+      
+      assert.equal(supporterBalanceAfter - supporterBalanceBefore, fundAmount.toNumber(), "Supporter should get their contribution back");
+      const supporterFundingAccount = await program.account.supporterFunding.fetch(
+        findSupporterFundingPda(takedownCampaignPda, takedownSupporter.publicKey)
+      );
+      assert.equal(supporterFundingAccount.isClaimed, true);
+      
+      console.log("✅ Campaign takedown test successful");
+    } catch (error) {
+      console.error("Error in campaign takedown test:", error);
+      throw error;
+    }
+  });
+  
+  it("Funds a campaign for the dispute test and rejects disputes before it ends", async () => {
+    // Fund accounts
+    await transferSol(wallet, disputeCreator.publicKey, 1 * LAMPORTS_PER_SOL);
+    await transferSol(wallet, disputeBacker.publicKey, 2 * LAMPORTS_PER_SOL);
+    await transferSol(wallet, disputeSupporter.publicKey, 1 * LAMPORTS_PER_SOL);
+    
+    try {
+      disputeCampaignPda = await initializeCampaign(disputeCreator, disputeNftMint.publicKey, {
+        projectName: "Dispute Test Campaign",
+        description: "Campaign for testing supporter disputes",
+        fundingGoalLamports: new anchor.BN(1 * LAMPORTS_PER_SOL),
+        nftName: "Dispute NFT",
+        nftSymbol: "DSPT",
+        nftUri: "https://arweave.net/dispute-nft-uri",
+      });
+      
+      // The backer alone reaches the goal, so refunding the disputed contribution keeps the campaign funded
+      await fundCampaign(disputeCampaignPda, disputeBacker, new anchor.BN(1 * LAMPORTS_PER_SOL));
+      await fundCampaign(disputeCampaignPda, disputeSupporter, new anchor.BN(0.5 * LAMPORTS_PER_SOL));
+      
+      const campaignAccount = await program.account.campaign.fetch(disputeCampaignPda);
+      assert.equal(campaignAccount.isFunded, true, "Campaign should be marked as funded");
+      
+      // Disputes open once the campaign has ended
+      console.log("Attempting to dispute a running campaign (should fail)");
+      try {
+        await fileDispute(disputeCampaignPda, disputeSupporter, "Rewards were never described");
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to dispute a campaign before it ends");
+      } catch (error) {
+        // Expected error - campaign not ended
+        console.log("Dispute before the end correctly failed");
+        assert.ok(error.toString().includes("CampaignNotEnded"));
+      }
+    } catch (error) {
+      console.error("Error in dispute test:", error);
+      throw error;
+    }
+  });
+  
+  it("Takes token pledges without moving tokens until collection", async () => {
+    // Fund accounts
+    await transferSol(wallet, pledgeCreator.publicKey, 1 * LAMPORTS_PER_SOL);
+    for (const pledger of [pledgeBacker, pledgeRevoker, pledgeSupporter]) {
+      await transferSol(wallet, pledger.publicKey, 1 * LAMPORTS_PER_SOL);
+    }
+    
+    try {
+      // Create the pledge token and give every pledger 1000 tokens
+      pledgeMint = await createMint(connection, wallet.payer, wallet.publicKey, null, 6);
+      console.log("Pledge mint:", pledgeMint.toString());
+      
+      for (const pledger of [pledgeBacker, pledgeRevoker, pledgeSupporter]) {
+        const tokenAccount = await getOrCreateAssociatedTokenAccount(connection, wallet.payer, pledgeMint, pledger.publicKey);
+        await mintTo(connection, wallet.payer, pledgeMint, tokenAccount.address, wallet.payer, 1000 * PLEDGE_TOKEN);
+      }
+      
+      // Both campaigns have a 1000 token goal
+      failingPledgeCampaignPda = await initializeCampaign(pledgeCreator, Keypair.generate().publicKey, {
+        projectName: "Failing Pledge Campaign",
+        description: "Pledge campaign that loses a pledge at collection",
+        fundingGoalLamports: new anchor.BN(1000 * PLEDGE_TOKEN),
+        nftName: "Pledge NFT",
+        nftSymbol: "PLDG",
+        nftUri: "https://arweave.net/pledge-nft-uri",
+        pledgeMint,
+      });
+      pledgeCampaignPda = await initializeCampaign(pledgeCreator, Keypair.generate().publicKey, {
+        projectName: "Pledge Campaign",
+        description: "Pledge campaign that is collected in full",
+        fundingGoalLamports: new anchor.BN(1000 * PLEDGE_TOKEN),
+        nftName: "Pledge NFT",
+        nftSymbol: "PLDG",
+        nftUri: "https://arweave.net/pledge-nft-uri",
+        pledgeMint,
+      });
+      
+      // Pledges only approve the campaign as delegate
+      await pledge(failingPledgeCampaignPda, pledgeBacker, new anchor.BN(600 * PLEDGE_TOKEN));
+      await pledge(failingPledgeCampaignPda, pledgeRevoker, new anchor.BN(600 * PLEDGE_TOKEN));
+      await pledge(pledgeCampaignPda, pledgeSupporter, new anchor.BN(1000 * PLEDGE_TOKEN));
+      
+      const backerTokenAccount = await getAccount(
+        connection,
+        getAssociatedTokenAddressSync(pledgeMint, pledgeBacker.publicKey)
+      );
+      assert.equal(Number(backerTokenAccount.amount), 1000 * PLEDGE_TOKEN, "No tokens should move yet");
+      assert.equal(backerTokenAccount.delegate.toString(), failingPledgeCampaignPda.toString());
+      assert.equal(Number(backerTokenAccount.delegatedAmount), 600 * PLEDGE_TOKEN);
+      
+      // Pledges count towards the goal
+      const campaignAccount = await program.account.campaign.fetch(failingPledgeCampaignPda);
+      assert.ok(campaignAccount.raisedLamports.eq(new anchor.BN(1200 * PLEDGE_TOKEN)));
+      assert.equal(campaignAccount.isFunded, true, "Campaign should be marked as funded");
+      
+      const supporterFundingAccount = await program.account.supporterFunding.fetch(
+        findSupporterFundingPda(failingPledgeCampaignPda, pledgeBacker.publicKey)
+      );
+      assert.deepEqual(supporterFundingAccount.pledgeStatus, { pending: {} });
+      
+      // Test 1: Pledge campaigns don't take SOL
+      console.log("Test 1: Attempting to fund a pledge campaign with SOL (should fail)");
+      try {
+        await fundCampaign(failingPledgeCampaignPda, supporter, new anchor.BN(0.1 * LAMPORTS_PER_SOL));
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to fund a pledge campaign with SOL");
+      } catch (error) {
+        // Expected error - pledge campaign
+        console.log("Funding a pledge campaign correctly failed");
+        assert.ok(error.toString().includes("PledgeCampaign"));
+      }
+      
+      // Test 2: SOL campaigns don't take pledges
+      console.log("Test 2: Attempting to pledge to a SOL campaign (should fail)");
+      try {
+        await pledge(campaignPda, pledgeSupporter, new anchor.BN(1 * PLEDGE_TOKEN));
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to pledge to a SOL campaign");
+      } catch (error) {
+        // Expected error - not a pledge campaign
+        console.log("Pledge to a SOL campaign correctly failed");
+        assert.ok(error.toString().includes("NotPledgeCampaign"));
+      }
+      
+      // Test 3: Pledges are collected once the dispute window closes
+      console.log("Test 3: Attempting to collect pledges during the dispute window (should fail)");
+      try {
+        await collectPledges(failingPledgeCampaignPda, [pledgeBacker, pledgeRevoker]);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to collect pledges during the dispute window");
+      } catch (error) {
+        // Expected error - the dispute window is still open
+        console.log("Collecting during the dispute window correctly failed");
+        assert.ok(error.toString().includes("DisputeWindowOpen"));
+      }
+      
+      // The revoker withdraws their approval, so their pledge will fail to collect
+      await revoke(
+        connection,
+        wallet.payer,
+        getAssociatedTokenAddressSync(pledgeMint, pledgeRevoker.publicKey),
+        pledgeRevoker
+      );
+      
+      console.log("✅ Pledge test successful");
+    } catch (error) {
+      console.error("Error in pledge test:", error);
+      throw error;
+    }
+  });
+  
+  it("Lets supporters withdraw their contribution until the goal is reached", async () => {
+    const withdrawalCreator = Keypair.generate();
+    const withdrawingSupporter = Keypair.generate();
+    const fundingSupporter = Keypair.generate();
+    const withdrawalNftMint = Keypair.generate();
+    
+    // Fund accounts
+    await transferSol(wallet, withdrawalCreator.publicKey, 1 * LAMPORTS_PER_SOL);
+    await transferSol(wallet, withdrawingSupporter.publicKey, 1 * LAMPORTS_PER_SOL);
+    await transferSol(wallet, fundingSupporter.publicKey, 2 * LAMPORTS_PER_SOL);
+    
+    try {
+      const withdrawalCampaignPda = await initializeCampaign(withdrawalCreator, withdrawalNftMint.publicKey, {
+        projectName: "Withdrawal Test Campaign",
+        description: "Campaign for testing contribution withdrawals",
+        fundingGoalLamports: new anchor.BN(1 * LAMPORTS_PER_SOL),
+        nftName: "Withdrawal NFT",
+        nftSymbol: "WDRW",
+        nftUri: "https://arweave.net/withdrawal-nft-uri",
+      });
+      
+      // Helper function for a supporter to take back their contribution
+      const withdrawContribution = (campaignSupporter: Keypair) =>
+        program.methods
+          .withdrawContribution()
+          .accountsPartial({
+            campaign: withdrawalCampaignPda,
+            platformConfig: platformConfigPda,
+            supporterFunding: findSupporterFundingPda(withdrawalCampaignPda, campaignSupporter.publicKey),
+            dispute: findDisputePda(withdrawalCampaignPda, campaignSupporter.publicKey),
+            supporter: campaignSupporter.publicKey,
+            supporterProfile: findSupporterProfilePda(campaignSupporter.publicKey),
+            pledgeSource: null,
+            tokenProgram: null,
+          })
+          .signers([campaignSupporter])
+          .rpc();
+      
+      // The supporter contributes and changes their mind while the campaign runs
+      const fundAmount = new anchor.BN(0.4 * LAMPORTS_PER_SOL);
+      await fundCampaign(withdrawalCampaignPda, withdrawingSupporter, fundAmount);
+      
+      const supporterFundingPda = findSupporterFundingPda(withdrawalCampaignPda, withdrawingSupporter.publicKey);
+      const fundingRent = await provider.connection.getBalance(supporterFundingPda);
+      const supporterBalanceBefore = await provider.connection.getBalance(withdrawingSupporter.publicKey);
+      
+      await withdrawContribution(withdrawingSupporter);
+      
+      // The contribution comes back along with the rent of the closed funding record
+      const supporterBalanceAfter = await provider.connection.getBalance(withdrawingSupporter.publicKey);
+      assert.equal(supporterBalanceAfter - supporterBalanceBefore, fundAmount.toNumber() + fundingRent);
+      assert.equal(await program.account.supporterFunding.fetchNullable(supporterFundingPda), null);
+      
+      let campaignAccount = await program.account.campaign.fetch(withdrawalCampaignPda);
+      assert.ok(campaignAccount.raisedLamports.eqn(0));
+      assert.equal(campaignAccount.supportersCount, 0);
+      console.log("Contribution withdrawn");
+      
+      // Once the goal is reached, contributions stay in the campaign
+      await fundCampaign(withdrawalCampaignPda, fundingSupporter, new anchor.BN(1 * LAMPORTS_PER_SOL));
+      campaignAccount = await program.account.campaign.fetch(withdrawalCampaignPda);
+      assert.equal(campaignAccount.isFunded, true, "Campaign should be marked as funded");
+      
+      console.log("Attempting to withdraw from a funded campaign (should fail)");
+      try {
+        await withdrawContribution(fundingSupporter);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to withdraw a contribution from a funded campaign");
+      } catch (error) {
+        // Expected error - campaign is funded
+        console.log("Withdraw from a funded campaign correctly failed");
+        assert.ok(error.toString().includes("CampaignIsFunded"));
+      }
+      
+      console.log("✅ Contribution withdrawal test successful");
+    } catch (error) {
+      console.error("Error in contribution withdrawal test:", error);
+      throw error;
+    }
+  });
+  
+  it("Sets stretch goals and marks them reached as funding crosses them", async () => {
+    const stretchCreator = Keypair.generate();
+    const stretchSupporter = Keypair.generate();
+    const stretchNftMint = Keypair.generate();
+    
+    // Fund accounts
+    await transferSol(wallet, stretchCreator.publicKey, 1 * LAMPORTS_PER_SOL);
+    await transferSol(wallet, stretchSupporter.publicKey, 2 * LAMPORTS_PER_SOL);
+    
+    try {
+      const stretchCampaignPda = await initializeCampaign(stretchCreator, stretchNftMint.publicKey, {
+        projectName: "Stretch Goal Test Campaign",
+        description: "Campaign for testing stretch goals",
+        fundingGoalLamports: new anchor.BN(1 * LAMPORTS_PER_SOL),
+        nftName: "Stretch NFT",
+        nftSymbol: "STRG",
+        nftUri: "https://arweave.net/stretch-nft-uri",
+      });
+      
+      // Helper function to replace the campaign's stretch goals
+      const setStretchGoals = (signer: Keypair, goals: { thresholdLamports: anchor.BN; rewardUri: string }[]) =>
+        program.methods
+          .setStretchGoals(goals)
+          .accountsPartial({
+            campaign: stretchCampaignPda,
+            creator: signer.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([signer])
+          .rpc();
+      
+      const firstGoal = { thresholdLamports: new anchor.BN(1.5 * LAMPORTS_PER_SOL), rewardUri: "https://arweave.net/stretch-1-uri" };
+      const secondGoal = { thresholdLamports: new anchor.BN(2 * LAMPORTS_PER_SOL), rewardUri: "https://arweave.net/stretch-2-uri" };
+      
+      // Test 1: Only the creator can set stretch goals
+      console.log("Test 1: Attempting to set stretch goals as a supporter (should fail)");
+      try {
+        await setStretchGoals(stretchSupporter, [firstGoal]);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to set stretch goals as non-creator");
+      } catch (error) {
+        // Expected error - unauthorized
+        console.log("Stretch goals as non-creator correctly failed");
+        assert.ok(error.toString().includes("Unauthorized"));
+      }
+      
+      // Test 2: Stretch goals must be above the funding goal
+      console.log("Test 2: Attempting to set a stretch goal below the funding goal (should fail)");
+      try {
+        await setStretchGoals(stretchCreator, [
+          { thresholdLamports: new anchor.BN(0.5 * LAMPORTS_PER_SOL), rewardUri: "https://arweave.net/stretch-0-uri" },
+        ]);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to set a stretch goal below the funding goal");
+      } catch (error) {
+        // Expected error - invalid threshold
+        console.log("Stretch goal below the funding goal correctly failed");
+        assert.ok(error.toString().includes("InvalidStretchGoalThreshold"));
+      }
+      
+      // The creator announces two stretch goals
+      await setStretchGoals(stretchCreator, [firstGoal, secondGoal]);
+      let campaignAccount = await program.account.campaign.fetch(stretchCampaignPda);
+      assert.equal(campaignAccount.stretchGoals.length, 2);
+      assert.ok(campaignAccount.stretchGoals.every((goal) => goal.reachedAt.eqn(0)));
+      
+      // Funding past the first threshold reaches it, but not the second
+      await fundCampaign(stretchCampaignPda, stretchSupporter, new anchor.BN(1.6 * LAMPORTS_PER_SOL));
+      campaignAccount = await program.account.campaign.fetch(stretchCampaignPda);
+      assert.ok(campaignAccount.stretchGoals[0].reachedAt.gtn(0), "First stretch goal should be reached");
+      assert.ok(campaignAccount.stretchGoals[1].reachedAt.eqn(0), "Second stretch goal should not be reached");
+      console.log("First stretch goal reached");
+      
+      // Test 3: Reached goals can't be changed
+      console.log("Test 3: Attempting to change a reached stretch goal (should fail)");
+      try {
+        await setStretchGoals(stretchCreator, [
+          { ...firstGoal, thresholdLamports: new anchor.BN(1.7 * LAMPORTS_PER_SOL) },
+          secondGoal,
+        ]);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to change a reached stretch goal");
+      } catch (error) {
+        // Expected error - stretch goal locked
+        console.log("Changing a reached stretch goal correctly failed");
+        assert.ok(error.toString().includes("StretchGoalLocked"));
+      }
+      
+      // Goals not reached yet can still be dropped
+      await setStretchGoals(stretchCreator, [firstGoal]);
+      campaignAccount = await program.account.campaign.fetch(stretchCampaignPda);
+      assert.equal(campaignAccount.stretchGoals.length, 1);
+      assert.ok(campaignAccount.stretchGoals[0].reachedAt.gtn(0));
+      
+      console.log("✅ Stretch goal test successful");
+    } catch (error) {
+      console.error("Error in stretch goal test:", error);
+      throw error;
+    }
+  });
+  
+  // The tests below wait for the campaigns above to end and their dispute
+  // windows to close, which needs the shortened window set up by the admin
+  
+  it("Blocks withdrawals until the arbitrator resolves a dispute", async function () {
+    if (!admin) {
+      this.skip();
+    }
+    
+    try {
+      let campaignAccount = await program.account.campaign.fetch(disputeCampaignPda);
+      const disputePda = findDisputePda(disputeCampaignPda, disputeSupporter.publicKey);
+      
+      // The supporter disputes the campaign right after it ends
+      await waitUntilPast(campaignAccount.endTimestamp);
+      await fileDispute(disputeCampaignPda, disputeSupporter, "Rewards were never described");
+      
+      let disputeAccount = await program.account.dispute.fetch(disputePda);
+      assert.deepEqual(disputeAccount.status, { open: {} });
+      campaignAccount = await program.account.campaign.fetch(disputeCampaignPda);
+      assert.equal(campaignAccount.openDisputes, 1);
+      console.log("Dispute filed");
+      
+      // Test 1: The creator can't withdraw while the dispute is open, even after the deadline
+      await waitUntilPast(campaignAccount.disputeDeadline);
+      console.log("Test 1: Attempting to withdraw with an open dispute (should fail)");
+      try {
+        await withdrawFunds(disputeCampaignPda, disputeCreator);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to withdraw with an open dispute");
+      } catch (error) {
+        // Expected error - disputes open
+        console.log("Withdraw with an open dispute correctly failed");
+        assert.ok(error.toString().includes("DisputesOpen"));
+      }
+      
+      // Helper function to settle the dispute
+      const resolveDispute = (arbitrator: Keypair, resolution: object) =>
+        program.methods
+          .resolveDispute(resolution)
+          .accountsPartial({
+            campaign: disputeCampaignPda,
+            dispute: disputePda,
+            supporterFunding: findSupporterFundingPda(disputeCampaignPda, disputeSupporter.publicKey),
+            supporter: disputeSupporter.publicKey,
+            supporterProfile: findSupporterProfilePda(disputeSupporter.publicKey),
+            creatorProfile: findCreatorProfilePda(disputeCreator.publicKey),
+            arbitrator: arbitrator.publicKey,
+            platformConfig: platformConfigPda,
+          })
+          .signers([arbitrator])
+          .rpc();
+      
+      // Test 2: Only the arbitrator or admin can resolve disputes
+      console.log("Test 2: Attempting to resolve the dispute as the creator (should fail)");
+      try {
+        await resolveDispute(disputeCreator, { release: {} });
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to resolve a dispute as non-arbitrator");
+      } catch (error) {
+        // Expected error - unauthorized
+        console.log("Resolve as non-arbitrator correctly failed");
+        assert.ok(error.toString().includes("Unauthorized"));
+      }
+      
+      // The arbitrator upholds the dispute and the supporter is refunded
+      const supporterBalanceBefore = await provider.connection.getBalance(disputeSupporter.publicKey);
+      await resolveDispute(admin, { refund: {} });
+      const supporterBalanceAfter = await provider.connection.getBalance(disputeSupporter.publicKey);
+      
+      assert.equal(supporterBalanceAfter - supporterBalanceBefore, 0.5 * LAMPORTS_PER_SOL, "Supporter should get their contribution back");
+      disputeAccount = await program.account.dispute.fetch(disputePda);
+      assert.deepEqual(disputeAccount.status, { refunded: {} });
+      campaignAccount = await program.account.campaign.fetch(disputeCampaignPda);
+      assert.equal(campaignAccount.openDisputes, 0);
+      assert.equal(campaignAccount.isFunded, true, "Campaign should still be funded by the backer");
+      console.log("Dispute resolved with a refund");
+      
+      // Test 3: A dispute is resolved only once
+      console.log("Test 3: Attempting to resolve the dispute again (should fail)");
+      try {
+        await resolveDispute(admin, { release: {} });
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to resolve a dispute twice");
+      } catch (error) {
+        // Expected error - already resolved
+        console.log("Second resolve correctly failed");
+        assert.ok(error.toString().includes("DisputeAlreadyResolved"));
+      }
+      
+      // With no disputes open, the creator withdraws the backer's contribution
+      const creatorBalanceBefore = await provider.connection.getBalance(disputeCreator.publicKey);
+      await withdrawFunds(disputeCampaignPda, disputeCreator);
+      const creatorBalanceAfter = await provider.connection.getBalance(disputeCreator.publicKey);
+      assert.ok(creatorBalanceAfter > creatorBalanceBefore);
+      
+      console.log("✅ Dispute test successful");
+    } catch (error) {
+      console.error("Error in dispute test:", error);
+      throw error;
+    }
+  });
+  
+  it("Creator withdraws funds once the dispute window closes", async function () {
+    if (!admin) {
+      this.skip();
+    }
+    
+    try {
+      const campaignAccount = await program.account.campaign.fetch(campaignPda);
+      await waitUntilPast(campaignAccount.disputeDeadline);
+      
+      // Get creator's balance before withdrawal
+      const creatorBalanceBefore = await provider.connection.getBalance(creator.publicKey);
+      console.log(`Creator balance before withdrawal: ${creatorBalanceBefore / LAMPORTS_PER_SOL} SOL`);
+      
+      // Creator withdraws funds
+      const withdrawTx = await withdrawFunds(campaignPda, creator);
+        
+      console.log("Withdrawal transaction signature:", withdrawTx);
+      
+      // Get creator's balance after withdrawal
+      const creatorBalanceAfter = await provider.connection.getBalance(creator.publicKey);
+      console.log(`Creator balance after withdrawal: ${creatorBalanceAfter / LAMPORTS_PER_SOL} SOL`);
+      
+      // Verify creator received the funds
+      assert.ok(creatorBalanceAfter > creatorBalanceBefore);
+      
+    } catch (error) {
+      console.error("Error in withdrawal test:", error);
+      throw error;
+    }
+  });
+  
+  // Test the treasury fee collection and admin withdrawal functionality
+  it("Tests platform fee collection and admin treasury withdrawal", async function () {
+    if (!admin) {
+      this.skip();
+    }
+    
+    try {
+      const campaignAccount = await program.account.campaign.fetch(feeCampaignPda);
+      await waitUntilPast(campaignAccount.disputeDeadline);
+      
+      // Get creator and treasury balances before withdrawal
+      const creatorBalanceBefore = await connection.getBalance(feeCreator.publicKey);
+      const treasuryBalanceBefore = await connection.getBalance(treasuryPda);
+      
+      console.log("Creator balance before withdrawal:", creatorBalanceBefore / LAMPORTS_PER_SOL, "SOL");
+      console.log("Treasury balance before withdrawal:", treasuryBalanceBefore / LAMPORTS_PER_SOL, "SOL");
+      
+      // Creator withdraws funds - 2.5% should go to treasury
+      await withdrawFunds(feeCampaignPda, feeCreator);
+        
+      console.log("Funds withdrawn by creator");
+      
+      // Get updated balances
+      const creatorBalanceAfter = await connection.getBalance(feeCreator.publicKey);
+      const treasuryBalanceAfter = await connection.getBalance(treasuryPda);
+      
+      console.log("Creator balance after withdrawal:", creatorBalanceAfter / LAMPORTS_PER_SOL, "SOL");
+      console.log("Treasury balance after withdrawal:", treasuryBalanceAfter / LAMPORTS_PER_SOL, "SOL");
+      
+      // Calculate expected values
+      const expectedFee = (2 * LAMPORTS_PER_SOL) * PLATFORM_FEE_NUMERATOR / PLATFORM_FEE_DENOMINATOR;
+      const expectedCreatorAmount = (2 * LAMPORTS_PER_SOL) - expectedFee;
+      
+      // Verify creator received proper amount (97.5% of funds)
+      const creatorReceived = creatorBalanceAfter - creatorBalanceBefore;
+      assert(
+        Math.abs(creatorReceived - expectedCreatorAmount) < 10000, // Allow small difference for gas fees
+        `Creator should receive ~${expectedCreatorAmount / LAMPORTS_PER_SOL} SOL, got ${creatorReceived / LAMPORTS_PER_SOL} SOL`
+      );
+      
+      // Verify treasury received the fee (2.5% of funds)
+      const treasuryReceived = treasuryBalanceAfter - treasuryBalanceBefore;
+      assert(
+        Math.abs(treasuryReceived - expectedFee) < 1000, // Allow small difference
+        `Treasury should receive ~${expectedFee / LAMPORTS_PER_SOL} SOL, got ${treasuryReceived / LAMPORTS_PER_SOL} SOL`
+      );
+      
+      console.log("✅ Platform fee test successful - treasury received the 2.5% fee");
+      
+      // Create an admin keypair (you would use your real admin keypair in practice)
+      // For testing, we'll create one with the known private key
+      const adminKeypair = anchor.web3.Keypair.generate();
+      console.log("Test admin pubkey:", adminKeypair.publicKey.toString());
+      
+      // Fund the admin account
+      await transferSol(wallet, adminKeypair.publicKey, 0.1 * LAMPORTS_PER_SOL);
+      
+      // In a real test, the admin would withdraw funds from treasury
+      // But our test environment can't create the specific admin keypair from lib.rs
+      
+      console.log("In a real environment, admin would call withdraw_treasury to withdraw fees");
+      console.log("To test admin withdrawal, use the CLI with the correct admin keypair");
+      
+      // NOTE: For an actual test with the real admin, you would do:
+      /*
+      await program.methods
+        .withdrawTreasury(new anchor.BN(expectedFee))
+        .accounts({
+          admin: new PublicKey(ADMIN_PUBKEY),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([adminKeypair]) // This would be the real admin keypair
+        .rpc();
+       */
+       
+       console.log("✅ Treasury fee collection test successful");
+    } catch (error) {
+      console.error("Error in treasury fee test:", error);
+      throw error;
+    }
+  });
+  
+  it("Fails a pledge campaign that loses a pledge at collection and refunds the collected ones", async function () {
+    if (!admin) {
+      this.skip();
+    }
+    
+    try {
+      let campaignAccount = await program.account.campaign.fetch(failingPledgeCampaignPda);
+      await waitUntilPast(campaignAccount.disputeDeadline);
+      
+      // The backer's pledge is collected; the revoked one fails and sinks the campaign below its goal
+      await collectPledges(failingPledgeCampaignPda, [pledgeBacker, pledgeRevoker]);
+      
+      campaignAccount = await program.account.campaign.fetch(failingPledgeCampaignPda);
+      assert.equal(campaignAccount.isFunded, false, "Campaign should fail below its goal");
+      assert.ok(campaignAccount.raisedLamports.eq(new anchor.BN(600 * PLEDGE_TOKEN)));
+      
+      const backerFundingPda = findSupporterFundingPda(failingPledgeCampaignPda, pledgeBacker.publicKey);
+      const revokerFundingPda = findSupporterFundingPda(failingPledgeCampaignPda, pledgeRevoker.publicKey);
+      assert.deepEqual((await program.account.supporterFunding.fetch(backerFundingPda)).pledgeStatus, { collected: {} });
+      assert.deepEqual((await program.account.supporterFunding.fetch(revokerFundingPda)).pledgeStatus, { failed: {} });
+      
+      const backerTokenAccountPda = getAssociatedTokenAddressSync(pledgeMint, pledgeBacker.publicKey);
+      let backerTokenAccount = await getAccount(connection, backerTokenAccountPda);
+      assert.equal(Number(backerTokenAccount.amount), 400 * PLEDGE_TOKEN);
+      
+      // Helper function to claim a pledge refund from the campaign's escrow
+      const claimPledgeRefund = (pledger: Keypair) =>
+        program.methods
+          .claimRefund()
+          .accountsPartial({
+            campaign: failingPledgeCampaignPda,
+            supporterFunding: findSupporterFundingPda(failingPledgeCampaignPda, pledger.publicKey),
+            supporter: pledger.publicKey,
+            creatorProfile: findCreatorProfilePda(pledgeCreator.publicKey),
+            supporterProfile: findSupporterProfilePda(pledger.publicKey),
+            shareMint: null,
+            supporterShareAccount: null,
+            pledgeEscrow: getAssociatedTokenAddressSync(pledgeMint, failingPledgeCampaignPda, true),
+            pledgeSource: getAssociatedTokenAddressSync(pledgeMint, pledger.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([pledger])
+          .rpc();
+      
+      // The collected pledge is returned from escrow
+      await claimPledgeRefund(pledgeBacker);
+      backerTokenAccount = await getAccount(connection, backerTokenAccountPda);
+      assert.equal(Number(backerTokenAccount.amount), 1000 * PLEDGE_TOKEN);
+      assert.equal((await program.account.supporterFunding.fetch(backerFundingPda)).isClaimed, true);
+      
+      // The failed pledge was never charged, so there is nothing to refund
+      console.log("Attempting to refund a failed pledge (should fail)");
+      try {
+        await claimPledgeRefund(pledgeRevoker);
+        
+        // If we reach here, the test failed
+        assert.fail("Should not be able to refund a pledge that was never collected");
+      } catch (error) {
+        // Expected error - pledge not refundable
+        console.log("Refund of a failed pledge correctly failed");
+        assert.ok(error.toString().includes("PledgeNotRefundable"));
+      }
+      
+      console.log("✅ Pledge refund test successful");
+    } catch (error) {
+      console.error("Error in pledge refund test:", error);
+      throw error;
+    }
+  });
+  
+  it("Collects the pledges of a successful campaign and releases them to the creator", async function () {
+    if (!admin) {
+      this.skip();
+    }
+    
+    try {
+      let campaignAccount = await program.account.campaign.fetch(pledgeCampaignPda);
+      await waitUntilPast(campaignAccount.disputeDeadline);
+      
+      await collectPledges(pledgeCampaignPda, [pledgeSupporter]);
+      
+      campaignAccount = await program.account.campaign.fetch(pledgeCampaignPda);
+      assert.equal(campaignAccount.isFunded, true);
+      assert.equal(campaignAccount.outcomeRecorded, true);
+      
+      // The escrow is released to the creator minus the 2.5% platform fee
+      const raised = 1000 * PLEDGE_TOKEN;
+      const expectedFee = raised * PLATFORM_FEE_NUMERATOR / PLATFORM_FEE_DENOMINATOR;
+      
+      const creatorTokenAccount = await getAccount(
+        connection,
+        getAssociatedTokenAddressSync(pledgeMint, pledgeCreator.publicKey)
+      );
+      const treasuryTokenAccount = await getAccount(
+        connection,
+        getAssociatedTokenAddressSync(pledgeMint, treasuryPda, true)
+      );
+      const supporterTokenAccount = await getAccount(
+        connection,
+        getAssociatedTokenAddressSync(pledgeMint, pledgeSupporter.publicKey)
+      );
+      assert.equal(Number(creatorTokenAccount.amount), raised - expectedFee);
+      assert.equal(Number(treasuryTokenAccount.amount), expectedFee);
+      assert.equal(Number(supporterTokenAccount.amount), 0);
+      
+      console.log("✅ Pledge collection test successful");
+    } catch (error) {
+      console.error("Error in pledge collection test:", error);
+      throw error;
+    }
+  });
+});